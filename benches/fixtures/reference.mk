@@ -0,0 +1,4402 @@
+// A reference program for the interpreter benchmarks: many small sections of bindings,
+// functions, collections and loops, each checking its own result.
+
+// Section 1
+let scale_1 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 1) {
+        doubled - 1
+    } else {
+        doubled + 1
+    }
+};
+let items_1 = [1, 2, 3, 4];
+let table_1 = {"name": "section 1", "size": len(items_1), "last": last(items_1)};
+let sum_1 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_1(rest(values), acc + first(values)) }
+};
+let count_1 = 0;
+while (count_1 < table_1["size"]) {
+    count_1 = count_1 + 1;
+}
+assert_eq(sum_1(items_1, 0), 10);
+assert_eq(scale_1(table_1["last"]) + count_1, 11);
+let ratio_1 = 1.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 2
+let scale_2 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 2) {
+        doubled - 2
+    } else {
+        doubled + 2
+    }
+};
+let items_2 = [2, 3, 4, 5];
+let table_2 = {"name": "section 2", "size": len(items_2), "last": last(items_2)};
+let sum_2 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_2(rest(values), acc + first(values)) }
+};
+let count_2 = 0;
+while (count_2 < table_2["size"]) {
+    count_2 = count_2 + 1;
+}
+assert_eq(sum_2(items_2, 0), 14);
+assert_eq(scale_2(table_2["last"]) + count_2, 12);
+let ratio_2 = 2.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 3
+let scale_3 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 3) {
+        doubled - 3
+    } else {
+        doubled + 3
+    }
+};
+let items_3 = [3, 4, 5, 6];
+let table_3 = {"name": "section 3", "size": len(items_3), "last": last(items_3)};
+let sum_3 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_3(rest(values), acc + first(values)) }
+};
+let count_3 = 0;
+while (count_3 < table_3["size"]) {
+    count_3 = count_3 + 1;
+}
+assert_eq(sum_3(items_3, 0), 18);
+assert_eq(scale_3(table_3["last"]) + count_3, 13);
+let ratio_3 = 3.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 4
+let scale_4 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 4) {
+        doubled - 4
+    } else {
+        doubled + 4
+    }
+};
+let items_4 = [4, 5, 6, 7];
+let table_4 = {"name": "section 4", "size": len(items_4), "last": last(items_4)};
+let sum_4 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_4(rest(values), acc + first(values)) }
+};
+let count_4 = 0;
+while (count_4 < table_4["size"]) {
+    count_4 = count_4 + 1;
+}
+assert_eq(sum_4(items_4, 0), 22);
+assert_eq(scale_4(table_4["last"]) + count_4, 14);
+let ratio_4 = 4.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 5
+let scale_5 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 5) {
+        doubled - 5
+    } else {
+        doubled + 5
+    }
+};
+let items_5 = [5, 6, 7, 8];
+let table_5 = {"name": "section 5", "size": len(items_5), "last": last(items_5)};
+let sum_5 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_5(rest(values), acc + first(values)) }
+};
+let count_5 = 0;
+while (count_5 < table_5["size"]) {
+    count_5 = count_5 + 1;
+}
+assert_eq(sum_5(items_5, 0), 26);
+assert_eq(scale_5(table_5["last"]) + count_5, 15);
+let ratio_5 = 5.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 6
+let scale_6 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 6) {
+        doubled - 6
+    } else {
+        doubled + 6
+    }
+};
+let items_6 = [6, 7, 8, 9];
+let table_6 = {"name": "section 6", "size": len(items_6), "last": last(items_6)};
+let sum_6 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_6(rest(values), acc + first(values)) }
+};
+let count_6 = 0;
+while (count_6 < table_6["size"]) {
+    count_6 = count_6 + 1;
+}
+assert_eq(sum_6(items_6, 0), 30);
+assert_eq(scale_6(table_6["last"]) + count_6, 16);
+let ratio_6 = 6.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 7
+let scale_7 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 7) {
+        doubled - 7
+    } else {
+        doubled + 7
+    }
+};
+let items_7 = [7, 8, 9, 10];
+let table_7 = {"name": "section 7", "size": len(items_7), "last": last(items_7)};
+let sum_7 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_7(rest(values), acc + first(values)) }
+};
+let count_7 = 0;
+while (count_7 < table_7["size"]) {
+    count_7 = count_7 + 1;
+}
+assert_eq(sum_7(items_7, 0), 34);
+assert_eq(scale_7(table_7["last"]) + count_7, 17);
+let ratio_7 = 7.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 8
+let scale_8 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 8) {
+        doubled - 8
+    } else {
+        doubled + 8
+    }
+};
+let items_8 = [8, 9, 10, 11];
+let table_8 = {"name": "section 8", "size": len(items_8), "last": last(items_8)};
+let sum_8 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_8(rest(values), acc + first(values)) }
+};
+let count_8 = 0;
+while (count_8 < table_8["size"]) {
+    count_8 = count_8 + 1;
+}
+assert_eq(sum_8(items_8, 0), 38);
+assert_eq(scale_8(table_8["last"]) + count_8, 18);
+let ratio_8 = 8.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 9
+let scale_9 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 9) {
+        doubled - 9
+    } else {
+        doubled + 9
+    }
+};
+let items_9 = [9, 10, 11, 12];
+let table_9 = {"name": "section 9", "size": len(items_9), "last": last(items_9)};
+let sum_9 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_9(rest(values), acc + first(values)) }
+};
+let count_9 = 0;
+while (count_9 < table_9["size"]) {
+    count_9 = count_9 + 1;
+}
+assert_eq(sum_9(items_9, 0), 42);
+assert_eq(scale_9(table_9["last"]) + count_9, 19);
+let ratio_9 = 9.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 10
+let scale_10 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 10) {
+        doubled - 10
+    } else {
+        doubled + 10
+    }
+};
+let items_10 = [10, 11, 12, 13];
+let table_10 = {"name": "section 10", "size": len(items_10), "last": last(items_10)};
+let sum_10 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_10(rest(values), acc + first(values)) }
+};
+let count_10 = 0;
+while (count_10 < table_10["size"]) {
+    count_10 = count_10 + 1;
+}
+assert_eq(sum_10(items_10, 0), 46);
+assert_eq(scale_10(table_10["last"]) + count_10, 20);
+let ratio_10 = 10.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 11
+let scale_11 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 11) {
+        doubled - 11
+    } else {
+        doubled + 11
+    }
+};
+let items_11 = [11, 12, 13, 14];
+let table_11 = {"name": "section 11", "size": len(items_11), "last": last(items_11)};
+let sum_11 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_11(rest(values), acc + first(values)) }
+};
+let count_11 = 0;
+while (count_11 < table_11["size"]) {
+    count_11 = count_11 + 1;
+}
+assert_eq(sum_11(items_11, 0), 50);
+assert_eq(scale_11(table_11["last"]) + count_11, 21);
+let ratio_11 = 11.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 12
+let scale_12 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 12) {
+        doubled - 12
+    } else {
+        doubled + 12
+    }
+};
+let items_12 = [12, 13, 14, 15];
+let table_12 = {"name": "section 12", "size": len(items_12), "last": last(items_12)};
+let sum_12 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_12(rest(values), acc + first(values)) }
+};
+let count_12 = 0;
+while (count_12 < table_12["size"]) {
+    count_12 = count_12 + 1;
+}
+assert_eq(sum_12(items_12, 0), 54);
+assert_eq(scale_12(table_12["last"]) + count_12, 22);
+let ratio_12 = 12.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 13
+let scale_13 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 13) {
+        doubled - 13
+    } else {
+        doubled + 13
+    }
+};
+let items_13 = [13, 14, 15, 16];
+let table_13 = {"name": "section 13", "size": len(items_13), "last": last(items_13)};
+let sum_13 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_13(rest(values), acc + first(values)) }
+};
+let count_13 = 0;
+while (count_13 < table_13["size"]) {
+    count_13 = count_13 + 1;
+}
+assert_eq(sum_13(items_13, 0), 58);
+assert_eq(scale_13(table_13["last"]) + count_13, 23);
+let ratio_13 = 13.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 14
+let scale_14 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 14) {
+        doubled - 14
+    } else {
+        doubled + 14
+    }
+};
+let items_14 = [14, 15, 16, 17];
+let table_14 = {"name": "section 14", "size": len(items_14), "last": last(items_14)};
+let sum_14 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_14(rest(values), acc + first(values)) }
+};
+let count_14 = 0;
+while (count_14 < table_14["size"]) {
+    count_14 = count_14 + 1;
+}
+assert_eq(sum_14(items_14, 0), 62);
+assert_eq(scale_14(table_14["last"]) + count_14, 24);
+let ratio_14 = 14.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 15
+let scale_15 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 15) {
+        doubled - 15
+    } else {
+        doubled + 15
+    }
+};
+let items_15 = [15, 16, 17, 18];
+let table_15 = {"name": "section 15", "size": len(items_15), "last": last(items_15)};
+let sum_15 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_15(rest(values), acc + first(values)) }
+};
+let count_15 = 0;
+while (count_15 < table_15["size"]) {
+    count_15 = count_15 + 1;
+}
+assert_eq(sum_15(items_15, 0), 66);
+assert_eq(scale_15(table_15["last"]) + count_15, 25);
+let ratio_15 = 15.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 16
+let scale_16 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 16) {
+        doubled - 16
+    } else {
+        doubled + 16
+    }
+};
+let items_16 = [16, 17, 18, 19];
+let table_16 = {"name": "section 16", "size": len(items_16), "last": last(items_16)};
+let sum_16 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_16(rest(values), acc + first(values)) }
+};
+let count_16 = 0;
+while (count_16 < table_16["size"]) {
+    count_16 = count_16 + 1;
+}
+assert_eq(sum_16(items_16, 0), 70);
+assert_eq(scale_16(table_16["last"]) + count_16, 26);
+let ratio_16 = 16.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 17
+let scale_17 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 17) {
+        doubled - 17
+    } else {
+        doubled + 17
+    }
+};
+let items_17 = [17, 18, 19, 20];
+let table_17 = {"name": "section 17", "size": len(items_17), "last": last(items_17)};
+let sum_17 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_17(rest(values), acc + first(values)) }
+};
+let count_17 = 0;
+while (count_17 < table_17["size"]) {
+    count_17 = count_17 + 1;
+}
+assert_eq(sum_17(items_17, 0), 74);
+assert_eq(scale_17(table_17["last"]) + count_17, 27);
+let ratio_17 = 17.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 18
+let scale_18 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 18) {
+        doubled - 18
+    } else {
+        doubled + 18
+    }
+};
+let items_18 = [18, 19, 20, 21];
+let table_18 = {"name": "section 18", "size": len(items_18), "last": last(items_18)};
+let sum_18 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_18(rest(values), acc + first(values)) }
+};
+let count_18 = 0;
+while (count_18 < table_18["size"]) {
+    count_18 = count_18 + 1;
+}
+assert_eq(sum_18(items_18, 0), 78);
+assert_eq(scale_18(table_18["last"]) + count_18, 28);
+let ratio_18 = 18.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 19
+let scale_19 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 19) {
+        doubled - 19
+    } else {
+        doubled + 19
+    }
+};
+let items_19 = [19, 20, 21, 22];
+let table_19 = {"name": "section 19", "size": len(items_19), "last": last(items_19)};
+let sum_19 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_19(rest(values), acc + first(values)) }
+};
+let count_19 = 0;
+while (count_19 < table_19["size"]) {
+    count_19 = count_19 + 1;
+}
+assert_eq(sum_19(items_19, 0), 82);
+assert_eq(scale_19(table_19["last"]) + count_19, 29);
+let ratio_19 = 19.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 20
+let scale_20 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 20) {
+        doubled - 20
+    } else {
+        doubled + 20
+    }
+};
+let items_20 = [20, 21, 22, 23];
+let table_20 = {"name": "section 20", "size": len(items_20), "last": last(items_20)};
+let sum_20 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_20(rest(values), acc + first(values)) }
+};
+let count_20 = 0;
+while (count_20 < table_20["size"]) {
+    count_20 = count_20 + 1;
+}
+assert_eq(sum_20(items_20, 0), 86);
+assert_eq(scale_20(table_20["last"]) + count_20, 30);
+let ratio_20 = 20.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 21
+let scale_21 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 21) {
+        doubled - 21
+    } else {
+        doubled + 21
+    }
+};
+let items_21 = [21, 22, 23, 24];
+let table_21 = {"name": "section 21", "size": len(items_21), "last": last(items_21)};
+let sum_21 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_21(rest(values), acc + first(values)) }
+};
+let count_21 = 0;
+while (count_21 < table_21["size"]) {
+    count_21 = count_21 + 1;
+}
+assert_eq(sum_21(items_21, 0), 90);
+assert_eq(scale_21(table_21["last"]) + count_21, 31);
+let ratio_21 = 21.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 22
+let scale_22 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 22) {
+        doubled - 22
+    } else {
+        doubled + 22
+    }
+};
+let items_22 = [22, 23, 24, 25];
+let table_22 = {"name": "section 22", "size": len(items_22), "last": last(items_22)};
+let sum_22 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_22(rest(values), acc + first(values)) }
+};
+let count_22 = 0;
+while (count_22 < table_22["size"]) {
+    count_22 = count_22 + 1;
+}
+assert_eq(sum_22(items_22, 0), 94);
+assert_eq(scale_22(table_22["last"]) + count_22, 32);
+let ratio_22 = 22.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 23
+let scale_23 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 23) {
+        doubled - 23
+    } else {
+        doubled + 23
+    }
+};
+let items_23 = [23, 24, 25, 26];
+let table_23 = {"name": "section 23", "size": len(items_23), "last": last(items_23)};
+let sum_23 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_23(rest(values), acc + first(values)) }
+};
+let count_23 = 0;
+while (count_23 < table_23["size"]) {
+    count_23 = count_23 + 1;
+}
+assert_eq(sum_23(items_23, 0), 98);
+assert_eq(scale_23(table_23["last"]) + count_23, 33);
+let ratio_23 = 23.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 24
+let scale_24 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 24) {
+        doubled - 24
+    } else {
+        doubled + 24
+    }
+};
+let items_24 = [24, 25, 26, 27];
+let table_24 = {"name": "section 24", "size": len(items_24), "last": last(items_24)};
+let sum_24 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_24(rest(values), acc + first(values)) }
+};
+let count_24 = 0;
+while (count_24 < table_24["size"]) {
+    count_24 = count_24 + 1;
+}
+assert_eq(sum_24(items_24, 0), 102);
+assert_eq(scale_24(table_24["last"]) + count_24, 34);
+let ratio_24 = 24.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 25
+let scale_25 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 25) {
+        doubled - 25
+    } else {
+        doubled + 25
+    }
+};
+let items_25 = [25, 26, 27, 28];
+let table_25 = {"name": "section 25", "size": len(items_25), "last": last(items_25)};
+let sum_25 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_25(rest(values), acc + first(values)) }
+};
+let count_25 = 0;
+while (count_25 < table_25["size"]) {
+    count_25 = count_25 + 1;
+}
+assert_eq(sum_25(items_25, 0), 106);
+assert_eq(scale_25(table_25["last"]) + count_25, 35);
+let ratio_25 = 25.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 26
+let scale_26 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 26) {
+        doubled - 26
+    } else {
+        doubled + 26
+    }
+};
+let items_26 = [26, 27, 28, 29];
+let table_26 = {"name": "section 26", "size": len(items_26), "last": last(items_26)};
+let sum_26 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_26(rest(values), acc + first(values)) }
+};
+let count_26 = 0;
+while (count_26 < table_26["size"]) {
+    count_26 = count_26 + 1;
+}
+assert_eq(sum_26(items_26, 0), 110);
+assert_eq(scale_26(table_26["last"]) + count_26, 36);
+let ratio_26 = 26.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 27
+let scale_27 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 27) {
+        doubled - 27
+    } else {
+        doubled + 27
+    }
+};
+let items_27 = [27, 28, 29, 30];
+let table_27 = {"name": "section 27", "size": len(items_27), "last": last(items_27)};
+let sum_27 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_27(rest(values), acc + first(values)) }
+};
+let count_27 = 0;
+while (count_27 < table_27["size"]) {
+    count_27 = count_27 + 1;
+}
+assert_eq(sum_27(items_27, 0), 114);
+assert_eq(scale_27(table_27["last"]) + count_27, 37);
+let ratio_27 = 27.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 28
+let scale_28 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 28) {
+        doubled - 28
+    } else {
+        doubled + 28
+    }
+};
+let items_28 = [28, 29, 30, 31];
+let table_28 = {"name": "section 28", "size": len(items_28), "last": last(items_28)};
+let sum_28 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_28(rest(values), acc + first(values)) }
+};
+let count_28 = 0;
+while (count_28 < table_28["size"]) {
+    count_28 = count_28 + 1;
+}
+assert_eq(sum_28(items_28, 0), 118);
+assert_eq(scale_28(table_28["last"]) + count_28, 38);
+let ratio_28 = 28.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 29
+let scale_29 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 29) {
+        doubled - 29
+    } else {
+        doubled + 29
+    }
+};
+let items_29 = [29, 30, 31, 32];
+let table_29 = {"name": "section 29", "size": len(items_29), "last": last(items_29)};
+let sum_29 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_29(rest(values), acc + first(values)) }
+};
+let count_29 = 0;
+while (count_29 < table_29["size"]) {
+    count_29 = count_29 + 1;
+}
+assert_eq(sum_29(items_29, 0), 122);
+assert_eq(scale_29(table_29["last"]) + count_29, 39);
+let ratio_29 = 29.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 30
+let scale_30 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 30) {
+        doubled - 30
+    } else {
+        doubled + 30
+    }
+};
+let items_30 = [30, 31, 32, 33];
+let table_30 = {"name": "section 30", "size": len(items_30), "last": last(items_30)};
+let sum_30 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_30(rest(values), acc + first(values)) }
+};
+let count_30 = 0;
+while (count_30 < table_30["size"]) {
+    count_30 = count_30 + 1;
+}
+assert_eq(sum_30(items_30, 0), 126);
+assert_eq(scale_30(table_30["last"]) + count_30, 40);
+let ratio_30 = 30.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 31
+let scale_31 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 31) {
+        doubled - 31
+    } else {
+        doubled + 31
+    }
+};
+let items_31 = [31, 32, 33, 34];
+let table_31 = {"name": "section 31", "size": len(items_31), "last": last(items_31)};
+let sum_31 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_31(rest(values), acc + first(values)) }
+};
+let count_31 = 0;
+while (count_31 < table_31["size"]) {
+    count_31 = count_31 + 1;
+}
+assert_eq(sum_31(items_31, 0), 130);
+assert_eq(scale_31(table_31["last"]) + count_31, 41);
+let ratio_31 = 31.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 32
+let scale_32 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 32) {
+        doubled - 32
+    } else {
+        doubled + 32
+    }
+};
+let items_32 = [32, 33, 34, 35];
+let table_32 = {"name": "section 32", "size": len(items_32), "last": last(items_32)};
+let sum_32 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_32(rest(values), acc + first(values)) }
+};
+let count_32 = 0;
+while (count_32 < table_32["size"]) {
+    count_32 = count_32 + 1;
+}
+assert_eq(sum_32(items_32, 0), 134);
+assert_eq(scale_32(table_32["last"]) + count_32, 42);
+let ratio_32 = 32.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 33
+let scale_33 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 33) {
+        doubled - 33
+    } else {
+        doubled + 33
+    }
+};
+let items_33 = [33, 34, 35, 36];
+let table_33 = {"name": "section 33", "size": len(items_33), "last": last(items_33)};
+let sum_33 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_33(rest(values), acc + first(values)) }
+};
+let count_33 = 0;
+while (count_33 < table_33["size"]) {
+    count_33 = count_33 + 1;
+}
+assert_eq(sum_33(items_33, 0), 138);
+assert_eq(scale_33(table_33["last"]) + count_33, 43);
+let ratio_33 = 33.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 34
+let scale_34 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 34) {
+        doubled - 34
+    } else {
+        doubled + 34
+    }
+};
+let items_34 = [34, 35, 36, 37];
+let table_34 = {"name": "section 34", "size": len(items_34), "last": last(items_34)};
+let sum_34 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_34(rest(values), acc + first(values)) }
+};
+let count_34 = 0;
+while (count_34 < table_34["size"]) {
+    count_34 = count_34 + 1;
+}
+assert_eq(sum_34(items_34, 0), 142);
+assert_eq(scale_34(table_34["last"]) + count_34, 44);
+let ratio_34 = 34.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 35
+let scale_35 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 35) {
+        doubled - 35
+    } else {
+        doubled + 35
+    }
+};
+let items_35 = [35, 36, 37, 38];
+let table_35 = {"name": "section 35", "size": len(items_35), "last": last(items_35)};
+let sum_35 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_35(rest(values), acc + first(values)) }
+};
+let count_35 = 0;
+while (count_35 < table_35["size"]) {
+    count_35 = count_35 + 1;
+}
+assert_eq(sum_35(items_35, 0), 146);
+assert_eq(scale_35(table_35["last"]) + count_35, 45);
+let ratio_35 = 35.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 36
+let scale_36 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 36) {
+        doubled - 36
+    } else {
+        doubled + 36
+    }
+};
+let items_36 = [36, 37, 38, 39];
+let table_36 = {"name": "section 36", "size": len(items_36), "last": last(items_36)};
+let sum_36 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_36(rest(values), acc + first(values)) }
+};
+let count_36 = 0;
+while (count_36 < table_36["size"]) {
+    count_36 = count_36 + 1;
+}
+assert_eq(sum_36(items_36, 0), 150);
+assert_eq(scale_36(table_36["last"]) + count_36, 46);
+let ratio_36 = 36.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 37
+let scale_37 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 37) {
+        doubled - 37
+    } else {
+        doubled + 37
+    }
+};
+let items_37 = [37, 38, 39, 40];
+let table_37 = {"name": "section 37", "size": len(items_37), "last": last(items_37)};
+let sum_37 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_37(rest(values), acc + first(values)) }
+};
+let count_37 = 0;
+while (count_37 < table_37["size"]) {
+    count_37 = count_37 + 1;
+}
+assert_eq(sum_37(items_37, 0), 154);
+assert_eq(scale_37(table_37["last"]) + count_37, 47);
+let ratio_37 = 37.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 38
+let scale_38 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 38) {
+        doubled - 38
+    } else {
+        doubled + 38
+    }
+};
+let items_38 = [38, 39, 40, 41];
+let table_38 = {"name": "section 38", "size": len(items_38), "last": last(items_38)};
+let sum_38 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_38(rest(values), acc + first(values)) }
+};
+let count_38 = 0;
+while (count_38 < table_38["size"]) {
+    count_38 = count_38 + 1;
+}
+assert_eq(sum_38(items_38, 0), 158);
+assert_eq(scale_38(table_38["last"]) + count_38, 48);
+let ratio_38 = 38.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 39
+let scale_39 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 39) {
+        doubled - 39
+    } else {
+        doubled + 39
+    }
+};
+let items_39 = [39, 40, 41, 42];
+let table_39 = {"name": "section 39", "size": len(items_39), "last": last(items_39)};
+let sum_39 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_39(rest(values), acc + first(values)) }
+};
+let count_39 = 0;
+while (count_39 < table_39["size"]) {
+    count_39 = count_39 + 1;
+}
+assert_eq(sum_39(items_39, 0), 162);
+assert_eq(scale_39(table_39["last"]) + count_39, 49);
+let ratio_39 = 39.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 40
+let scale_40 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 40) {
+        doubled - 40
+    } else {
+        doubled + 40
+    }
+};
+let items_40 = [40, 41, 42, 43];
+let table_40 = {"name": "section 40", "size": len(items_40), "last": last(items_40)};
+let sum_40 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_40(rest(values), acc + first(values)) }
+};
+let count_40 = 0;
+while (count_40 < table_40["size"]) {
+    count_40 = count_40 + 1;
+}
+assert_eq(sum_40(items_40, 0), 166);
+assert_eq(scale_40(table_40["last"]) + count_40, 50);
+let ratio_40 = 40.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 41
+let scale_41 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 41) {
+        doubled - 41
+    } else {
+        doubled + 41
+    }
+};
+let items_41 = [41, 42, 43, 44];
+let table_41 = {"name": "section 41", "size": len(items_41), "last": last(items_41)};
+let sum_41 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_41(rest(values), acc + first(values)) }
+};
+let count_41 = 0;
+while (count_41 < table_41["size"]) {
+    count_41 = count_41 + 1;
+}
+assert_eq(sum_41(items_41, 0), 170);
+assert_eq(scale_41(table_41["last"]) + count_41, 51);
+let ratio_41 = 41.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 42
+let scale_42 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 42) {
+        doubled - 42
+    } else {
+        doubled + 42
+    }
+};
+let items_42 = [42, 43, 44, 45];
+let table_42 = {"name": "section 42", "size": len(items_42), "last": last(items_42)};
+let sum_42 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_42(rest(values), acc + first(values)) }
+};
+let count_42 = 0;
+while (count_42 < table_42["size"]) {
+    count_42 = count_42 + 1;
+}
+assert_eq(sum_42(items_42, 0), 174);
+assert_eq(scale_42(table_42["last"]) + count_42, 52);
+let ratio_42 = 42.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 43
+let scale_43 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 43) {
+        doubled - 43
+    } else {
+        doubled + 43
+    }
+};
+let items_43 = [43, 44, 45, 46];
+let table_43 = {"name": "section 43", "size": len(items_43), "last": last(items_43)};
+let sum_43 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_43(rest(values), acc + first(values)) }
+};
+let count_43 = 0;
+while (count_43 < table_43["size"]) {
+    count_43 = count_43 + 1;
+}
+assert_eq(sum_43(items_43, 0), 178);
+assert_eq(scale_43(table_43["last"]) + count_43, 53);
+let ratio_43 = 43.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 44
+let scale_44 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 44) {
+        doubled - 44
+    } else {
+        doubled + 44
+    }
+};
+let items_44 = [44, 45, 46, 47];
+let table_44 = {"name": "section 44", "size": len(items_44), "last": last(items_44)};
+let sum_44 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_44(rest(values), acc + first(values)) }
+};
+let count_44 = 0;
+while (count_44 < table_44["size"]) {
+    count_44 = count_44 + 1;
+}
+assert_eq(sum_44(items_44, 0), 182);
+assert_eq(scale_44(table_44["last"]) + count_44, 54);
+let ratio_44 = 44.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 45
+let scale_45 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 45) {
+        doubled - 45
+    } else {
+        doubled + 45
+    }
+};
+let items_45 = [45, 46, 47, 48];
+let table_45 = {"name": "section 45", "size": len(items_45), "last": last(items_45)};
+let sum_45 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_45(rest(values), acc + first(values)) }
+};
+let count_45 = 0;
+while (count_45 < table_45["size"]) {
+    count_45 = count_45 + 1;
+}
+assert_eq(sum_45(items_45, 0), 186);
+assert_eq(scale_45(table_45["last"]) + count_45, 55);
+let ratio_45 = 45.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 46
+let scale_46 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 46) {
+        doubled - 46
+    } else {
+        doubled + 46
+    }
+};
+let items_46 = [46, 47, 48, 49];
+let table_46 = {"name": "section 46", "size": len(items_46), "last": last(items_46)};
+let sum_46 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_46(rest(values), acc + first(values)) }
+};
+let count_46 = 0;
+while (count_46 < table_46["size"]) {
+    count_46 = count_46 + 1;
+}
+assert_eq(sum_46(items_46, 0), 190);
+assert_eq(scale_46(table_46["last"]) + count_46, 56);
+let ratio_46 = 46.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 47
+let scale_47 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 47) {
+        doubled - 47
+    } else {
+        doubled + 47
+    }
+};
+let items_47 = [47, 48, 49, 50];
+let table_47 = {"name": "section 47", "size": len(items_47), "last": last(items_47)};
+let sum_47 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_47(rest(values), acc + first(values)) }
+};
+let count_47 = 0;
+while (count_47 < table_47["size"]) {
+    count_47 = count_47 + 1;
+}
+assert_eq(sum_47(items_47, 0), 194);
+assert_eq(scale_47(table_47["last"]) + count_47, 57);
+let ratio_47 = 47.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 48
+let scale_48 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 48) {
+        doubled - 48
+    } else {
+        doubled + 48
+    }
+};
+let items_48 = [48, 49, 50, 51];
+let table_48 = {"name": "section 48", "size": len(items_48), "last": last(items_48)};
+let sum_48 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_48(rest(values), acc + first(values)) }
+};
+let count_48 = 0;
+while (count_48 < table_48["size"]) {
+    count_48 = count_48 + 1;
+}
+assert_eq(sum_48(items_48, 0), 198);
+assert_eq(scale_48(table_48["last"]) + count_48, 58);
+let ratio_48 = 48.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 49
+let scale_49 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 49) {
+        doubled - 49
+    } else {
+        doubled + 49
+    }
+};
+let items_49 = [49, 50, 51, 52];
+let table_49 = {"name": "section 49", "size": len(items_49), "last": last(items_49)};
+let sum_49 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_49(rest(values), acc + first(values)) }
+};
+let count_49 = 0;
+while (count_49 < table_49["size"]) {
+    count_49 = count_49 + 1;
+}
+assert_eq(sum_49(items_49, 0), 202);
+assert_eq(scale_49(table_49["last"]) + count_49, 59);
+let ratio_49 = 49.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 50
+let scale_50 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 50) {
+        doubled - 50
+    } else {
+        doubled + 50
+    }
+};
+let items_50 = [50, 51, 52, 53];
+let table_50 = {"name": "section 50", "size": len(items_50), "last": last(items_50)};
+let sum_50 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_50(rest(values), acc + first(values)) }
+};
+let count_50 = 0;
+while (count_50 < table_50["size"]) {
+    count_50 = count_50 + 1;
+}
+assert_eq(sum_50(items_50, 0), 206);
+assert_eq(scale_50(table_50["last"]) + count_50, 60);
+let ratio_50 = 50.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 51
+let scale_51 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 51) {
+        doubled - 51
+    } else {
+        doubled + 51
+    }
+};
+let items_51 = [51, 52, 53, 54];
+let table_51 = {"name": "section 51", "size": len(items_51), "last": last(items_51)};
+let sum_51 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_51(rest(values), acc + first(values)) }
+};
+let count_51 = 0;
+while (count_51 < table_51["size"]) {
+    count_51 = count_51 + 1;
+}
+assert_eq(sum_51(items_51, 0), 210);
+assert_eq(scale_51(table_51["last"]) + count_51, 61);
+let ratio_51 = 51.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 52
+let scale_52 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 52) {
+        doubled - 52
+    } else {
+        doubled + 52
+    }
+};
+let items_52 = [52, 53, 54, 55];
+let table_52 = {"name": "section 52", "size": len(items_52), "last": last(items_52)};
+let sum_52 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_52(rest(values), acc + first(values)) }
+};
+let count_52 = 0;
+while (count_52 < table_52["size"]) {
+    count_52 = count_52 + 1;
+}
+assert_eq(sum_52(items_52, 0), 214);
+assert_eq(scale_52(table_52["last"]) + count_52, 62);
+let ratio_52 = 52.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 53
+let scale_53 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 53) {
+        doubled - 53
+    } else {
+        doubled + 53
+    }
+};
+let items_53 = [53, 54, 55, 56];
+let table_53 = {"name": "section 53", "size": len(items_53), "last": last(items_53)};
+let sum_53 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_53(rest(values), acc + first(values)) }
+};
+let count_53 = 0;
+while (count_53 < table_53["size"]) {
+    count_53 = count_53 + 1;
+}
+assert_eq(sum_53(items_53, 0), 218);
+assert_eq(scale_53(table_53["last"]) + count_53, 63);
+let ratio_53 = 53.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 54
+let scale_54 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 54) {
+        doubled - 54
+    } else {
+        doubled + 54
+    }
+};
+let items_54 = [54, 55, 56, 57];
+let table_54 = {"name": "section 54", "size": len(items_54), "last": last(items_54)};
+let sum_54 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_54(rest(values), acc + first(values)) }
+};
+let count_54 = 0;
+while (count_54 < table_54["size"]) {
+    count_54 = count_54 + 1;
+}
+assert_eq(sum_54(items_54, 0), 222);
+assert_eq(scale_54(table_54["last"]) + count_54, 64);
+let ratio_54 = 54.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 55
+let scale_55 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 55) {
+        doubled - 55
+    } else {
+        doubled + 55
+    }
+};
+let items_55 = [55, 56, 57, 58];
+let table_55 = {"name": "section 55", "size": len(items_55), "last": last(items_55)};
+let sum_55 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_55(rest(values), acc + first(values)) }
+};
+let count_55 = 0;
+while (count_55 < table_55["size"]) {
+    count_55 = count_55 + 1;
+}
+assert_eq(sum_55(items_55, 0), 226);
+assert_eq(scale_55(table_55["last"]) + count_55, 65);
+let ratio_55 = 55.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 56
+let scale_56 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 56) {
+        doubled - 56
+    } else {
+        doubled + 56
+    }
+};
+let items_56 = [56, 57, 58, 59];
+let table_56 = {"name": "section 56", "size": len(items_56), "last": last(items_56)};
+let sum_56 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_56(rest(values), acc + first(values)) }
+};
+let count_56 = 0;
+while (count_56 < table_56["size"]) {
+    count_56 = count_56 + 1;
+}
+assert_eq(sum_56(items_56, 0), 230);
+assert_eq(scale_56(table_56["last"]) + count_56, 66);
+let ratio_56 = 56.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 57
+let scale_57 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 57) {
+        doubled - 57
+    } else {
+        doubled + 57
+    }
+};
+let items_57 = [57, 58, 59, 60];
+let table_57 = {"name": "section 57", "size": len(items_57), "last": last(items_57)};
+let sum_57 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_57(rest(values), acc + first(values)) }
+};
+let count_57 = 0;
+while (count_57 < table_57["size"]) {
+    count_57 = count_57 + 1;
+}
+assert_eq(sum_57(items_57, 0), 234);
+assert_eq(scale_57(table_57["last"]) + count_57, 67);
+let ratio_57 = 57.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 58
+let scale_58 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 58) {
+        doubled - 58
+    } else {
+        doubled + 58
+    }
+};
+let items_58 = [58, 59, 60, 61];
+let table_58 = {"name": "section 58", "size": len(items_58), "last": last(items_58)};
+let sum_58 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_58(rest(values), acc + first(values)) }
+};
+let count_58 = 0;
+while (count_58 < table_58["size"]) {
+    count_58 = count_58 + 1;
+}
+assert_eq(sum_58(items_58, 0), 238);
+assert_eq(scale_58(table_58["last"]) + count_58, 68);
+let ratio_58 = 58.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 59
+let scale_59 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 59) {
+        doubled - 59
+    } else {
+        doubled + 59
+    }
+};
+let items_59 = [59, 60, 61, 62];
+let table_59 = {"name": "section 59", "size": len(items_59), "last": last(items_59)};
+let sum_59 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_59(rest(values), acc + first(values)) }
+};
+let count_59 = 0;
+while (count_59 < table_59["size"]) {
+    count_59 = count_59 + 1;
+}
+assert_eq(sum_59(items_59, 0), 242);
+assert_eq(scale_59(table_59["last"]) + count_59, 69);
+let ratio_59 = 59.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 60
+let scale_60 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 60) {
+        doubled - 60
+    } else {
+        doubled + 60
+    }
+};
+let items_60 = [60, 61, 62, 63];
+let table_60 = {"name": "section 60", "size": len(items_60), "last": last(items_60)};
+let sum_60 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_60(rest(values), acc + first(values)) }
+};
+let count_60 = 0;
+while (count_60 < table_60["size"]) {
+    count_60 = count_60 + 1;
+}
+assert_eq(sum_60(items_60, 0), 246);
+assert_eq(scale_60(table_60["last"]) + count_60, 70);
+let ratio_60 = 60.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 61
+let scale_61 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 61) {
+        doubled - 61
+    } else {
+        doubled + 61
+    }
+};
+let items_61 = [61, 62, 63, 64];
+let table_61 = {"name": "section 61", "size": len(items_61), "last": last(items_61)};
+let sum_61 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_61(rest(values), acc + first(values)) }
+};
+let count_61 = 0;
+while (count_61 < table_61["size"]) {
+    count_61 = count_61 + 1;
+}
+assert_eq(sum_61(items_61, 0), 250);
+assert_eq(scale_61(table_61["last"]) + count_61, 71);
+let ratio_61 = 61.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 62
+let scale_62 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 62) {
+        doubled - 62
+    } else {
+        doubled + 62
+    }
+};
+let items_62 = [62, 63, 64, 65];
+let table_62 = {"name": "section 62", "size": len(items_62), "last": last(items_62)};
+let sum_62 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_62(rest(values), acc + first(values)) }
+};
+let count_62 = 0;
+while (count_62 < table_62["size"]) {
+    count_62 = count_62 + 1;
+}
+assert_eq(sum_62(items_62, 0), 254);
+assert_eq(scale_62(table_62["last"]) + count_62, 72);
+let ratio_62 = 62.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 63
+let scale_63 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 63) {
+        doubled - 63
+    } else {
+        doubled + 63
+    }
+};
+let items_63 = [63, 64, 65, 66];
+let table_63 = {"name": "section 63", "size": len(items_63), "last": last(items_63)};
+let sum_63 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_63(rest(values), acc + first(values)) }
+};
+let count_63 = 0;
+while (count_63 < table_63["size"]) {
+    count_63 = count_63 + 1;
+}
+assert_eq(sum_63(items_63, 0), 258);
+assert_eq(scale_63(table_63["last"]) + count_63, 73);
+let ratio_63 = 63.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 64
+let scale_64 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 64) {
+        doubled - 64
+    } else {
+        doubled + 64
+    }
+};
+let items_64 = [64, 65, 66, 67];
+let table_64 = {"name": "section 64", "size": len(items_64), "last": last(items_64)};
+let sum_64 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_64(rest(values), acc + first(values)) }
+};
+let count_64 = 0;
+while (count_64 < table_64["size"]) {
+    count_64 = count_64 + 1;
+}
+assert_eq(sum_64(items_64, 0), 262);
+assert_eq(scale_64(table_64["last"]) + count_64, 74);
+let ratio_64 = 64.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 65
+let scale_65 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 65) {
+        doubled - 65
+    } else {
+        doubled + 65
+    }
+};
+let items_65 = [65, 66, 67, 68];
+let table_65 = {"name": "section 65", "size": len(items_65), "last": last(items_65)};
+let sum_65 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_65(rest(values), acc + first(values)) }
+};
+let count_65 = 0;
+while (count_65 < table_65["size"]) {
+    count_65 = count_65 + 1;
+}
+assert_eq(sum_65(items_65, 0), 266);
+assert_eq(scale_65(table_65["last"]) + count_65, 75);
+let ratio_65 = 65.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 66
+let scale_66 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 66) {
+        doubled - 66
+    } else {
+        doubled + 66
+    }
+};
+let items_66 = [66, 67, 68, 69];
+let table_66 = {"name": "section 66", "size": len(items_66), "last": last(items_66)};
+let sum_66 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_66(rest(values), acc + first(values)) }
+};
+let count_66 = 0;
+while (count_66 < table_66["size"]) {
+    count_66 = count_66 + 1;
+}
+assert_eq(sum_66(items_66, 0), 270);
+assert_eq(scale_66(table_66["last"]) + count_66, 76);
+let ratio_66 = 66.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 67
+let scale_67 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 67) {
+        doubled - 67
+    } else {
+        doubled + 67
+    }
+};
+let items_67 = [67, 68, 69, 70];
+let table_67 = {"name": "section 67", "size": len(items_67), "last": last(items_67)};
+let sum_67 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_67(rest(values), acc + first(values)) }
+};
+let count_67 = 0;
+while (count_67 < table_67["size"]) {
+    count_67 = count_67 + 1;
+}
+assert_eq(sum_67(items_67, 0), 274);
+assert_eq(scale_67(table_67["last"]) + count_67, 77);
+let ratio_67 = 67.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 68
+let scale_68 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 68) {
+        doubled - 68
+    } else {
+        doubled + 68
+    }
+};
+let items_68 = [68, 69, 70, 71];
+let table_68 = {"name": "section 68", "size": len(items_68), "last": last(items_68)};
+let sum_68 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_68(rest(values), acc + first(values)) }
+};
+let count_68 = 0;
+while (count_68 < table_68["size"]) {
+    count_68 = count_68 + 1;
+}
+assert_eq(sum_68(items_68, 0), 278);
+assert_eq(scale_68(table_68["last"]) + count_68, 78);
+let ratio_68 = 68.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 69
+let scale_69 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 69) {
+        doubled - 69
+    } else {
+        doubled + 69
+    }
+};
+let items_69 = [69, 70, 71, 72];
+let table_69 = {"name": "section 69", "size": len(items_69), "last": last(items_69)};
+let sum_69 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_69(rest(values), acc + first(values)) }
+};
+let count_69 = 0;
+while (count_69 < table_69["size"]) {
+    count_69 = count_69 + 1;
+}
+assert_eq(sum_69(items_69, 0), 282);
+assert_eq(scale_69(table_69["last"]) + count_69, 79);
+let ratio_69 = 69.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 70
+let scale_70 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 70) {
+        doubled - 70
+    } else {
+        doubled + 70
+    }
+};
+let items_70 = [70, 71, 72, 73];
+let table_70 = {"name": "section 70", "size": len(items_70), "last": last(items_70)};
+let sum_70 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_70(rest(values), acc + first(values)) }
+};
+let count_70 = 0;
+while (count_70 < table_70["size"]) {
+    count_70 = count_70 + 1;
+}
+assert_eq(sum_70(items_70, 0), 286);
+assert_eq(scale_70(table_70["last"]) + count_70, 80);
+let ratio_70 = 70.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 71
+let scale_71 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 71) {
+        doubled - 71
+    } else {
+        doubled + 71
+    }
+};
+let items_71 = [71, 72, 73, 74];
+let table_71 = {"name": "section 71", "size": len(items_71), "last": last(items_71)};
+let sum_71 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_71(rest(values), acc + first(values)) }
+};
+let count_71 = 0;
+while (count_71 < table_71["size"]) {
+    count_71 = count_71 + 1;
+}
+assert_eq(sum_71(items_71, 0), 290);
+assert_eq(scale_71(table_71["last"]) + count_71, 81);
+let ratio_71 = 71.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 72
+let scale_72 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 72) {
+        doubled - 72
+    } else {
+        doubled + 72
+    }
+};
+let items_72 = [72, 73, 74, 75];
+let table_72 = {"name": "section 72", "size": len(items_72), "last": last(items_72)};
+let sum_72 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_72(rest(values), acc + first(values)) }
+};
+let count_72 = 0;
+while (count_72 < table_72["size"]) {
+    count_72 = count_72 + 1;
+}
+assert_eq(sum_72(items_72, 0), 294);
+assert_eq(scale_72(table_72["last"]) + count_72, 82);
+let ratio_72 = 72.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 73
+let scale_73 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 73) {
+        doubled - 73
+    } else {
+        doubled + 73
+    }
+};
+let items_73 = [73, 74, 75, 76];
+let table_73 = {"name": "section 73", "size": len(items_73), "last": last(items_73)};
+let sum_73 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_73(rest(values), acc + first(values)) }
+};
+let count_73 = 0;
+while (count_73 < table_73["size"]) {
+    count_73 = count_73 + 1;
+}
+assert_eq(sum_73(items_73, 0), 298);
+assert_eq(scale_73(table_73["last"]) + count_73, 83);
+let ratio_73 = 73.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 74
+let scale_74 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 74) {
+        doubled - 74
+    } else {
+        doubled + 74
+    }
+};
+let items_74 = [74, 75, 76, 77];
+let table_74 = {"name": "section 74", "size": len(items_74), "last": last(items_74)};
+let sum_74 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_74(rest(values), acc + first(values)) }
+};
+let count_74 = 0;
+while (count_74 < table_74["size"]) {
+    count_74 = count_74 + 1;
+}
+assert_eq(sum_74(items_74, 0), 302);
+assert_eq(scale_74(table_74["last"]) + count_74, 84);
+let ratio_74 = 74.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 75
+let scale_75 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 75) {
+        doubled - 75
+    } else {
+        doubled + 75
+    }
+};
+let items_75 = [75, 76, 77, 78];
+let table_75 = {"name": "section 75", "size": len(items_75), "last": last(items_75)};
+let sum_75 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_75(rest(values), acc + first(values)) }
+};
+let count_75 = 0;
+while (count_75 < table_75["size"]) {
+    count_75 = count_75 + 1;
+}
+assert_eq(sum_75(items_75, 0), 306);
+assert_eq(scale_75(table_75["last"]) + count_75, 85);
+let ratio_75 = 75.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 76
+let scale_76 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 76) {
+        doubled - 76
+    } else {
+        doubled + 76
+    }
+};
+let items_76 = [76, 77, 78, 79];
+let table_76 = {"name": "section 76", "size": len(items_76), "last": last(items_76)};
+let sum_76 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_76(rest(values), acc + first(values)) }
+};
+let count_76 = 0;
+while (count_76 < table_76["size"]) {
+    count_76 = count_76 + 1;
+}
+assert_eq(sum_76(items_76, 0), 310);
+assert_eq(scale_76(table_76["last"]) + count_76, 86);
+let ratio_76 = 76.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 77
+let scale_77 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 77) {
+        doubled - 77
+    } else {
+        doubled + 77
+    }
+};
+let items_77 = [77, 78, 79, 80];
+let table_77 = {"name": "section 77", "size": len(items_77), "last": last(items_77)};
+let sum_77 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_77(rest(values), acc + first(values)) }
+};
+let count_77 = 0;
+while (count_77 < table_77["size"]) {
+    count_77 = count_77 + 1;
+}
+assert_eq(sum_77(items_77, 0), 314);
+assert_eq(scale_77(table_77["last"]) + count_77, 87);
+let ratio_77 = 77.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 78
+let scale_78 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 78) {
+        doubled - 78
+    } else {
+        doubled + 78
+    }
+};
+let items_78 = [78, 79, 80, 81];
+let table_78 = {"name": "section 78", "size": len(items_78), "last": last(items_78)};
+let sum_78 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_78(rest(values), acc + first(values)) }
+};
+let count_78 = 0;
+while (count_78 < table_78["size"]) {
+    count_78 = count_78 + 1;
+}
+assert_eq(sum_78(items_78, 0), 318);
+assert_eq(scale_78(table_78["last"]) + count_78, 88);
+let ratio_78 = 78.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 79
+let scale_79 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 79) {
+        doubled - 79
+    } else {
+        doubled + 79
+    }
+};
+let items_79 = [79, 80, 81, 82];
+let table_79 = {"name": "section 79", "size": len(items_79), "last": last(items_79)};
+let sum_79 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_79(rest(values), acc + first(values)) }
+};
+let count_79 = 0;
+while (count_79 < table_79["size"]) {
+    count_79 = count_79 + 1;
+}
+assert_eq(sum_79(items_79, 0), 322);
+assert_eq(scale_79(table_79["last"]) + count_79, 89);
+let ratio_79 = 79.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 80
+let scale_80 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 80) {
+        doubled - 80
+    } else {
+        doubled + 80
+    }
+};
+let items_80 = [80, 81, 82, 83];
+let table_80 = {"name": "section 80", "size": len(items_80), "last": last(items_80)};
+let sum_80 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_80(rest(values), acc + first(values)) }
+};
+let count_80 = 0;
+while (count_80 < table_80["size"]) {
+    count_80 = count_80 + 1;
+}
+assert_eq(sum_80(items_80, 0), 326);
+assert_eq(scale_80(table_80["last"]) + count_80, 90);
+let ratio_80 = 80.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 81
+let scale_81 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 81) {
+        doubled - 81
+    } else {
+        doubled + 81
+    }
+};
+let items_81 = [81, 82, 83, 84];
+let table_81 = {"name": "section 81", "size": len(items_81), "last": last(items_81)};
+let sum_81 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_81(rest(values), acc + first(values)) }
+};
+let count_81 = 0;
+while (count_81 < table_81["size"]) {
+    count_81 = count_81 + 1;
+}
+assert_eq(sum_81(items_81, 0), 330);
+assert_eq(scale_81(table_81["last"]) + count_81, 91);
+let ratio_81 = 81.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 82
+let scale_82 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 82) {
+        doubled - 82
+    } else {
+        doubled + 82
+    }
+};
+let items_82 = [82, 83, 84, 85];
+let table_82 = {"name": "section 82", "size": len(items_82), "last": last(items_82)};
+let sum_82 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_82(rest(values), acc + first(values)) }
+};
+let count_82 = 0;
+while (count_82 < table_82["size"]) {
+    count_82 = count_82 + 1;
+}
+assert_eq(sum_82(items_82, 0), 334);
+assert_eq(scale_82(table_82["last"]) + count_82, 92);
+let ratio_82 = 82.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 83
+let scale_83 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 83) {
+        doubled - 83
+    } else {
+        doubled + 83
+    }
+};
+let items_83 = [83, 84, 85, 86];
+let table_83 = {"name": "section 83", "size": len(items_83), "last": last(items_83)};
+let sum_83 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_83(rest(values), acc + first(values)) }
+};
+let count_83 = 0;
+while (count_83 < table_83["size"]) {
+    count_83 = count_83 + 1;
+}
+assert_eq(sum_83(items_83, 0), 338);
+assert_eq(scale_83(table_83["last"]) + count_83, 93);
+let ratio_83 = 83.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 84
+let scale_84 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 84) {
+        doubled - 84
+    } else {
+        doubled + 84
+    }
+};
+let items_84 = [84, 85, 86, 87];
+let table_84 = {"name": "section 84", "size": len(items_84), "last": last(items_84)};
+let sum_84 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_84(rest(values), acc + first(values)) }
+};
+let count_84 = 0;
+while (count_84 < table_84["size"]) {
+    count_84 = count_84 + 1;
+}
+assert_eq(sum_84(items_84, 0), 342);
+assert_eq(scale_84(table_84["last"]) + count_84, 94);
+let ratio_84 = 84.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 85
+let scale_85 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 85) {
+        doubled - 85
+    } else {
+        doubled + 85
+    }
+};
+let items_85 = [85, 86, 87, 88];
+let table_85 = {"name": "section 85", "size": len(items_85), "last": last(items_85)};
+let sum_85 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_85(rest(values), acc + first(values)) }
+};
+let count_85 = 0;
+while (count_85 < table_85["size"]) {
+    count_85 = count_85 + 1;
+}
+assert_eq(sum_85(items_85, 0), 346);
+assert_eq(scale_85(table_85["last"]) + count_85, 95);
+let ratio_85 = 85.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 86
+let scale_86 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 86) {
+        doubled - 86
+    } else {
+        doubled + 86
+    }
+};
+let items_86 = [86, 87, 88, 89];
+let table_86 = {"name": "section 86", "size": len(items_86), "last": last(items_86)};
+let sum_86 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_86(rest(values), acc + first(values)) }
+};
+let count_86 = 0;
+while (count_86 < table_86["size"]) {
+    count_86 = count_86 + 1;
+}
+assert_eq(sum_86(items_86, 0), 350);
+assert_eq(scale_86(table_86["last"]) + count_86, 96);
+let ratio_86 = 86.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 87
+let scale_87 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 87) {
+        doubled - 87
+    } else {
+        doubled + 87
+    }
+};
+let items_87 = [87, 88, 89, 90];
+let table_87 = {"name": "section 87", "size": len(items_87), "last": last(items_87)};
+let sum_87 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_87(rest(values), acc + first(values)) }
+};
+let count_87 = 0;
+while (count_87 < table_87["size"]) {
+    count_87 = count_87 + 1;
+}
+assert_eq(sum_87(items_87, 0), 354);
+assert_eq(scale_87(table_87["last"]) + count_87, 97);
+let ratio_87 = 87.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 88
+let scale_88 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 88) {
+        doubled - 88
+    } else {
+        doubled + 88
+    }
+};
+let items_88 = [88, 89, 90, 91];
+let table_88 = {"name": "section 88", "size": len(items_88), "last": last(items_88)};
+let sum_88 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_88(rest(values), acc + first(values)) }
+};
+let count_88 = 0;
+while (count_88 < table_88["size"]) {
+    count_88 = count_88 + 1;
+}
+assert_eq(sum_88(items_88, 0), 358);
+assert_eq(scale_88(table_88["last"]) + count_88, 98);
+let ratio_88 = 88.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 89
+let scale_89 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 89) {
+        doubled - 89
+    } else {
+        doubled + 89
+    }
+};
+let items_89 = [89, 90, 91, 92];
+let table_89 = {"name": "section 89", "size": len(items_89), "last": last(items_89)};
+let sum_89 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_89(rest(values), acc + first(values)) }
+};
+let count_89 = 0;
+while (count_89 < table_89["size"]) {
+    count_89 = count_89 + 1;
+}
+assert_eq(sum_89(items_89, 0), 362);
+assert_eq(scale_89(table_89["last"]) + count_89, 99);
+let ratio_89 = 89.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 90
+let scale_90 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 90) {
+        doubled - 90
+    } else {
+        doubled + 90
+    }
+};
+let items_90 = [90, 91, 92, 93];
+let table_90 = {"name": "section 90", "size": len(items_90), "last": last(items_90)};
+let sum_90 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_90(rest(values), acc + first(values)) }
+};
+let count_90 = 0;
+while (count_90 < table_90["size"]) {
+    count_90 = count_90 + 1;
+}
+assert_eq(sum_90(items_90, 0), 366);
+assert_eq(scale_90(table_90["last"]) + count_90, 100);
+let ratio_90 = 90.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 91
+let scale_91 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 91) {
+        doubled - 91
+    } else {
+        doubled + 91
+    }
+};
+let items_91 = [91, 92, 93, 94];
+let table_91 = {"name": "section 91", "size": len(items_91), "last": last(items_91)};
+let sum_91 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_91(rest(values), acc + first(values)) }
+};
+let count_91 = 0;
+while (count_91 < table_91["size"]) {
+    count_91 = count_91 + 1;
+}
+assert_eq(sum_91(items_91, 0), 370);
+assert_eq(scale_91(table_91["last"]) + count_91, 101);
+let ratio_91 = 91.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 92
+let scale_92 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 92) {
+        doubled - 92
+    } else {
+        doubled + 92
+    }
+};
+let items_92 = [92, 93, 94, 95];
+let table_92 = {"name": "section 92", "size": len(items_92), "last": last(items_92)};
+let sum_92 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_92(rest(values), acc + first(values)) }
+};
+let count_92 = 0;
+while (count_92 < table_92["size"]) {
+    count_92 = count_92 + 1;
+}
+assert_eq(sum_92(items_92, 0), 374);
+assert_eq(scale_92(table_92["last"]) + count_92, 102);
+let ratio_92 = 92.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 93
+let scale_93 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 93) {
+        doubled - 93
+    } else {
+        doubled + 93
+    }
+};
+let items_93 = [93, 94, 95, 96];
+let table_93 = {"name": "section 93", "size": len(items_93), "last": last(items_93)};
+let sum_93 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_93(rest(values), acc + first(values)) }
+};
+let count_93 = 0;
+while (count_93 < table_93["size"]) {
+    count_93 = count_93 + 1;
+}
+assert_eq(sum_93(items_93, 0), 378);
+assert_eq(scale_93(table_93["last"]) + count_93, 103);
+let ratio_93 = 93.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 94
+let scale_94 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 94) {
+        doubled - 94
+    } else {
+        doubled + 94
+    }
+};
+let items_94 = [94, 95, 96, 97];
+let table_94 = {"name": "section 94", "size": len(items_94), "last": last(items_94)};
+let sum_94 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_94(rest(values), acc + first(values)) }
+};
+let count_94 = 0;
+while (count_94 < table_94["size"]) {
+    count_94 = count_94 + 1;
+}
+assert_eq(sum_94(items_94, 0), 382);
+assert_eq(scale_94(table_94["last"]) + count_94, 104);
+let ratio_94 = 94.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 95
+let scale_95 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 95) {
+        doubled - 95
+    } else {
+        doubled + 95
+    }
+};
+let items_95 = [95, 96, 97, 98];
+let table_95 = {"name": "section 95", "size": len(items_95), "last": last(items_95)};
+let sum_95 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_95(rest(values), acc + first(values)) }
+};
+let count_95 = 0;
+while (count_95 < table_95["size"]) {
+    count_95 = count_95 + 1;
+}
+assert_eq(sum_95(items_95, 0), 386);
+assert_eq(scale_95(table_95["last"]) + count_95, 105);
+let ratio_95 = 95.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 96
+let scale_96 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 96) {
+        doubled - 96
+    } else {
+        doubled + 96
+    }
+};
+let items_96 = [96, 97, 98, 99];
+let table_96 = {"name": "section 96", "size": len(items_96), "last": last(items_96)};
+let sum_96 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_96(rest(values), acc + first(values)) }
+};
+let count_96 = 0;
+while (count_96 < table_96["size"]) {
+    count_96 = count_96 + 1;
+}
+assert_eq(sum_96(items_96, 0), 390);
+assert_eq(scale_96(table_96["last"]) + count_96, 106);
+let ratio_96 = 96.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 97
+let scale_97 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 97) {
+        doubled - 97
+    } else {
+        doubled + 97
+    }
+};
+let items_97 = [97, 98, 99, 100];
+let table_97 = {"name": "section 97", "size": len(items_97), "last": last(items_97)};
+let sum_97 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_97(rest(values), acc + first(values)) }
+};
+let count_97 = 0;
+while (count_97 < table_97["size"]) {
+    count_97 = count_97 + 1;
+}
+assert_eq(sum_97(items_97, 0), 394);
+assert_eq(scale_97(table_97["last"]) + count_97, 107);
+let ratio_97 = 97.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 98
+let scale_98 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 98) {
+        doubled - 98
+    } else {
+        doubled + 98
+    }
+};
+let items_98 = [98, 99, 100, 101];
+let table_98 = {"name": "section 98", "size": len(items_98), "last": last(items_98)};
+let sum_98 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_98(rest(values), acc + first(values)) }
+};
+let count_98 = 0;
+while (count_98 < table_98["size"]) {
+    count_98 = count_98 + 1;
+}
+assert_eq(sum_98(items_98, 0), 398);
+assert_eq(scale_98(table_98["last"]) + count_98, 108);
+let ratio_98 = 98.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 99
+let scale_99 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 99) {
+        doubled - 99
+    } else {
+        doubled + 99
+    }
+};
+let items_99 = [99, 100, 101, 102];
+let table_99 = {"name": "section 99", "size": len(items_99), "last": last(items_99)};
+let sum_99 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_99(rest(values), acc + first(values)) }
+};
+let count_99 = 0;
+while (count_99 < table_99["size"]) {
+    count_99 = count_99 + 1;
+}
+assert_eq(sum_99(items_99, 0), 402);
+assert_eq(scale_99(table_99["last"]) + count_99, 109);
+let ratio_99 = 99.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 100
+let scale_100 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 100) {
+        doubled - 100
+    } else {
+        doubled + 100
+    }
+};
+let items_100 = [100, 101, 102, 103];
+let table_100 = {"name": "section 100", "size": len(items_100), "last": last(items_100)};
+let sum_100 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_100(rest(values), acc + first(values)) }
+};
+let count_100 = 0;
+while (count_100 < table_100["size"]) {
+    count_100 = count_100 + 1;
+}
+assert_eq(sum_100(items_100, 0), 406);
+assert_eq(scale_100(table_100["last"]) + count_100, 110);
+let ratio_100 = 100.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 101
+let scale_101 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 101) {
+        doubled - 101
+    } else {
+        doubled + 101
+    }
+};
+let items_101 = [101, 102, 103, 104];
+let table_101 = {"name": "section 101", "size": len(items_101), "last": last(items_101)};
+let sum_101 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_101(rest(values), acc + first(values)) }
+};
+let count_101 = 0;
+while (count_101 < table_101["size"]) {
+    count_101 = count_101 + 1;
+}
+assert_eq(sum_101(items_101, 0), 410);
+assert_eq(scale_101(table_101["last"]) + count_101, 111);
+let ratio_101 = 101.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 102
+let scale_102 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 102) {
+        doubled - 102
+    } else {
+        doubled + 102
+    }
+};
+let items_102 = [102, 103, 104, 105];
+let table_102 = {"name": "section 102", "size": len(items_102), "last": last(items_102)};
+let sum_102 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_102(rest(values), acc + first(values)) }
+};
+let count_102 = 0;
+while (count_102 < table_102["size"]) {
+    count_102 = count_102 + 1;
+}
+assert_eq(sum_102(items_102, 0), 414);
+assert_eq(scale_102(table_102["last"]) + count_102, 112);
+let ratio_102 = 102.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 103
+let scale_103 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 103) {
+        doubled - 103
+    } else {
+        doubled + 103
+    }
+};
+let items_103 = [103, 104, 105, 106];
+let table_103 = {"name": "section 103", "size": len(items_103), "last": last(items_103)};
+let sum_103 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_103(rest(values), acc + first(values)) }
+};
+let count_103 = 0;
+while (count_103 < table_103["size"]) {
+    count_103 = count_103 + 1;
+}
+assert_eq(sum_103(items_103, 0), 418);
+assert_eq(scale_103(table_103["last"]) + count_103, 113);
+let ratio_103 = 103.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 104
+let scale_104 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 104) {
+        doubled - 104
+    } else {
+        doubled + 104
+    }
+};
+let items_104 = [104, 105, 106, 107];
+let table_104 = {"name": "section 104", "size": len(items_104), "last": last(items_104)};
+let sum_104 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_104(rest(values), acc + first(values)) }
+};
+let count_104 = 0;
+while (count_104 < table_104["size"]) {
+    count_104 = count_104 + 1;
+}
+assert_eq(sum_104(items_104, 0), 422);
+assert_eq(scale_104(table_104["last"]) + count_104, 114);
+let ratio_104 = 104.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 105
+let scale_105 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 105) {
+        doubled - 105
+    } else {
+        doubled + 105
+    }
+};
+let items_105 = [105, 106, 107, 108];
+let table_105 = {"name": "section 105", "size": len(items_105), "last": last(items_105)};
+let sum_105 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_105(rest(values), acc + first(values)) }
+};
+let count_105 = 0;
+while (count_105 < table_105["size"]) {
+    count_105 = count_105 + 1;
+}
+assert_eq(sum_105(items_105, 0), 426);
+assert_eq(scale_105(table_105["last"]) + count_105, 115);
+let ratio_105 = 105.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 106
+let scale_106 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 106) {
+        doubled - 106
+    } else {
+        doubled + 106
+    }
+};
+let items_106 = [106, 107, 108, 109];
+let table_106 = {"name": "section 106", "size": len(items_106), "last": last(items_106)};
+let sum_106 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_106(rest(values), acc + first(values)) }
+};
+let count_106 = 0;
+while (count_106 < table_106["size"]) {
+    count_106 = count_106 + 1;
+}
+assert_eq(sum_106(items_106, 0), 430);
+assert_eq(scale_106(table_106["last"]) + count_106, 116);
+let ratio_106 = 106.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 107
+let scale_107 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 107) {
+        doubled - 107
+    } else {
+        doubled + 107
+    }
+};
+let items_107 = [107, 108, 109, 110];
+let table_107 = {"name": "section 107", "size": len(items_107), "last": last(items_107)};
+let sum_107 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_107(rest(values), acc + first(values)) }
+};
+let count_107 = 0;
+while (count_107 < table_107["size"]) {
+    count_107 = count_107 + 1;
+}
+assert_eq(sum_107(items_107, 0), 434);
+assert_eq(scale_107(table_107["last"]) + count_107, 117);
+let ratio_107 = 107.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 108
+let scale_108 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 108) {
+        doubled - 108
+    } else {
+        doubled + 108
+    }
+};
+let items_108 = [108, 109, 110, 111];
+let table_108 = {"name": "section 108", "size": len(items_108), "last": last(items_108)};
+let sum_108 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_108(rest(values), acc + first(values)) }
+};
+let count_108 = 0;
+while (count_108 < table_108["size"]) {
+    count_108 = count_108 + 1;
+}
+assert_eq(sum_108(items_108, 0), 438);
+assert_eq(scale_108(table_108["last"]) + count_108, 118);
+let ratio_108 = 108.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 109
+let scale_109 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 109) {
+        doubled - 109
+    } else {
+        doubled + 109
+    }
+};
+let items_109 = [109, 110, 111, 112];
+let table_109 = {"name": "section 109", "size": len(items_109), "last": last(items_109)};
+let sum_109 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_109(rest(values), acc + first(values)) }
+};
+let count_109 = 0;
+while (count_109 < table_109["size"]) {
+    count_109 = count_109 + 1;
+}
+assert_eq(sum_109(items_109, 0), 442);
+assert_eq(scale_109(table_109["last"]) + count_109, 119);
+let ratio_109 = 109.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 110
+let scale_110 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 110) {
+        doubled - 110
+    } else {
+        doubled + 110
+    }
+};
+let items_110 = [110, 111, 112, 113];
+let table_110 = {"name": "section 110", "size": len(items_110), "last": last(items_110)};
+let sum_110 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_110(rest(values), acc + first(values)) }
+};
+let count_110 = 0;
+while (count_110 < table_110["size"]) {
+    count_110 = count_110 + 1;
+}
+assert_eq(sum_110(items_110, 0), 446);
+assert_eq(scale_110(table_110["last"]) + count_110, 120);
+let ratio_110 = 110.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 111
+let scale_111 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 111) {
+        doubled - 111
+    } else {
+        doubled + 111
+    }
+};
+let items_111 = [111, 112, 113, 114];
+let table_111 = {"name": "section 111", "size": len(items_111), "last": last(items_111)};
+let sum_111 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_111(rest(values), acc + first(values)) }
+};
+let count_111 = 0;
+while (count_111 < table_111["size"]) {
+    count_111 = count_111 + 1;
+}
+assert_eq(sum_111(items_111, 0), 450);
+assert_eq(scale_111(table_111["last"]) + count_111, 121);
+let ratio_111 = 111.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 112
+let scale_112 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 112) {
+        doubled - 112
+    } else {
+        doubled + 112
+    }
+};
+let items_112 = [112, 113, 114, 115];
+let table_112 = {"name": "section 112", "size": len(items_112), "last": last(items_112)};
+let sum_112 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_112(rest(values), acc + first(values)) }
+};
+let count_112 = 0;
+while (count_112 < table_112["size"]) {
+    count_112 = count_112 + 1;
+}
+assert_eq(sum_112(items_112, 0), 454);
+assert_eq(scale_112(table_112["last"]) + count_112, 122);
+let ratio_112 = 112.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 113
+let scale_113 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 113) {
+        doubled - 113
+    } else {
+        doubled + 113
+    }
+};
+let items_113 = [113, 114, 115, 116];
+let table_113 = {"name": "section 113", "size": len(items_113), "last": last(items_113)};
+let sum_113 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_113(rest(values), acc + first(values)) }
+};
+let count_113 = 0;
+while (count_113 < table_113["size"]) {
+    count_113 = count_113 + 1;
+}
+assert_eq(sum_113(items_113, 0), 458);
+assert_eq(scale_113(table_113["last"]) + count_113, 123);
+let ratio_113 = 113.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 114
+let scale_114 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 114) {
+        doubled - 114
+    } else {
+        doubled + 114
+    }
+};
+let items_114 = [114, 115, 116, 117];
+let table_114 = {"name": "section 114", "size": len(items_114), "last": last(items_114)};
+let sum_114 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_114(rest(values), acc + first(values)) }
+};
+let count_114 = 0;
+while (count_114 < table_114["size"]) {
+    count_114 = count_114 + 1;
+}
+assert_eq(sum_114(items_114, 0), 462);
+assert_eq(scale_114(table_114["last"]) + count_114, 124);
+let ratio_114 = 114.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 115
+let scale_115 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 115) {
+        doubled - 115
+    } else {
+        doubled + 115
+    }
+};
+let items_115 = [115, 116, 117, 118];
+let table_115 = {"name": "section 115", "size": len(items_115), "last": last(items_115)};
+let sum_115 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_115(rest(values), acc + first(values)) }
+};
+let count_115 = 0;
+while (count_115 < table_115["size"]) {
+    count_115 = count_115 + 1;
+}
+assert_eq(sum_115(items_115, 0), 466);
+assert_eq(scale_115(table_115["last"]) + count_115, 125);
+let ratio_115 = 115.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 116
+let scale_116 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 116) {
+        doubled - 116
+    } else {
+        doubled + 116
+    }
+};
+let items_116 = [116, 117, 118, 119];
+let table_116 = {"name": "section 116", "size": len(items_116), "last": last(items_116)};
+let sum_116 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_116(rest(values), acc + first(values)) }
+};
+let count_116 = 0;
+while (count_116 < table_116["size"]) {
+    count_116 = count_116 + 1;
+}
+assert_eq(sum_116(items_116, 0), 470);
+assert_eq(scale_116(table_116["last"]) + count_116, 126);
+let ratio_116 = 116.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 117
+let scale_117 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 117) {
+        doubled - 117
+    } else {
+        doubled + 117
+    }
+};
+let items_117 = [117, 118, 119, 120];
+let table_117 = {"name": "section 117", "size": len(items_117), "last": last(items_117)};
+let sum_117 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_117(rest(values), acc + first(values)) }
+};
+let count_117 = 0;
+while (count_117 < table_117["size"]) {
+    count_117 = count_117 + 1;
+}
+assert_eq(sum_117(items_117, 0), 474);
+assert_eq(scale_117(table_117["last"]) + count_117, 127);
+let ratio_117 = 117.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 118
+let scale_118 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 118) {
+        doubled - 118
+    } else {
+        doubled + 118
+    }
+};
+let items_118 = [118, 119, 120, 121];
+let table_118 = {"name": "section 118", "size": len(items_118), "last": last(items_118)};
+let sum_118 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_118(rest(values), acc + first(values)) }
+};
+let count_118 = 0;
+while (count_118 < table_118["size"]) {
+    count_118 = count_118 + 1;
+}
+assert_eq(sum_118(items_118, 0), 478);
+assert_eq(scale_118(table_118["last"]) + count_118, 128);
+let ratio_118 = 118.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 119
+let scale_119 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 119) {
+        doubled - 119
+    } else {
+        doubled + 119
+    }
+};
+let items_119 = [119, 120, 121, 122];
+let table_119 = {"name": "section 119", "size": len(items_119), "last": last(items_119)};
+let sum_119 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_119(rest(values), acc + first(values)) }
+};
+let count_119 = 0;
+while (count_119 < table_119["size"]) {
+    count_119 = count_119 + 1;
+}
+assert_eq(sum_119(items_119, 0), 482);
+assert_eq(scale_119(table_119["last"]) + count_119, 129);
+let ratio_119 = 119.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 120
+let scale_120 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 120) {
+        doubled - 120
+    } else {
+        doubled + 120
+    }
+};
+let items_120 = [120, 121, 122, 123];
+let table_120 = {"name": "section 120", "size": len(items_120), "last": last(items_120)};
+let sum_120 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_120(rest(values), acc + first(values)) }
+};
+let count_120 = 0;
+while (count_120 < table_120["size"]) {
+    count_120 = count_120 + 1;
+}
+assert_eq(sum_120(items_120, 0), 486);
+assert_eq(scale_120(table_120["last"]) + count_120, 130);
+let ratio_120 = 120.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 121
+let scale_121 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 121) {
+        doubled - 121
+    } else {
+        doubled + 121
+    }
+};
+let items_121 = [121, 122, 123, 124];
+let table_121 = {"name": "section 121", "size": len(items_121), "last": last(items_121)};
+let sum_121 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_121(rest(values), acc + first(values)) }
+};
+let count_121 = 0;
+while (count_121 < table_121["size"]) {
+    count_121 = count_121 + 1;
+}
+assert_eq(sum_121(items_121, 0), 490);
+assert_eq(scale_121(table_121["last"]) + count_121, 131);
+let ratio_121 = 121.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 122
+let scale_122 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 122) {
+        doubled - 122
+    } else {
+        doubled + 122
+    }
+};
+let items_122 = [122, 123, 124, 125];
+let table_122 = {"name": "section 122", "size": len(items_122), "last": last(items_122)};
+let sum_122 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_122(rest(values), acc + first(values)) }
+};
+let count_122 = 0;
+while (count_122 < table_122["size"]) {
+    count_122 = count_122 + 1;
+}
+assert_eq(sum_122(items_122, 0), 494);
+assert_eq(scale_122(table_122["last"]) + count_122, 132);
+let ratio_122 = 122.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 123
+let scale_123 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 123) {
+        doubled - 123
+    } else {
+        doubled + 123
+    }
+};
+let items_123 = [123, 124, 125, 126];
+let table_123 = {"name": "section 123", "size": len(items_123), "last": last(items_123)};
+let sum_123 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_123(rest(values), acc + first(values)) }
+};
+let count_123 = 0;
+while (count_123 < table_123["size"]) {
+    count_123 = count_123 + 1;
+}
+assert_eq(sum_123(items_123, 0), 498);
+assert_eq(scale_123(table_123["last"]) + count_123, 133);
+let ratio_123 = 123.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 124
+let scale_124 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 124) {
+        doubled - 124
+    } else {
+        doubled + 124
+    }
+};
+let items_124 = [124, 125, 126, 127];
+let table_124 = {"name": "section 124", "size": len(items_124), "last": last(items_124)};
+let sum_124 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_124(rest(values), acc + first(values)) }
+};
+let count_124 = 0;
+while (count_124 < table_124["size"]) {
+    count_124 = count_124 + 1;
+}
+assert_eq(sum_124(items_124, 0), 502);
+assert_eq(scale_124(table_124["last"]) + count_124, 134);
+let ratio_124 = 124.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 125
+let scale_125 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 125) {
+        doubled - 125
+    } else {
+        doubled + 125
+    }
+};
+let items_125 = [125, 126, 127, 128];
+let table_125 = {"name": "section 125", "size": len(items_125), "last": last(items_125)};
+let sum_125 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_125(rest(values), acc + first(values)) }
+};
+let count_125 = 0;
+while (count_125 < table_125["size"]) {
+    count_125 = count_125 + 1;
+}
+assert_eq(sum_125(items_125, 0), 506);
+assert_eq(scale_125(table_125["last"]) + count_125, 135);
+let ratio_125 = 125.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 126
+let scale_126 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 126) {
+        doubled - 126
+    } else {
+        doubled + 126
+    }
+};
+let items_126 = [126, 127, 128, 129];
+let table_126 = {"name": "section 126", "size": len(items_126), "last": last(items_126)};
+let sum_126 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_126(rest(values), acc + first(values)) }
+};
+let count_126 = 0;
+while (count_126 < table_126["size"]) {
+    count_126 = count_126 + 1;
+}
+assert_eq(sum_126(items_126, 0), 510);
+assert_eq(scale_126(table_126["last"]) + count_126, 136);
+let ratio_126 = 126.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 127
+let scale_127 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 127) {
+        doubled - 127
+    } else {
+        doubled + 127
+    }
+};
+let items_127 = [127, 128, 129, 130];
+let table_127 = {"name": "section 127", "size": len(items_127), "last": last(items_127)};
+let sum_127 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_127(rest(values), acc + first(values)) }
+};
+let count_127 = 0;
+while (count_127 < table_127["size"]) {
+    count_127 = count_127 + 1;
+}
+assert_eq(sum_127(items_127, 0), 514);
+assert_eq(scale_127(table_127["last"]) + count_127, 137);
+let ratio_127 = 127.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 128
+let scale_128 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 128) {
+        doubled - 128
+    } else {
+        doubled + 128
+    }
+};
+let items_128 = [128, 129, 130, 131];
+let table_128 = {"name": "section 128", "size": len(items_128), "last": last(items_128)};
+let sum_128 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_128(rest(values), acc + first(values)) }
+};
+let count_128 = 0;
+while (count_128 < table_128["size"]) {
+    count_128 = count_128 + 1;
+}
+assert_eq(sum_128(items_128, 0), 518);
+assert_eq(scale_128(table_128["last"]) + count_128, 138);
+let ratio_128 = 128.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 129
+let scale_129 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 129) {
+        doubled - 129
+    } else {
+        doubled + 129
+    }
+};
+let items_129 = [129, 130, 131, 132];
+let table_129 = {"name": "section 129", "size": len(items_129), "last": last(items_129)};
+let sum_129 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_129(rest(values), acc + first(values)) }
+};
+let count_129 = 0;
+while (count_129 < table_129["size"]) {
+    count_129 = count_129 + 1;
+}
+assert_eq(sum_129(items_129, 0), 522);
+assert_eq(scale_129(table_129["last"]) + count_129, 139);
+let ratio_129 = 129.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 130
+let scale_130 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 130) {
+        doubled - 130
+    } else {
+        doubled + 130
+    }
+};
+let items_130 = [130, 131, 132, 133];
+let table_130 = {"name": "section 130", "size": len(items_130), "last": last(items_130)};
+let sum_130 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_130(rest(values), acc + first(values)) }
+};
+let count_130 = 0;
+while (count_130 < table_130["size"]) {
+    count_130 = count_130 + 1;
+}
+assert_eq(sum_130(items_130, 0), 526);
+assert_eq(scale_130(table_130["last"]) + count_130, 140);
+let ratio_130 = 130.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 131
+let scale_131 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 131) {
+        doubled - 131
+    } else {
+        doubled + 131
+    }
+};
+let items_131 = [131, 132, 133, 134];
+let table_131 = {"name": "section 131", "size": len(items_131), "last": last(items_131)};
+let sum_131 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_131(rest(values), acc + first(values)) }
+};
+let count_131 = 0;
+while (count_131 < table_131["size"]) {
+    count_131 = count_131 + 1;
+}
+assert_eq(sum_131(items_131, 0), 530);
+assert_eq(scale_131(table_131["last"]) + count_131, 141);
+let ratio_131 = 131.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 132
+let scale_132 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 132) {
+        doubled - 132
+    } else {
+        doubled + 132
+    }
+};
+let items_132 = [132, 133, 134, 135];
+let table_132 = {"name": "section 132", "size": len(items_132), "last": last(items_132)};
+let sum_132 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_132(rest(values), acc + first(values)) }
+};
+let count_132 = 0;
+while (count_132 < table_132["size"]) {
+    count_132 = count_132 + 1;
+}
+assert_eq(sum_132(items_132, 0), 534);
+assert_eq(scale_132(table_132["last"]) + count_132, 142);
+let ratio_132 = 132.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 133
+let scale_133 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 133) {
+        doubled - 133
+    } else {
+        doubled + 133
+    }
+};
+let items_133 = [133, 134, 135, 136];
+let table_133 = {"name": "section 133", "size": len(items_133), "last": last(items_133)};
+let sum_133 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_133(rest(values), acc + first(values)) }
+};
+let count_133 = 0;
+while (count_133 < table_133["size"]) {
+    count_133 = count_133 + 1;
+}
+assert_eq(sum_133(items_133, 0), 538);
+assert_eq(scale_133(table_133["last"]) + count_133, 143);
+let ratio_133 = 133.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 134
+let scale_134 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 134) {
+        doubled - 134
+    } else {
+        doubled + 134
+    }
+};
+let items_134 = [134, 135, 136, 137];
+let table_134 = {"name": "section 134", "size": len(items_134), "last": last(items_134)};
+let sum_134 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_134(rest(values), acc + first(values)) }
+};
+let count_134 = 0;
+while (count_134 < table_134["size"]) {
+    count_134 = count_134 + 1;
+}
+assert_eq(sum_134(items_134, 0), 542);
+assert_eq(scale_134(table_134["last"]) + count_134, 144);
+let ratio_134 = 134.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 135
+let scale_135 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 135) {
+        doubled - 135
+    } else {
+        doubled + 135
+    }
+};
+let items_135 = [135, 136, 137, 138];
+let table_135 = {"name": "section 135", "size": len(items_135), "last": last(items_135)};
+let sum_135 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_135(rest(values), acc + first(values)) }
+};
+let count_135 = 0;
+while (count_135 < table_135["size"]) {
+    count_135 = count_135 + 1;
+}
+assert_eq(sum_135(items_135, 0), 546);
+assert_eq(scale_135(table_135["last"]) + count_135, 145);
+let ratio_135 = 135.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 136
+let scale_136 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 136) {
+        doubled - 136
+    } else {
+        doubled + 136
+    }
+};
+let items_136 = [136, 137, 138, 139];
+let table_136 = {"name": "section 136", "size": len(items_136), "last": last(items_136)};
+let sum_136 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_136(rest(values), acc + first(values)) }
+};
+let count_136 = 0;
+while (count_136 < table_136["size"]) {
+    count_136 = count_136 + 1;
+}
+assert_eq(sum_136(items_136, 0), 550);
+assert_eq(scale_136(table_136["last"]) + count_136, 146);
+let ratio_136 = 136.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 137
+let scale_137 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 137) {
+        doubled - 137
+    } else {
+        doubled + 137
+    }
+};
+let items_137 = [137, 138, 139, 140];
+let table_137 = {"name": "section 137", "size": len(items_137), "last": last(items_137)};
+let sum_137 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_137(rest(values), acc + first(values)) }
+};
+let count_137 = 0;
+while (count_137 < table_137["size"]) {
+    count_137 = count_137 + 1;
+}
+assert_eq(sum_137(items_137, 0), 554);
+assert_eq(scale_137(table_137["last"]) + count_137, 147);
+let ratio_137 = 137.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 138
+let scale_138 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 138) {
+        doubled - 138
+    } else {
+        doubled + 138
+    }
+};
+let items_138 = [138, 139, 140, 141];
+let table_138 = {"name": "section 138", "size": len(items_138), "last": last(items_138)};
+let sum_138 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_138(rest(values), acc + first(values)) }
+};
+let count_138 = 0;
+while (count_138 < table_138["size"]) {
+    count_138 = count_138 + 1;
+}
+assert_eq(sum_138(items_138, 0), 558);
+assert_eq(scale_138(table_138["last"]) + count_138, 148);
+let ratio_138 = 138.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 139
+let scale_139 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 139) {
+        doubled - 139
+    } else {
+        doubled + 139
+    }
+};
+let items_139 = [139, 140, 141, 142];
+let table_139 = {"name": "section 139", "size": len(items_139), "last": last(items_139)};
+let sum_139 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_139(rest(values), acc + first(values)) }
+};
+let count_139 = 0;
+while (count_139 < table_139["size"]) {
+    count_139 = count_139 + 1;
+}
+assert_eq(sum_139(items_139, 0), 562);
+assert_eq(scale_139(table_139["last"]) + count_139, 149);
+let ratio_139 = 139.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 140
+let scale_140 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 140) {
+        doubled - 140
+    } else {
+        doubled + 140
+    }
+};
+let items_140 = [140, 141, 142, 143];
+let table_140 = {"name": "section 140", "size": len(items_140), "last": last(items_140)};
+let sum_140 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_140(rest(values), acc + first(values)) }
+};
+let count_140 = 0;
+while (count_140 < table_140["size"]) {
+    count_140 = count_140 + 1;
+}
+assert_eq(sum_140(items_140, 0), 566);
+assert_eq(scale_140(table_140["last"]) + count_140, 150);
+let ratio_140 = 140.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 141
+let scale_141 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 141) {
+        doubled - 141
+    } else {
+        doubled + 141
+    }
+};
+let items_141 = [141, 142, 143, 144];
+let table_141 = {"name": "section 141", "size": len(items_141), "last": last(items_141)};
+let sum_141 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_141(rest(values), acc + first(values)) }
+};
+let count_141 = 0;
+while (count_141 < table_141["size"]) {
+    count_141 = count_141 + 1;
+}
+assert_eq(sum_141(items_141, 0), 570);
+assert_eq(scale_141(table_141["last"]) + count_141, 151);
+let ratio_141 = 141.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 142
+let scale_142 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 142) {
+        doubled - 142
+    } else {
+        doubled + 142
+    }
+};
+let items_142 = [142, 143, 144, 145];
+let table_142 = {"name": "section 142", "size": len(items_142), "last": last(items_142)};
+let sum_142 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_142(rest(values), acc + first(values)) }
+};
+let count_142 = 0;
+while (count_142 < table_142["size"]) {
+    count_142 = count_142 + 1;
+}
+assert_eq(sum_142(items_142, 0), 574);
+assert_eq(scale_142(table_142["last"]) + count_142, 152);
+let ratio_142 = 142.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 143
+let scale_143 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 143) {
+        doubled - 143
+    } else {
+        doubled + 143
+    }
+};
+let items_143 = [143, 144, 145, 146];
+let table_143 = {"name": "section 143", "size": len(items_143), "last": last(items_143)};
+let sum_143 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_143(rest(values), acc + first(values)) }
+};
+let count_143 = 0;
+while (count_143 < table_143["size"]) {
+    count_143 = count_143 + 1;
+}
+assert_eq(sum_143(items_143, 0), 578);
+assert_eq(scale_143(table_143["last"]) + count_143, 153);
+let ratio_143 = 143.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 144
+let scale_144 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 144) {
+        doubled - 144
+    } else {
+        doubled + 144
+    }
+};
+let items_144 = [144, 145, 146, 147];
+let table_144 = {"name": "section 144", "size": len(items_144), "last": last(items_144)};
+let sum_144 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_144(rest(values), acc + first(values)) }
+};
+let count_144 = 0;
+while (count_144 < table_144["size"]) {
+    count_144 = count_144 + 1;
+}
+assert_eq(sum_144(items_144, 0), 582);
+assert_eq(scale_144(table_144["last"]) + count_144, 154);
+let ratio_144 = 144.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 145
+let scale_145 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 145) {
+        doubled - 145
+    } else {
+        doubled + 145
+    }
+};
+let items_145 = [145, 146, 147, 148];
+let table_145 = {"name": "section 145", "size": len(items_145), "last": last(items_145)};
+let sum_145 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_145(rest(values), acc + first(values)) }
+};
+let count_145 = 0;
+while (count_145 < table_145["size"]) {
+    count_145 = count_145 + 1;
+}
+assert_eq(sum_145(items_145, 0), 586);
+assert_eq(scale_145(table_145["last"]) + count_145, 155);
+let ratio_145 = 145.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 146
+let scale_146 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 146) {
+        doubled - 146
+    } else {
+        doubled + 146
+    }
+};
+let items_146 = [146, 147, 148, 149];
+let table_146 = {"name": "section 146", "size": len(items_146), "last": last(items_146)};
+let sum_146 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_146(rest(values), acc + first(values)) }
+};
+let count_146 = 0;
+while (count_146 < table_146["size"]) {
+    count_146 = count_146 + 1;
+}
+assert_eq(sum_146(items_146, 0), 590);
+assert_eq(scale_146(table_146["last"]) + count_146, 156);
+let ratio_146 = 146.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 147
+let scale_147 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 147) {
+        doubled - 147
+    } else {
+        doubled + 147
+    }
+};
+let items_147 = [147, 148, 149, 150];
+let table_147 = {"name": "section 147", "size": len(items_147), "last": last(items_147)};
+let sum_147 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_147(rest(values), acc + first(values)) }
+};
+let count_147 = 0;
+while (count_147 < table_147["size"]) {
+    count_147 = count_147 + 1;
+}
+assert_eq(sum_147(items_147, 0), 594);
+assert_eq(scale_147(table_147["last"]) + count_147, 157);
+let ratio_147 = 147.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 148
+let scale_148 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 148) {
+        doubled - 148
+    } else {
+        doubled + 148
+    }
+};
+let items_148 = [148, 149, 150, 151];
+let table_148 = {"name": "section 148", "size": len(items_148), "last": last(items_148)};
+let sum_148 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_148(rest(values), acc + first(values)) }
+};
+let count_148 = 0;
+while (count_148 < table_148["size"]) {
+    count_148 = count_148 + 1;
+}
+assert_eq(sum_148(items_148, 0), 598);
+assert_eq(scale_148(table_148["last"]) + count_148, 158);
+let ratio_148 = 148.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 149
+let scale_149 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 149) {
+        doubled - 149
+    } else {
+        doubled + 149
+    }
+};
+let items_149 = [149, 150, 151, 152];
+let table_149 = {"name": "section 149", "size": len(items_149), "last": last(items_149)};
+let sum_149 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_149(rest(values), acc + first(values)) }
+};
+let count_149 = 0;
+while (count_149 < table_149["size"]) {
+    count_149 = count_149 + 1;
+}
+assert_eq(sum_149(items_149, 0), 602);
+assert_eq(scale_149(table_149["last"]) + count_149, 159);
+let ratio_149 = 149.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 150
+let scale_150 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 150) {
+        doubled - 150
+    } else {
+        doubled + 150
+    }
+};
+let items_150 = [150, 151, 152, 153];
+let table_150 = {"name": "section 150", "size": len(items_150), "last": last(items_150)};
+let sum_150 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_150(rest(values), acc + first(values)) }
+};
+let count_150 = 0;
+while (count_150 < table_150["size"]) {
+    count_150 = count_150 + 1;
+}
+assert_eq(sum_150(items_150, 0), 606);
+assert_eq(scale_150(table_150["last"]) + count_150, 160);
+let ratio_150 = 150.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 151
+let scale_151 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 151) {
+        doubled - 151
+    } else {
+        doubled + 151
+    }
+};
+let items_151 = [151, 152, 153, 154];
+let table_151 = {"name": "section 151", "size": len(items_151), "last": last(items_151)};
+let sum_151 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_151(rest(values), acc + first(values)) }
+};
+let count_151 = 0;
+while (count_151 < table_151["size"]) {
+    count_151 = count_151 + 1;
+}
+assert_eq(sum_151(items_151, 0), 610);
+assert_eq(scale_151(table_151["last"]) + count_151, 161);
+let ratio_151 = 151.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 152
+let scale_152 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 152) {
+        doubled - 152
+    } else {
+        doubled + 152
+    }
+};
+let items_152 = [152, 153, 154, 155];
+let table_152 = {"name": "section 152", "size": len(items_152), "last": last(items_152)};
+let sum_152 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_152(rest(values), acc + first(values)) }
+};
+let count_152 = 0;
+while (count_152 < table_152["size"]) {
+    count_152 = count_152 + 1;
+}
+assert_eq(sum_152(items_152, 0), 614);
+assert_eq(scale_152(table_152["last"]) + count_152, 162);
+let ratio_152 = 152.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 153
+let scale_153 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 153) {
+        doubled - 153
+    } else {
+        doubled + 153
+    }
+};
+let items_153 = [153, 154, 155, 156];
+let table_153 = {"name": "section 153", "size": len(items_153), "last": last(items_153)};
+let sum_153 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_153(rest(values), acc + first(values)) }
+};
+let count_153 = 0;
+while (count_153 < table_153["size"]) {
+    count_153 = count_153 + 1;
+}
+assert_eq(sum_153(items_153, 0), 618);
+assert_eq(scale_153(table_153["last"]) + count_153, 163);
+let ratio_153 = 153.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 154
+let scale_154 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 154) {
+        doubled - 154
+    } else {
+        doubled + 154
+    }
+};
+let items_154 = [154, 155, 156, 157];
+let table_154 = {"name": "section 154", "size": len(items_154), "last": last(items_154)};
+let sum_154 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_154(rest(values), acc + first(values)) }
+};
+let count_154 = 0;
+while (count_154 < table_154["size"]) {
+    count_154 = count_154 + 1;
+}
+assert_eq(sum_154(items_154, 0), 622);
+assert_eq(scale_154(table_154["last"]) + count_154, 164);
+let ratio_154 = 154.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 155
+let scale_155 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 155) {
+        doubled - 155
+    } else {
+        doubled + 155
+    }
+};
+let items_155 = [155, 156, 157, 158];
+let table_155 = {"name": "section 155", "size": len(items_155), "last": last(items_155)};
+let sum_155 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_155(rest(values), acc + first(values)) }
+};
+let count_155 = 0;
+while (count_155 < table_155["size"]) {
+    count_155 = count_155 + 1;
+}
+assert_eq(sum_155(items_155, 0), 626);
+assert_eq(scale_155(table_155["last"]) + count_155, 165);
+let ratio_155 = 155.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 156
+let scale_156 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 156) {
+        doubled - 156
+    } else {
+        doubled + 156
+    }
+};
+let items_156 = [156, 157, 158, 159];
+let table_156 = {"name": "section 156", "size": len(items_156), "last": last(items_156)};
+let sum_156 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_156(rest(values), acc + first(values)) }
+};
+let count_156 = 0;
+while (count_156 < table_156["size"]) {
+    count_156 = count_156 + 1;
+}
+assert_eq(sum_156(items_156, 0), 630);
+assert_eq(scale_156(table_156["last"]) + count_156, 166);
+let ratio_156 = 156.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 157
+let scale_157 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 157) {
+        doubled - 157
+    } else {
+        doubled + 157
+    }
+};
+let items_157 = [157, 158, 159, 160];
+let table_157 = {"name": "section 157", "size": len(items_157), "last": last(items_157)};
+let sum_157 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_157(rest(values), acc + first(values)) }
+};
+let count_157 = 0;
+while (count_157 < table_157["size"]) {
+    count_157 = count_157 + 1;
+}
+assert_eq(sum_157(items_157, 0), 634);
+assert_eq(scale_157(table_157["last"]) + count_157, 167);
+let ratio_157 = 157.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 158
+let scale_158 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 158) {
+        doubled - 158
+    } else {
+        doubled + 158
+    }
+};
+let items_158 = [158, 159, 160, 161];
+let table_158 = {"name": "section 158", "size": len(items_158), "last": last(items_158)};
+let sum_158 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_158(rest(values), acc + first(values)) }
+};
+let count_158 = 0;
+while (count_158 < table_158["size"]) {
+    count_158 = count_158 + 1;
+}
+assert_eq(sum_158(items_158, 0), 638);
+assert_eq(scale_158(table_158["last"]) + count_158, 168);
+let ratio_158 = 158.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 159
+let scale_159 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 159) {
+        doubled - 159
+    } else {
+        doubled + 159
+    }
+};
+let items_159 = [159, 160, 161, 162];
+let table_159 = {"name": "section 159", "size": len(items_159), "last": last(items_159)};
+let sum_159 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_159(rest(values), acc + first(values)) }
+};
+let count_159 = 0;
+while (count_159 < table_159["size"]) {
+    count_159 = count_159 + 1;
+}
+assert_eq(sum_159(items_159, 0), 642);
+assert_eq(scale_159(table_159["last"]) + count_159, 169);
+let ratio_159 = 159.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 160
+let scale_160 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 160) {
+        doubled - 160
+    } else {
+        doubled + 160
+    }
+};
+let items_160 = [160, 161, 162, 163];
+let table_160 = {"name": "section 160", "size": len(items_160), "last": last(items_160)};
+let sum_160 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_160(rest(values), acc + first(values)) }
+};
+let count_160 = 0;
+while (count_160 < table_160["size"]) {
+    count_160 = count_160 + 1;
+}
+assert_eq(sum_160(items_160, 0), 646);
+assert_eq(scale_160(table_160["last"]) + count_160, 170);
+let ratio_160 = 160.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 161
+let scale_161 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 161) {
+        doubled - 161
+    } else {
+        doubled + 161
+    }
+};
+let items_161 = [161, 162, 163, 164];
+let table_161 = {"name": "section 161", "size": len(items_161), "last": last(items_161)};
+let sum_161 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_161(rest(values), acc + first(values)) }
+};
+let count_161 = 0;
+while (count_161 < table_161["size"]) {
+    count_161 = count_161 + 1;
+}
+assert_eq(sum_161(items_161, 0), 650);
+assert_eq(scale_161(table_161["last"]) + count_161, 171);
+let ratio_161 = 161.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 162
+let scale_162 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 162) {
+        doubled - 162
+    } else {
+        doubled + 162
+    }
+};
+let items_162 = [162, 163, 164, 165];
+let table_162 = {"name": "section 162", "size": len(items_162), "last": last(items_162)};
+let sum_162 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_162(rest(values), acc + first(values)) }
+};
+let count_162 = 0;
+while (count_162 < table_162["size"]) {
+    count_162 = count_162 + 1;
+}
+assert_eq(sum_162(items_162, 0), 654);
+assert_eq(scale_162(table_162["last"]) + count_162, 172);
+let ratio_162 = 162.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 163
+let scale_163 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 163) {
+        doubled - 163
+    } else {
+        doubled + 163
+    }
+};
+let items_163 = [163, 164, 165, 166];
+let table_163 = {"name": "section 163", "size": len(items_163), "last": last(items_163)};
+let sum_163 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_163(rest(values), acc + first(values)) }
+};
+let count_163 = 0;
+while (count_163 < table_163["size"]) {
+    count_163 = count_163 + 1;
+}
+assert_eq(sum_163(items_163, 0), 658);
+assert_eq(scale_163(table_163["last"]) + count_163, 173);
+let ratio_163 = 163.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 164
+let scale_164 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 164) {
+        doubled - 164
+    } else {
+        doubled + 164
+    }
+};
+let items_164 = [164, 165, 166, 167];
+let table_164 = {"name": "section 164", "size": len(items_164), "last": last(items_164)};
+let sum_164 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_164(rest(values), acc + first(values)) }
+};
+let count_164 = 0;
+while (count_164 < table_164["size"]) {
+    count_164 = count_164 + 1;
+}
+assert_eq(sum_164(items_164, 0), 662);
+assert_eq(scale_164(table_164["last"]) + count_164, 174);
+let ratio_164 = 164.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 165
+let scale_165 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 165) {
+        doubled - 165
+    } else {
+        doubled + 165
+    }
+};
+let items_165 = [165, 166, 167, 168];
+let table_165 = {"name": "section 165", "size": len(items_165), "last": last(items_165)};
+let sum_165 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_165(rest(values), acc + first(values)) }
+};
+let count_165 = 0;
+while (count_165 < table_165["size"]) {
+    count_165 = count_165 + 1;
+}
+assert_eq(sum_165(items_165, 0), 666);
+assert_eq(scale_165(table_165["last"]) + count_165, 175);
+let ratio_165 = 165.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 166
+let scale_166 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 166) {
+        doubled - 166
+    } else {
+        doubled + 166
+    }
+};
+let items_166 = [166, 167, 168, 169];
+let table_166 = {"name": "section 166", "size": len(items_166), "last": last(items_166)};
+let sum_166 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_166(rest(values), acc + first(values)) }
+};
+let count_166 = 0;
+while (count_166 < table_166["size"]) {
+    count_166 = count_166 + 1;
+}
+assert_eq(sum_166(items_166, 0), 670);
+assert_eq(scale_166(table_166["last"]) + count_166, 176);
+let ratio_166 = 166.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 167
+let scale_167 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 167) {
+        doubled - 167
+    } else {
+        doubled + 167
+    }
+};
+let items_167 = [167, 168, 169, 170];
+let table_167 = {"name": "section 167", "size": len(items_167), "last": last(items_167)};
+let sum_167 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_167(rest(values), acc + first(values)) }
+};
+let count_167 = 0;
+while (count_167 < table_167["size"]) {
+    count_167 = count_167 + 1;
+}
+assert_eq(sum_167(items_167, 0), 674);
+assert_eq(scale_167(table_167["last"]) + count_167, 177);
+let ratio_167 = 167.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 168
+let scale_168 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 168) {
+        doubled - 168
+    } else {
+        doubled + 168
+    }
+};
+let items_168 = [168, 169, 170, 171];
+let table_168 = {"name": "section 168", "size": len(items_168), "last": last(items_168)};
+let sum_168 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_168(rest(values), acc + first(values)) }
+};
+let count_168 = 0;
+while (count_168 < table_168["size"]) {
+    count_168 = count_168 + 1;
+}
+assert_eq(sum_168(items_168, 0), 678);
+assert_eq(scale_168(table_168["last"]) + count_168, 178);
+let ratio_168 = 168.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 169
+let scale_169 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 169) {
+        doubled - 169
+    } else {
+        doubled + 169
+    }
+};
+let items_169 = [169, 170, 171, 172];
+let table_169 = {"name": "section 169", "size": len(items_169), "last": last(items_169)};
+let sum_169 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_169(rest(values), acc + first(values)) }
+};
+let count_169 = 0;
+while (count_169 < table_169["size"]) {
+    count_169 = count_169 + 1;
+}
+assert_eq(sum_169(items_169, 0), 682);
+assert_eq(scale_169(table_169["last"]) + count_169, 179);
+let ratio_169 = 169.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 170
+let scale_170 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 170) {
+        doubled - 170
+    } else {
+        doubled + 170
+    }
+};
+let items_170 = [170, 171, 172, 173];
+let table_170 = {"name": "section 170", "size": len(items_170), "last": last(items_170)};
+let sum_170 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_170(rest(values), acc + first(values)) }
+};
+let count_170 = 0;
+while (count_170 < table_170["size"]) {
+    count_170 = count_170 + 1;
+}
+assert_eq(sum_170(items_170, 0), 686);
+assert_eq(scale_170(table_170["last"]) + count_170, 180);
+let ratio_170 = 170.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 171
+let scale_171 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 171) {
+        doubled - 171
+    } else {
+        doubled + 171
+    }
+};
+let items_171 = [171, 172, 173, 174];
+let table_171 = {"name": "section 171", "size": len(items_171), "last": last(items_171)};
+let sum_171 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_171(rest(values), acc + first(values)) }
+};
+let count_171 = 0;
+while (count_171 < table_171["size"]) {
+    count_171 = count_171 + 1;
+}
+assert_eq(sum_171(items_171, 0), 690);
+assert_eq(scale_171(table_171["last"]) + count_171, 181);
+let ratio_171 = 171.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 172
+let scale_172 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 172) {
+        doubled - 172
+    } else {
+        doubled + 172
+    }
+};
+let items_172 = [172, 173, 174, 175];
+let table_172 = {"name": "section 172", "size": len(items_172), "last": last(items_172)};
+let sum_172 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_172(rest(values), acc + first(values)) }
+};
+let count_172 = 0;
+while (count_172 < table_172["size"]) {
+    count_172 = count_172 + 1;
+}
+assert_eq(sum_172(items_172, 0), 694);
+assert_eq(scale_172(table_172["last"]) + count_172, 182);
+let ratio_172 = 172.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 173
+let scale_173 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 173) {
+        doubled - 173
+    } else {
+        doubled + 173
+    }
+};
+let items_173 = [173, 174, 175, 176];
+let table_173 = {"name": "section 173", "size": len(items_173), "last": last(items_173)};
+let sum_173 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_173(rest(values), acc + first(values)) }
+};
+let count_173 = 0;
+while (count_173 < table_173["size"]) {
+    count_173 = count_173 + 1;
+}
+assert_eq(sum_173(items_173, 0), 698);
+assert_eq(scale_173(table_173["last"]) + count_173, 183);
+let ratio_173 = 173.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 174
+let scale_174 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 174) {
+        doubled - 174
+    } else {
+        doubled + 174
+    }
+};
+let items_174 = [174, 175, 176, 177];
+let table_174 = {"name": "section 174", "size": len(items_174), "last": last(items_174)};
+let sum_174 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_174(rest(values), acc + first(values)) }
+};
+let count_174 = 0;
+while (count_174 < table_174["size"]) {
+    count_174 = count_174 + 1;
+}
+assert_eq(sum_174(items_174, 0), 702);
+assert_eq(scale_174(table_174["last"]) + count_174, 184);
+let ratio_174 = 174.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 175
+let scale_175 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 175) {
+        doubled - 175
+    } else {
+        doubled + 175
+    }
+};
+let items_175 = [175, 176, 177, 178];
+let table_175 = {"name": "section 175", "size": len(items_175), "last": last(items_175)};
+let sum_175 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_175(rest(values), acc + first(values)) }
+};
+let count_175 = 0;
+while (count_175 < table_175["size"]) {
+    count_175 = count_175 + 1;
+}
+assert_eq(sum_175(items_175, 0), 706);
+assert_eq(scale_175(table_175["last"]) + count_175, 185);
+let ratio_175 = 175.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 176
+let scale_176 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 176) {
+        doubled - 176
+    } else {
+        doubled + 176
+    }
+};
+let items_176 = [176, 177, 178, 179];
+let table_176 = {"name": "section 176", "size": len(items_176), "last": last(items_176)};
+let sum_176 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_176(rest(values), acc + first(values)) }
+};
+let count_176 = 0;
+while (count_176 < table_176["size"]) {
+    count_176 = count_176 + 1;
+}
+assert_eq(sum_176(items_176, 0), 710);
+assert_eq(scale_176(table_176["last"]) + count_176, 186);
+let ratio_176 = 176.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 177
+let scale_177 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 177) {
+        doubled - 177
+    } else {
+        doubled + 177
+    }
+};
+let items_177 = [177, 178, 179, 180];
+let table_177 = {"name": "section 177", "size": len(items_177), "last": last(items_177)};
+let sum_177 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_177(rest(values), acc + first(values)) }
+};
+let count_177 = 0;
+while (count_177 < table_177["size"]) {
+    count_177 = count_177 + 1;
+}
+assert_eq(sum_177(items_177, 0), 714);
+assert_eq(scale_177(table_177["last"]) + count_177, 187);
+let ratio_177 = 177.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 178
+let scale_178 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 178) {
+        doubled - 178
+    } else {
+        doubled + 178
+    }
+};
+let items_178 = [178, 179, 180, 181];
+let table_178 = {"name": "section 178", "size": len(items_178), "last": last(items_178)};
+let sum_178 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_178(rest(values), acc + first(values)) }
+};
+let count_178 = 0;
+while (count_178 < table_178["size"]) {
+    count_178 = count_178 + 1;
+}
+assert_eq(sum_178(items_178, 0), 718);
+assert_eq(scale_178(table_178["last"]) + count_178, 188);
+let ratio_178 = 178.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 179
+let scale_179 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 179) {
+        doubled - 179
+    } else {
+        doubled + 179
+    }
+};
+let items_179 = [179, 180, 181, 182];
+let table_179 = {"name": "section 179", "size": len(items_179), "last": last(items_179)};
+let sum_179 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_179(rest(values), acc + first(values)) }
+};
+let count_179 = 0;
+while (count_179 < table_179["size"]) {
+    count_179 = count_179 + 1;
+}
+assert_eq(sum_179(items_179, 0), 722);
+assert_eq(scale_179(table_179["last"]) + count_179, 189);
+let ratio_179 = 179.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 180
+let scale_180 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 180) {
+        doubled - 180
+    } else {
+        doubled + 180
+    }
+};
+let items_180 = [180, 181, 182, 183];
+let table_180 = {"name": "section 180", "size": len(items_180), "last": last(items_180)};
+let sum_180 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_180(rest(values), acc + first(values)) }
+};
+let count_180 = 0;
+while (count_180 < table_180["size"]) {
+    count_180 = count_180 + 1;
+}
+assert_eq(sum_180(items_180, 0), 726);
+assert_eq(scale_180(table_180["last"]) + count_180, 190);
+let ratio_180 = 180.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 181
+let scale_181 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 181) {
+        doubled - 181
+    } else {
+        doubled + 181
+    }
+};
+let items_181 = [181, 182, 183, 184];
+let table_181 = {"name": "section 181", "size": len(items_181), "last": last(items_181)};
+let sum_181 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_181(rest(values), acc + first(values)) }
+};
+let count_181 = 0;
+while (count_181 < table_181["size"]) {
+    count_181 = count_181 + 1;
+}
+assert_eq(sum_181(items_181, 0), 730);
+assert_eq(scale_181(table_181["last"]) + count_181, 191);
+let ratio_181 = 181.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 182
+let scale_182 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 182) {
+        doubled - 182
+    } else {
+        doubled + 182
+    }
+};
+let items_182 = [182, 183, 184, 185];
+let table_182 = {"name": "section 182", "size": len(items_182), "last": last(items_182)};
+let sum_182 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_182(rest(values), acc + first(values)) }
+};
+let count_182 = 0;
+while (count_182 < table_182["size"]) {
+    count_182 = count_182 + 1;
+}
+assert_eq(sum_182(items_182, 0), 734);
+assert_eq(scale_182(table_182["last"]) + count_182, 192);
+let ratio_182 = 182.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 183
+let scale_183 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 183) {
+        doubled - 183
+    } else {
+        doubled + 183
+    }
+};
+let items_183 = [183, 184, 185, 186];
+let table_183 = {"name": "section 183", "size": len(items_183), "last": last(items_183)};
+let sum_183 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_183(rest(values), acc + first(values)) }
+};
+let count_183 = 0;
+while (count_183 < table_183["size"]) {
+    count_183 = count_183 + 1;
+}
+assert_eq(sum_183(items_183, 0), 738);
+assert_eq(scale_183(table_183["last"]) + count_183, 193);
+let ratio_183 = 183.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 184
+let scale_184 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 184) {
+        doubled - 184
+    } else {
+        doubled + 184
+    }
+};
+let items_184 = [184, 185, 186, 187];
+let table_184 = {"name": "section 184", "size": len(items_184), "last": last(items_184)};
+let sum_184 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_184(rest(values), acc + first(values)) }
+};
+let count_184 = 0;
+while (count_184 < table_184["size"]) {
+    count_184 = count_184 + 1;
+}
+assert_eq(sum_184(items_184, 0), 742);
+assert_eq(scale_184(table_184["last"]) + count_184, 194);
+let ratio_184 = 184.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 185
+let scale_185 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 185) {
+        doubled - 185
+    } else {
+        doubled + 185
+    }
+};
+let items_185 = [185, 186, 187, 188];
+let table_185 = {"name": "section 185", "size": len(items_185), "last": last(items_185)};
+let sum_185 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_185(rest(values), acc + first(values)) }
+};
+let count_185 = 0;
+while (count_185 < table_185["size"]) {
+    count_185 = count_185 + 1;
+}
+assert_eq(sum_185(items_185, 0), 746);
+assert_eq(scale_185(table_185["last"]) + count_185, 195);
+let ratio_185 = 185.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 186
+let scale_186 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 186) {
+        doubled - 186
+    } else {
+        doubled + 186
+    }
+};
+let items_186 = [186, 187, 188, 189];
+let table_186 = {"name": "section 186", "size": len(items_186), "last": last(items_186)};
+let sum_186 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_186(rest(values), acc + first(values)) }
+};
+let count_186 = 0;
+while (count_186 < table_186["size"]) {
+    count_186 = count_186 + 1;
+}
+assert_eq(sum_186(items_186, 0), 750);
+assert_eq(scale_186(table_186["last"]) + count_186, 196);
+let ratio_186 = 186.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 187
+let scale_187 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 187) {
+        doubled - 187
+    } else {
+        doubled + 187
+    }
+};
+let items_187 = [187, 188, 189, 190];
+let table_187 = {"name": "section 187", "size": len(items_187), "last": last(items_187)};
+let sum_187 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_187(rest(values), acc + first(values)) }
+};
+let count_187 = 0;
+while (count_187 < table_187["size"]) {
+    count_187 = count_187 + 1;
+}
+assert_eq(sum_187(items_187, 0), 754);
+assert_eq(scale_187(table_187["last"]) + count_187, 197);
+let ratio_187 = 187.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 188
+let scale_188 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 188) {
+        doubled - 188
+    } else {
+        doubled + 188
+    }
+};
+let items_188 = [188, 189, 190, 191];
+let table_188 = {"name": "section 188", "size": len(items_188), "last": last(items_188)};
+let sum_188 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_188(rest(values), acc + first(values)) }
+};
+let count_188 = 0;
+while (count_188 < table_188["size"]) {
+    count_188 = count_188 + 1;
+}
+assert_eq(sum_188(items_188, 0), 758);
+assert_eq(scale_188(table_188["last"]) + count_188, 198);
+let ratio_188 = 188.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 189
+let scale_189 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 189) {
+        doubled - 189
+    } else {
+        doubled + 189
+    }
+};
+let items_189 = [189, 190, 191, 192];
+let table_189 = {"name": "section 189", "size": len(items_189), "last": last(items_189)};
+let sum_189 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_189(rest(values), acc + first(values)) }
+};
+let count_189 = 0;
+while (count_189 < table_189["size"]) {
+    count_189 = count_189 + 1;
+}
+assert_eq(sum_189(items_189, 0), 762);
+assert_eq(scale_189(table_189["last"]) + count_189, 199);
+let ratio_189 = 189.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 190
+let scale_190 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 190) {
+        doubled - 190
+    } else {
+        doubled + 190
+    }
+};
+let items_190 = [190, 191, 192, 193];
+let table_190 = {"name": "section 190", "size": len(items_190), "last": last(items_190)};
+let sum_190 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_190(rest(values), acc + first(values)) }
+};
+let count_190 = 0;
+while (count_190 < table_190["size"]) {
+    count_190 = count_190 + 1;
+}
+assert_eq(sum_190(items_190, 0), 766);
+assert_eq(scale_190(table_190["last"]) + count_190, 200);
+let ratio_190 = 190.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 191
+let scale_191 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 191) {
+        doubled - 191
+    } else {
+        doubled + 191
+    }
+};
+let items_191 = [191, 192, 193, 194];
+let table_191 = {"name": "section 191", "size": len(items_191), "last": last(items_191)};
+let sum_191 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_191(rest(values), acc + first(values)) }
+};
+let count_191 = 0;
+while (count_191 < table_191["size"]) {
+    count_191 = count_191 + 1;
+}
+assert_eq(sum_191(items_191, 0), 770);
+assert_eq(scale_191(table_191["last"]) + count_191, 201);
+let ratio_191 = 191.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 192
+let scale_192 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 192) {
+        doubled - 192
+    } else {
+        doubled + 192
+    }
+};
+let items_192 = [192, 193, 194, 195];
+let table_192 = {"name": "section 192", "size": len(items_192), "last": last(items_192)};
+let sum_192 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_192(rest(values), acc + first(values)) }
+};
+let count_192 = 0;
+while (count_192 < table_192["size"]) {
+    count_192 = count_192 + 1;
+}
+assert_eq(sum_192(items_192, 0), 774);
+assert_eq(scale_192(table_192["last"]) + count_192, 202);
+let ratio_192 = 192.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 193
+let scale_193 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 193) {
+        doubled - 193
+    } else {
+        doubled + 193
+    }
+};
+let items_193 = [193, 194, 195, 196];
+let table_193 = {"name": "section 193", "size": len(items_193), "last": last(items_193)};
+let sum_193 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_193(rest(values), acc + first(values)) }
+};
+let count_193 = 0;
+while (count_193 < table_193["size"]) {
+    count_193 = count_193 + 1;
+}
+assert_eq(sum_193(items_193, 0), 778);
+assert_eq(scale_193(table_193["last"]) + count_193, 203);
+let ratio_193 = 193.5 / 2.0 + 4 * 1.25 - 0.5;
+
+// Section 194
+let scale_194 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 194) {
+        doubled - 194
+    } else {
+        doubled + 194
+    }
+};
+let items_194 = [194, 195, 196, 197];
+let table_194 = {"name": "section 194", "size": len(items_194), "last": last(items_194)};
+let sum_194 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_194(rest(values), acc + first(values)) }
+};
+let count_194 = 0;
+while (count_194 < table_194["size"]) {
+    count_194 = count_194 + 1;
+}
+assert_eq(sum_194(items_194, 0), 782);
+assert_eq(scale_194(table_194["last"]) + count_194, 204);
+let ratio_194 = 194.5 / 2.0 + 5 * 1.25 - 0.5;
+
+// Section 195
+let scale_195 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 195) {
+        doubled - 195
+    } else {
+        doubled + 195
+    }
+};
+let items_195 = [195, 196, 197, 198];
+let table_195 = {"name": "section 195", "size": len(items_195), "last": last(items_195)};
+let sum_195 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_195(rest(values), acc + first(values)) }
+};
+let count_195 = 0;
+while (count_195 < table_195["size"]) {
+    count_195 = count_195 + 1;
+}
+assert_eq(sum_195(items_195, 0), 786);
+assert_eq(scale_195(table_195["last"]) + count_195, 205);
+let ratio_195 = 195.5 / 2.0 + 6 * 1.25 - 0.5;
+
+// Section 196
+let scale_196 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 196) {
+        doubled - 196
+    } else {
+        doubled + 196
+    }
+};
+let items_196 = [196, 197, 198, 199];
+let table_196 = {"name": "section 196", "size": len(items_196), "last": last(items_196)};
+let sum_196 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_196(rest(values), acc + first(values)) }
+};
+let count_196 = 0;
+while (count_196 < table_196["size"]) {
+    count_196 = count_196 + 1;
+}
+assert_eq(sum_196(items_196, 0), 790);
+assert_eq(scale_196(table_196["last"]) + count_196, 206);
+let ratio_196 = 196.5 / 2.0 + 0 * 1.25 - 0.5;
+
+// Section 197
+let scale_197 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 197) {
+        doubled - 197
+    } else {
+        doubled + 197
+    }
+};
+let items_197 = [197, 198, 199, 200];
+let table_197 = {"name": "section 197", "size": len(items_197), "last": last(items_197)};
+let sum_197 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_197(rest(values), acc + first(values)) }
+};
+let count_197 = 0;
+while (count_197 < table_197["size"]) {
+    count_197 = count_197 + 1;
+}
+assert_eq(sum_197(items_197, 0), 794);
+assert_eq(scale_197(table_197["last"]) + count_197, 207);
+let ratio_197 = 197.5 / 2.0 + 1 * 1.25 - 0.5;
+
+// Section 198
+let scale_198 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 198) {
+        doubled - 198
+    } else {
+        doubled + 198
+    }
+};
+let items_198 = [198, 199, 200, 201];
+let table_198 = {"name": "section 198", "size": len(items_198), "last": last(items_198)};
+let sum_198 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_198(rest(values), acc + first(values)) }
+};
+let count_198 = 0;
+while (count_198 < table_198["size"]) {
+    count_198 = count_198 + 1;
+}
+assert_eq(sum_198(items_198, 0), 798);
+assert_eq(scale_198(table_198["last"]) + count_198, 208);
+let ratio_198 = 198.5 / 2.0 + 2 * 1.25 - 0.5;
+
+// Section 199
+let scale_199 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 199) {
+        doubled - 199
+    } else {
+        doubled + 199
+    }
+};
+let items_199 = [199, 200, 201, 202];
+let table_199 = {"name": "section 199", "size": len(items_199), "last": last(items_199)};
+let sum_199 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_199(rest(values), acc + first(values)) }
+};
+let count_199 = 0;
+while (count_199 < table_199["size"]) {
+    count_199 = count_199 + 1;
+}
+assert_eq(sum_199(items_199, 0), 802);
+assert_eq(scale_199(table_199["last"]) + count_199, 209);
+let ratio_199 = 199.5 / 2.0 + 3 * 1.25 - 0.5;
+
+// Section 200
+let scale_200 = fn(x) {
+    let doubled = x * 2;
+    if (doubled > 200) {
+        doubled - 200
+    } else {
+        doubled + 200
+    }
+};
+let items_200 = [200, 201, 202, 203];
+let table_200 = {"name": "section 200", "size": len(items_200), "last": last(items_200)};
+let sum_200 = fn(values, acc) {
+    if (len(values) == 0) { acc } else { sum_200(rest(values), acc + first(values)) }
+};
+let count_200 = 0;
+while (count_200 < table_200["size"]) {
+    count_200 = count_200 + 1;
+}
+assert_eq(sum_200(items_200, 0), 806);
+assert_eq(scale_200(table_200["last"]) + count_200, 210);
+let ratio_200 = 200.5 / 2.0 + 4 * 1.25 - 0.5;
@@ -0,0 +1,103 @@
+//! Throughput of the lexer and parser over `fixtures/reference.mk`, and evaluation time of
+//! that program and of `fibonacci(25)` on each engine, and on the VM of a recursive program
+//! calling small helpers, with and without hot functions inlined (`--opt=2`).
+//!
+//! Run with `cargo bench`; criterion compares each run with the last one.
+
+use std::{cell::RefCell, hint::black_box, rc::Rc};
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use rustmonk::{
+    environment::Environment,
+    eval::{Engine, Program},
+    lexer::Lexer,
+    parser::Parser,
+};
+
+const REFERENCE: &str = include_str!("fixtures/reference.mk");
+
+const FIBONACCI: &str = "let fibonacci = fn(n) {
+    if (n < 2) { n } else { fibonacci(n - 1) + fibonacci(n - 2) }
+};
+fibonacci(25);";
+
+#[cfg(feature = "vm")]
+const HELPERS: &str = "let add = fn(a, b) { a + b };
+let less = fn(a, b) { a < b };
+let fibonacci = fn(n) {
+    if (less(n, 2)) { n } else { add(fibonacci(n - 1), fibonacci(n - 2)) }
+};
+fibonacci(25);";
+
+const ENGINES: &[Engine] = &[
+    Engine::Tree,
+    #[cfg(feature = "vm")]
+    Engine::Vm,
+];
+
+fn eval(source: &str, engine: Engine) {
+    let mut parser = Parser::new(Lexer::new(source).tokens());
+    let env = Rc::new(RefCell::new(Environment::default()));
+    Program::with_engine(engine)
+        .eval(&mut parser, env)
+        .expect("benchmark program runs");
+}
+
+fn lexer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lexer");
+    group.throughput(Throughput::Elements(Lexer::new(REFERENCE).count() as u64));
+    group.bench_function("reference", |b| {
+        b.iter(|| Lexer::new(black_box(REFERENCE)).count())
+    });
+    group.finish();
+}
+
+fn parser(c: &mut Criterion) {
+    let statements = Parser::new(Lexer::new(REFERENCE).tokens()).count();
+    let mut group = c.benchmark_group("parser");
+    group.throughput(Throughput::Elements(statements as u64));
+    group.bench_function("reference", |b| {
+        b.iter(|| Parser::new(Lexer::new(black_box(REFERENCE)).tokens()).count())
+    });
+    group.finish();
+}
+
+fn evaluation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("eval");
+    group.sample_size(10);
+    for &engine in ENGINES {
+        group.bench_function(format!("reference/{}", engine.name()), |b| {
+            b.iter(|| eval(black_box(REFERENCE), engine))
+        });
+        group.bench_function(format!("fibonacci/{}", engine.name()), |b| {
+            b.iter(|| eval(black_box(FIBONACCI), engine))
+        });
+    }
+    group.finish();
+}
+
+#[cfg(feature = "vm")]
+fn inlining(c: &mut Criterion) {
+    let mut group = c.benchmark_group("inlining");
+    group.sample_size(10);
+    for (name, inline_hot) in [("off", false), ("hot", true)] {
+        group.bench_function(format!("helpers/{name}"), |b| {
+            b.iter(|| {
+                let mut parser = Parser::new(Lexer::new(black_box(HELPERS)).tokens());
+                let env = Rc::new(RefCell::new(Environment::default()));
+                Program::with_engine(Engine::Vm)
+                    .optimized(true)
+                    .inline_hot(inline_hot)
+                    .eval(&mut parser, env)
+                    .expect("benchmark program runs")
+            })
+        });
+    }
+    group.finish();
+}
+
+#[cfg(not(feature = "vm"))]
+fn inlining(_: &mut Criterion) {}
+
+criterion_group!(benches, lexer, parser, evaluation, inlining);
+criterion_main!(benches);
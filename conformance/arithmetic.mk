@@ -0,0 +1 @@
+(1 + 2) * 3 - 10 / 4 + 17 % 5
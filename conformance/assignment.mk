@@ -0,0 +1,6 @@
+let i = 0;
+let total = 0;
+while (i < 5) { total = total + i; i = i + 1; }
+let counter = fn() { i = i * 2 };
+counter();
+[i, total]
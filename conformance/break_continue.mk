@@ -0,0 +1,7 @@
+let total = 0;
+for (n in range(0, 100)) {
+  if (n > 6) { break }
+  if (n % 3 == 0) { continue }
+  total = total + n;
+}
+total
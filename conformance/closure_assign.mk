@@ -0,0 +1,9 @@
+let n = 0;
+let inc = fn() { n = n + 1 };
+inc();
+inc();
+let counter = fn() { let c = 0; let add = fn(by) { c = c + by; c }; add(2); add(3); c };
+let fs = [];
+for (x in [1, 2]) { fs = push(fs, fn() { x = x * 10; x }); }
+let make = fn(start) { let bump = fn() { start = start + 1 }; bump(); start };
+[n, counter(), fs[0](), fs[0](), fs[1](), make(5)]
@@ -0,0 +1,3 @@
+let adder = fn(x) { fn(y) { x + y } };
+let addTwo = adder(2);
+addTwo(40)
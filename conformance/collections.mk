@@ -0,0 +1,2 @@
+let h = {"one": 1, "two": 2};
+[h["one"] + h["two"], [1, 2, 3][1], h["three"], len(h)]
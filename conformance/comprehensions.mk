@@ -0,0 +1,5 @@
+let stock = {"pears": 3, "apples": 5, "figs": 0};
+let squares = [n * n for n in range(1, 6)];
+let evens = [n for n in squares if n % 2 == 0];
+let report = [name + ": " + str(count) for name, count in stock if count > 0];
+[squares, evens, report]
@@ -0,0 +1,2 @@
+let max = fn(a, b) { if (a > b) { a } else { b } };
+[max(1, 2), max(5, 3), if (false) { 1 }]
@@ -0,0 +1,14 @@
+let log = buffer();
+defer puts("top level, last");
+let f = fn() {
+  defer push(log, "a");
+  defer push(log, "b");
+  for (x in [1, 2]) { defer push(log, str(x)); }
+  let n = 3;
+  defer push(log, str(n));
+  n = 4;
+  push(log, "body");
+  "done"
+};
+let result = f();
+[result, str(log)]
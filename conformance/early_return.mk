@@ -0,0 +1,9 @@
+let find = fn(xs, target) {
+  let i = 0;
+  while (i < len(xs)) {
+    if (xs[i] == target) { return i; }
+    let i = i + 1;
+  }
+  -1
+};
+[find([4, 5, 6], 6), find([4, 5, 6], 7)]
@@ -0,0 +1,11 @@
+let close = fn(name) { puts("closed " + name) };
+let inner = fn() {
+  defer close("inner");
+  1 / 0
+};
+let outer = fn() {
+  defer close("first");
+  defer close("second");
+  inner()
+};
+outer()
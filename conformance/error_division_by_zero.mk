@@ -0,0 +1,2 @@
+let zero = 0;
+10 / zero
@@ -0,0 +1,2 @@
+let half = 1 / 2.0;
+half * 3 + 1
@@ -0,0 +1,10 @@
+let fs = [];
+for (x in [1, 2]) { fs = push(fs, fn() { x }); }
+let gs = [];
+for (k, v in {"a": 1, "b": 2}) { let both = k + str(v); gs = push(gs, fn() { both }); }
+let hs = fn() {
+    let out = [];
+    for (y in [3, 4]) { out = push(out, fn() { y }); }
+    out
+}();
+[fs[0](), fs[1](), gs[0](), gs[1](), hs[0](), hs[1]()]
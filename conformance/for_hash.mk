@@ -0,0 +1,9 @@
+let stock = {"pears": 3, "apples": 5, "figs": 0};
+let names = [];
+for (name in stock) { names = push(names, name); }
+let report = [];
+for (name, count in stock) {
+    if (count == 0) { continue }
+    report = push(report, name + ": " + str(count));
+}
+[names, report]
@@ -0,0 +1,5 @@
+let squares = [];
+for (n in range(1, 5)) { squares = push(squares, n * n); }
+let total = 0;
+for (square in squares) { total = total + square; }
+[squares, total]
@@ -0,0 +1,11 @@
+let x = 1;
+for (i in [1]) { let x = 2; }
+let out = [];
+for (k, v in {"a": 1, "b": 2}) { let out = push(out, k); }
+let seen = [];
+for (n in [1, 2]) {
+    let seen = push(seen, x);
+    let x = n * 10;
+    seen = push(seen, x);
+}
+[x, out, seen]
@@ -0,0 +1,7 @@
+let map = fn(xs, f) {
+  let iter = fn(xs, acc) {
+    if (len(xs) == 0) { acc } else { iter(rest(xs), push(acc, f(first(xs)))) }
+  };
+  iter(xs, [])
+};
+map([1, 2, 3], fn(x) { x * x })
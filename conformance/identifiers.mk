@@ -0,0 +1,6 @@
+let max_len = 3;
+let item_1 = "ab";
+let item_2 = "cde";
+let fits = fn(s) { len(s) <= max_len };
+assert_eq(fits(item_2), true);
+[fits(item_1), fits(item_2 + item_1)]
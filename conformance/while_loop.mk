@@ -0,0 +1,4 @@
+let i = 0;
+let total = 0;
+while (i < 5) { let total = total + i; let i = i + 1; }
+total
@@ -0,0 +1,20 @@
+use crate::{
+    ast::Function,
+    visitor::{walk_function, Visitor},
+};
+
+pub fn frame_escapes(function: &Function) -> bool {
+    let mut finder = ClosureFinder { found: false };
+    walk_function(&mut finder, function);
+    finder.found
+}
+
+struct ClosureFinder {
+    found: bool,
+}
+
+impl Visitor for ClosureFinder {
+    fn visit_function(&mut self, _: &Function) {
+        self.found = true;
+    }
+}
@@ -1,9 +1,15 @@
+use alloc::rc::Rc;
 use core::fmt;
-use std::fmt::Display;
+use core::fmt::{Display, Write};
+use core::mem;
 
-use crate::token::{Identifier, TokenType};
+use crate::object::Quoted;
+use crate::prelude::*;
+use crate::syntax::{expression_kind, statement_kind};
+use crate::token::{Identifier, Int, TokenType};
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Statement {
     Let {
         identifier: Identifier,
@@ -12,9 +18,26 @@ pub enum Statement {
     Return(Expression),
     Expression(Expression),
     Block(Block),
+    Defer(Expression),
+    While {
+        condition: Expression,
+        body: Block,
+    },
+    /// `for (variable in iterable) { body }`, running the body once per element of an array
+    /// or key of a hash. `for (key, value in iterable)` binds each value too, with an
+    /// array's indexes as its keys.
+    For {
+        variable: Identifier,
+        value: Option<Identifier>,
+        iterable: Expression,
+        body: Block,
+    },
+    Break,
+    Continue,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Expression {
     Identifier(Identifier),
     Literal(Literal),
@@ -22,12 +45,21 @@ pub enum Expression {
     Infix(Infix),
     If(If),
     Function(Function),
+    Macro(Function),
     Call(Call),
+    Array(Vec<Expression>),
+    Comprehension(Comprehension),
+    Index(Index),
+    Hash(Vec<(Expression, Expression)>),
+    /// `name = value`, rebinding a variable declared with `let`.
+    Assign(Identifier, Box<Expression>),
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Literal {
-    Int(i64),
+    Int(Int),
+    Float(f64),
     String(String),
     True,
     False,
@@ -35,12 +67,14 @@ pub enum Literal {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PrefixOperation {
     Bang,
     Minus,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InfixOperation {
     Add,
     Sub,
@@ -52,28 +86,63 @@ pub enum InfixOperation {
     Gte,
     Mul,
     Div,
+    Mod,
     LParen,
+    LBracket,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Prefix {
     pub expression: Box<Expression>,
     pub operation: PrefixOperation,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Function {
     pub params: Vec<Identifier>,
-    pub body: Block,
+    /// `...rest` after the parameters, bound to an array of the arguments left over.
+    pub rest: Option<Identifier>,
+    /// Shared with the function values evaluating the literal creates.
+    pub body: Rc<Block>,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Call {
     pub arguments: Vec<Expression>,
     pub function: Box<Expression>,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+/// `[element for variable in iterable if condition]`: `element` for each item `for` would
+/// bind, skipping those the optional condition is false for.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Comprehension {
+    pub element: Box<Expression>,
+    pub variable: Identifier,
+    pub value: Option<Identifier>,
+    pub iterable: Box<Expression>,
+    pub condition: Option<Box<Expression>>,
+}
+
+impl Comprehension {
+    /// The variables bound for each item, as in `for (key, value in ...)`.
+    pub fn variables(&self) -> impl Iterator<Item = &Identifier> {
+        Some(&self.variable).into_iter().chain(&self.value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Index {
+    pub left: Box<Expression>,
+    pub index: Box<Expression>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Block(pub Vec<Statement>);
 
 impl Block {
@@ -82,14 +151,16 @@ impl Block {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Infix {
     pub left_expression: Box<Expression>,
     pub right_expression: Box<Expression>,
     pub operation: InfixOperation,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct If {
     pub condition: Box<Expression>,
     pub alternative: Option<Block>,
@@ -100,6 +171,35 @@ impl Expression {
     pub fn boxed(self) -> Box<Self> {
         Box::new(self)
     }
+
+    /// Drops the expression a node at a time. The derived drop recurses once per level,
+    /// and a long `+` chain can be deeper than the stack.
+    pub fn drop_flat(self) {
+        let mut pending = vec![self];
+        while let Some(mut expression) = pending.pop() {
+            let mut take = |expression: &mut Expression| {
+                pending.push(mem::replace(expression, Expression::Literal(Literal::Nil)));
+            };
+            match &mut expression {
+                Expression::Prefix(prefix) => take(&mut prefix.expression),
+                Expression::Infix(infix) => {
+                    take(&mut infix.left_expression);
+                    take(&mut infix.right_expression);
+                }
+                Expression::Index(index) => {
+                    take(&mut index.left);
+                    take(&mut index.index);
+                }
+                Expression::Call(call) => {
+                    take(&mut call.function);
+                    call.arguments.iter_mut().for_each(take);
+                }
+                Expression::Array(elements) => elements.iter_mut().for_each(take),
+                Expression::Assign(_, value) => take(value),
+                _ => {}
+            }
+        }
+    }
 }
 
 impl Display for Literal {
@@ -108,11 +208,12 @@ impl Display for Literal {
             Literal::Nil => f.write_str("nil"),
             Literal::True => f.write_str("true"),
             Literal::False => f.write_str("false"),
-            Literal::String(str) => f.write_str(str.as_str()),
+            Literal::String(str) => write!(f, "{}", Quoted(str)),
             Literal::Int(int) => {
                 let fmt_str = int.to_string();
                 f.write_str(fmt_str.as_str())
             }
+            Literal::Float(float) => write!(f, "{float:?}"),
         }
     }
 }
@@ -126,6 +227,22 @@ impl Display for Statement {
             } => write!(f, "let {identifier} = {expression}"),
             Statement::Block(block) => write!(f, "{block}"),
             Statement::Return(ret) => write!(f, "return {ret}"),
+            Statement::Defer(deferred) => write!(f, "defer {deferred}"),
+            Statement::While { condition, body } => write!(f, "while {condition} {body}"),
+            Statement::For {
+                variable,
+                value: None,
+                iterable,
+                body,
+            } => write!(f, "for {variable} in {iterable} {body}"),
+            Statement::For {
+                variable,
+                value: Some(value),
+                iterable,
+                body,
+            } => write!(f, "for {variable}, {value} in {iterable} {body}"),
+            Statement::Break => f.write_str("break"),
+            Statement::Continue => f.write_str("continue"),
             Statement::Expression(expression) => write!(f, "{expression}"),
         }
     }
@@ -133,15 +250,15 @@ impl Display for Statement {
 
 impl Display for Function {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} (", TokenType::Function)?;
+        self.write(f, TokenType::Function)
+    }
+}
 
-        for (index, param) in self.params.iter().enumerate() {
-            write!(f, "{}", param)?;
-            if index != self.params.len() - 1 {
-                write!(f, ", ")?;
-            }
-        }
-        write!(f, ")")?;
+impl Function {
+    fn write(&self, f: &mut fmt::Formatter<'_>, keyword: TokenType) -> fmt::Result {
+        write!(f, "{keyword} (")?;
+
+        write!(f, "{})", self.signature())?;
 
         if self.body.len() > 0 {
             write!(f, " ")?;
@@ -151,6 +268,19 @@ impl Display for Function {
     }
 }
 
+impl Function {
+    /// The parameters as written between the parentheses, such as `a, b, ...rest`.
+    pub fn signature(&self) -> String {
+        signature(&self.params, self.rest.as_ref())
+    }
+}
+
+pub(crate) fn signature(params: &[Identifier], rest: Option<&Identifier>) -> String {
+    let rest = rest.map(|rest| format!("...{rest}"));
+    let params: Vec<String> = params.iter().map(|p| p.to_string()).chain(rest).collect();
+    params.join(", ")
+}
+
 impl Display for PrefixOperation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -188,7 +318,9 @@ impl Display for InfixOperation {
             InfixOperation::NotEq => f.write_str("!="),
             InfixOperation::Mul => f.write_str("*"),
             InfixOperation::Div => f.write_str("/"),
+            InfixOperation::Mod => f.write_str("%"),
             InfixOperation::LParen => f.write_str("("),
+            InfixOperation::LBracket => f.write_str("["),
         }
     }
 }
@@ -202,7 +334,31 @@ impl Display for Expression {
             Expression::If(if_expression) => write!(f, "{if_expression}"),
             Expression::Identifier(identifier) => write!(f, "{identifier}"),
             Expression::Function(function) => write!(f, "{function}"),
+            Expression::Macro(function) => function.write(f, TokenType::Macro),
             Expression::Call(call) => write!(f, "{call}"),
+            Expression::Array(elements) => {
+                write!(f, "[")?;
+                for (index, element) in elements.iter().enumerate() {
+                    write!(f, "{element}")?;
+                    if index != elements.len() - 1 {
+                        write!(f, ", ")?;
+                    }
+                }
+                write!(f, "]")
+            }
+            Expression::Comprehension(comprehension) => write!(f, "{comprehension}"),
+            Expression::Index(index) => write!(f, "{index}"),
+            Expression::Assign(name, value) => write!(f, "{name} = {value}"),
+            Expression::Hash(pairs) => {
+                write!(f, "{{")?;
+                for (index, (key, value)) in pairs.iter().enumerate() {
+                    write!(f, "{key}: {value}")?;
+                    if index != pairs.len() - 1 {
+                        write!(f, ", ")?;
+                    }
+                }
+                write!(f, "}}")
+            }
         }
     }
 }
@@ -229,16 +385,33 @@ impl Display for If {
         let condition = self.condition.to_string();
         let consequence = self.consequence.to_string();
         write!(f, "if {} {}", condition, consequence)?;
-        match &self.alternative {
-            Some(alt) => {
-                write!(f, " else {}", alt)?;
-            }
-            None => {}
+        if let Some(alt) = &self.alternative {
+            write!(f, " else {}", alt)?;
         }
         write!(f, "")
     }
 }
 
+impl Display for Comprehension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{} for {}", self.element, self.variable)?;
+        if let Some(value) = &self.value {
+            write!(f, ", {value}")?;
+        }
+        write!(f, " in {}", self.iterable)?;
+        if let Some(condition) = &self.condition {
+            write!(f, " if {condition}")?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl Display for Index {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}[{}])", self.left, self.index)
+    }
+}
+
 impl Display for Call {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let function = &self.function;
@@ -252,3 +425,201 @@ impl Display for Call {
         write!(f, ")")
     }
 }
+
+impl Statement {
+    /// Drops the statement without recursing through its expression, see
+    /// [`Expression::drop_flat`].
+    pub fn drop_flat(self) {
+        match self {
+            Statement::Let { expression, .. }
+            | Statement::Return(expression)
+            | Statement::Expression(expression)
+            | Statement::Defer(expression) => expression.drop_flat(),
+            statement => drop(statement),
+        }
+    }
+
+    /// An indented tree of the statement, one node per line with its kind and any name,
+    /// operator or literal value, showing exactly how the parser grouped the source.
+    pub fn to_pretty_string(&self) -> String {
+        let mut printer = Pretty::default();
+        printer.statement(self);
+        printer.out
+    }
+}
+
+#[derive(Default)]
+struct Pretty {
+    out: String,
+    depth: usize,
+}
+
+impl Pretty {
+    fn line(&mut self, kind: &str, detail: impl Display) {
+        let detail = detail.to_string();
+        let separator = if detail.is_empty() { "" } else { " " };
+        let indent = "  ".repeat(self.depth);
+        let _ = writeln!(self.out, "{indent}{kind}{separator}{detail}");
+    }
+
+    fn nested(&mut self, kind: &str, detail: impl Display, children: impl FnOnce(&mut Self)) {
+        self.line(kind, detail);
+        self.depth += 1;
+        children(self);
+        self.depth -= 1;
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        let kind = statement_kind(statement);
+        match statement {
+            Statement::Let {
+                identifier,
+                expression,
+            } => self.nested(kind, identifier, |p| p.expression(expression)),
+            Statement::Return(expression)
+            | Statement::Expression(expression)
+            | Statement::Defer(expression) => self.nested(kind, "", |p| p.expression(expression)),
+            Statement::Block(block) => self.block("", block),
+            Statement::While { condition, body } => self.nested(kind, "", |p| {
+                p.expression(condition);
+                p.block("", body);
+            }),
+            Statement::For {
+                variable,
+                value,
+                iterable,
+                body,
+            } => {
+                let variables = match value {
+                    Some(value) => format!("{variable}, {value}"),
+                    None => variable.to_string(),
+                };
+                self.nested(kind, variables, |p| {
+                    p.expression(iterable);
+                    p.block("", body);
+                })
+            }
+            Statement::Break | Statement::Continue => self.line(kind, ""),
+        }
+    }
+
+    fn block(&mut self, label: &str, block: &Block) {
+        self.nested("block", label, |p| {
+            block.0.iter().for_each(|statement| p.statement(statement))
+        });
+    }
+
+    fn expression(&mut self, expression: &Expression) {
+        let kind = expression_kind(expression);
+        match expression {
+            Expression::Identifier(identifier) => self.line(kind, identifier),
+            Expression::Literal(Literal::True | Literal::False | Literal::Nil) => {
+                self.line(kind, "")
+            }
+            Expression::Literal(literal) => self.line(kind, literal),
+            Expression::Prefix(prefix) => self.nested(kind, &prefix.operation, |p| {
+                p.expression(&prefix.expression)
+            }),
+            Expression::Infix(infix) => self.nested(kind, &infix.operation, |p| {
+                p.expression(&infix.left_expression);
+                p.expression(&infix.right_expression);
+            }),
+            Expression::If(if_expression) => self.nested(kind, "", |p| {
+                p.expression(&if_expression.condition);
+                p.block("then", &if_expression.consequence);
+                if let Some(alternative) = &if_expression.alternative {
+                    p.block("else", alternative);
+                }
+            }),
+            Expression::Function(function) | Expression::Macro(function) => {
+                self.nested(kind, format!("({})", function.signature()), |p| {
+                    p.block("", &function.body)
+                })
+            }
+            Expression::Call(call) => self.nested(kind, "", |p| {
+                p.expression(&call.function);
+                call.arguments
+                    .iter()
+                    .for_each(|argument| p.expression(argument));
+            }),
+            Expression::Array(elements) => self.nested(kind, "", |p| {
+                elements.iter().for_each(|element| p.expression(element))
+            }),
+            Expression::Comprehension(comprehension) => {
+                let variables: Vec<String> =
+                    comprehension.variables().map(|v| v.to_string()).collect();
+                self.nested(kind, variables.join(", "), |p| {
+                    p.expression(&comprehension.element);
+                    p.expression(&comprehension.iterable);
+                    if let Some(condition) = &comprehension.condition {
+                        p.expression(condition);
+                    }
+                })
+            }
+            Expression::Index(index) => self.nested(kind, "", |p| {
+                p.expression(&index.left);
+                p.expression(&index.index);
+            }),
+            Expression::Hash(pairs) => self.nested(kind, "", |p| {
+                for (key, value) in pairs {
+                    p.nested("pair", "", |p| {
+                        p.expression(key);
+                        p.expression(value);
+                    });
+                }
+            }),
+            Expression::Assign(name, value) => self.nested(kind, name, |p| p.expression(value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod ast_tests {
+    use crate::{lexer::Lexer, parser::Parser, prelude::*};
+
+    #[test]
+    fn pretty_printing_shows_grouping() {
+        let mut parser = Parser::new(Lexer::new("let f = fn(a) { -a + 2 * 3 }; f(1)[0]").tokens());
+        let trees: Vec<String> = parser
+            .by_ref()
+            .map(|statement| statement.unwrap().to_pretty_string())
+            .collect();
+        assert_eq!(
+            trees,
+            vec![
+                "let_statement f\n\
+                 \x20 function (a)\n\
+                 \x20   block\n\
+                 \x20     expression_statement\n\
+                 \x20       infix_expression +\n\
+                 \x20         prefix_expression -\n\
+                 \x20           identifier a\n\
+                 \x20         infix_expression *\n\
+                 \x20           integer 2\n\
+                 \x20           integer 3\n",
+                "expression_statement\n\
+                 \x20 index_expression\n\
+                 \x20   call_expression\n\
+                 \x20     identifier f\n\
+                 \x20     integer 1\n\
+                 \x20   integer 0\n",
+            ]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn programs_round_trip_through_json() {
+        use crate::ast::Statement;
+
+        let source =
+            r#"let add = fn(a, b) { a + b }; if (!x) { add(1, 2.5) } else { {"k": [nil]}[0] }"#;
+        let statements: Vec<Statement> = Parser::new(Lexer::new(source).tokens())
+            .map(Result::unwrap)
+            .collect();
+        let json = serde_json::to_string(&statements).unwrap();
+        assert!(json.starts_with(r#"[{"Let":{"identifier":"add","expression":{"Function""#));
+        let decoded: Vec<Statement> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, statements);
+    }
+}
@@ -0,0 +1,163 @@
+use std::{cell::RefCell, rc::Rc, time::Duration, time::Instant};
+
+use anyhow::{bail, Result};
+
+use crate::{
+    ast::Statement,
+    environment::Environment,
+    eval::{Engine, Program},
+    lexer::Lexer,
+    optimizer,
+    parser::Parser,
+};
+
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    pub warmup: usize,
+    pub iterations: usize,
+    pub engine: Engine,
+    /// As in `--opt=N`: 1 runs the optimizer, 2 also inlines hot functions on the VM.
+    pub opt: u8,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            iterations: 10,
+            engine: Engine::default(),
+            opt: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+    /// Instructions executed per run, when the script ran on the VM.
+    pub instructions: Option<u64>,
+}
+
+/// Runs `source` `warmup + iterations` times in a fresh environment each time and
+/// reports wall times of the measured iterations.
+pub fn run(source: &str, options: &BenchOptions) -> Result<BenchReport> {
+    if options.iterations == 0 {
+        bail!("iterations must be at least 1");
+    }
+    let mut parser = Parser::new(Lexer::new(source).tokens());
+    let statements: Vec<Statement> = (parser.by_ref().flatten())
+        .map(|statement| match options.opt {
+            0 => statement,
+            _ => optimizer::optimize(statement),
+        })
+        .collect();
+    if !parser.errors().is_empty() {
+        let messages: Vec<String> = parser.errors().iter().map(|err| err.to_string()).collect();
+        bail!("{}", messages.join("\n"));
+    }
+
+    for _ in 0..options.warmup {
+        run_once(source, options)?;
+    }
+    let mut times = Vec::with_capacity(options.iterations);
+    for _ in 0..options.iterations {
+        let start = Instant::now();
+        run_once(source, options)?;
+        times.push(start.elapsed());
+    }
+    times.sort();
+
+    Ok(BenchReport {
+        min: times[0],
+        median: times[times.len() / 2],
+        max: times[times.len() - 1],
+        instructions: instruction_count(&statements, options)?,
+    })
+}
+
+fn run_once(source: &str, options: &BenchOptions) -> Result<()> {
+    let mut parser = Parser::new(Lexer::new(source).tokens());
+    let env = Rc::new(RefCell::new(Environment::default()));
+    Program::with_engine(options.engine)
+        .optimized(options.opt >= 1)
+        .inline_hot(options.opt >= 2)
+        .eval(&mut parser, env)?;
+    Ok(())
+}
+
+#[cfg(feature = "vm")]
+fn instruction_count(statements: &[Statement], options: &BenchOptions) -> Result<Option<u64>> {
+    use crate::{compiler::Compiler, vm::Vm};
+    use std::mem;
+
+    if options.engine != Engine::Vm {
+        return Ok(None);
+    }
+    let mut compiler = Compiler::new();
+    compiler.inline_hot = options.opt >= 2;
+    compiler.compile(statements)?;
+    let functions = mem::take(&mut compiler.functions);
+    let env = Rc::new(RefCell::new(Environment::default()));
+    let mut vm = Vm::new(compiler.bytecode(), env);
+    if options.opt >= 2 {
+        vm = vm.with_inlining(functions);
+    }
+    vm.run()?;
+    Ok(Some(vm.instructions_executed()))
+}
+
+#[cfg(not(feature = "vm"))]
+fn instruction_count(_: &[Statement], _: &BenchOptions) -> Result<Option<u64>> {
+    Ok(None)
+}
+
+#[cfg(test)]
+mod bench_tests {
+    use super::{run, BenchOptions};
+
+    #[test]
+    fn reports_ordered_times() {
+        let options = BenchOptions {
+            warmup: 1,
+            iterations: 5,
+            ..Default::default()
+        };
+        let report = run("let f = fn(x) { x * 2 }; f(21)", &options).unwrap();
+        assert!(report.min <= report.median && report.median <= report.max);
+        assert_eq!(report.instructions, None);
+        assert!(run("let = 1", &options).is_err());
+    }
+
+    #[cfg(feature = "vm")]
+    #[test]
+    fn counts_vm_instructions() {
+        let options = BenchOptions {
+            warmup: 0,
+            iterations: 1,
+            engine: crate::eval::Engine::Vm,
+            ..Default::default()
+        };
+        let report = run("1 + 2", &options).unwrap();
+        assert_eq!(report.instructions, Some(4));
+    }
+
+    #[cfg(feature = "vm")]
+    #[test]
+    fn inlining_saves_vm_instructions() {
+        let source = "let add = fn(a, b) { a + b }; let less = fn(a, b) { a < b }; \
+                      let fib = fn(n) { if (less(n, 2)) { n } else { add(fib(n - 1), fib(n - 2)) } }; \
+                      fib(15)";
+        let instructions = |opt| {
+            let options = BenchOptions {
+                warmup: 0,
+                iterations: 1,
+                engine: crate::eval::Engine::Vm,
+                opt,
+            };
+            run(source, &options).unwrap().instructions.unwrap()
+        };
+        assert!(instructions(2) < instructions(1));
+    }
+}
@@ -0,0 +1,1288 @@
+use alloc::rc::Rc;
+use anyhow::{anyhow, bail, Result};
+use core::{cell::RefCell, fmt};
+
+use crate::{
+    diff,
+    environment::GlobalEnv,
+    eval::{apply_function, EvalError},
+    object::{HashKey, Inspect, Object, Shared},
+    prelude::*,
+    runtime::Runtime,
+    token::Int,
+};
+
+pub type BuiltinFn = fn(Vec<Object>, &GlobalEnv) -> Result<Object>;
+
+#[derive(Clone)]
+pub struct Builtin {
+    pub name: &'static str,
+    pub func: BuiltinFn,
+}
+
+impl fmt::Debug for Builtin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Builtin({})", self.name)
+    }
+}
+
+impl PartialEq for Builtin {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+static BUILTINS: &[Builtin] = &[
+    Builtin {
+        name: "len",
+        func: len,
+    },
+    Builtin {
+        name: "first",
+        func: first,
+    },
+    Builtin {
+        name: "last",
+        func: last,
+    },
+    Builtin {
+        name: "rest",
+        func: rest,
+    },
+    Builtin {
+        name: "push",
+        func: push,
+    },
+    Builtin {
+        name: "range",
+        func: range,
+    },
+    Builtin {
+        name: "buffer",
+        func: buffer,
+    },
+    Builtin {
+        name: "str",
+        func: str,
+    },
+    Builtin {
+        name: "inspect",
+        func: inspect,
+    },
+    Builtin {
+        name: "parse_float",
+        func: parse_float,
+    },
+    Builtin {
+        name: "format",
+        func: format,
+    },
+    Builtin {
+        name: "format_number",
+        func: format_number,
+    },
+    #[cfg(feature = "builtins-net")]
+    Builtin {
+        name: "url_parse",
+        func: url::url_parse,
+    },
+    #[cfg(feature = "builtins-net")]
+    Builtin {
+        name: "url_encode",
+        func: url::url_encode,
+    },
+    #[cfg(feature = "builtins-net")]
+    Builtin {
+        name: "url_decode",
+        func: url::url_decode,
+    },
+    Builtin {
+        name: "uuid",
+        func: uuid,
+    },
+    Builtin {
+        name: "nanoid",
+        func: nanoid,
+    },
+    Builtin {
+        name: "puts",
+        func: puts,
+    },
+    Builtin {
+        name: "print",
+        func: print,
+    },
+    Builtin {
+        name: "assert_eq",
+        func: assert_eq,
+    },
+    Builtin {
+        name: "runtime",
+        func: runtime,
+    },
+    Builtin {
+        name: "map",
+        func: map,
+    },
+    Builtin {
+        name: "filter",
+        func: filter,
+    },
+    Builtin {
+        name: "reduce",
+        func: reduce,
+    },
+    #[cfg(feature = "vm")]
+    Builtin {
+        name: "coroutine",
+        func: coroutines::coroutine,
+    },
+    #[cfg(feature = "vm")]
+    Builtin {
+        name: "resume",
+        func: coroutines::resume,
+    },
+    #[cfg(feature = "vm")]
+    Builtin {
+        name: "yield",
+        func: coroutines::yield_,
+    },
+    #[cfg(feature = "builtins-csv")]
+    Builtin {
+        name: "csv_parse",
+        func: csv::csv_parse,
+    },
+    #[cfg(feature = "builtins-csv")]
+    Builtin {
+        name: "csv_stringify",
+        func: csv::csv_stringify,
+    },
+    #[cfg(feature = "builtins-toml")]
+    Builtin {
+        name: "toml_parse",
+        func: config::toml_parse,
+    },
+    #[cfg(feature = "builtins-yaml")]
+    Builtin {
+        name: "yaml_parse",
+        func: config::yaml_parse,
+    },
+    #[cfg(feature = "builtins-io")]
+    Builtin {
+        name: "open",
+        func: io::open,
+    },
+    #[cfg(feature = "builtins-io")]
+    Builtin {
+        name: "read",
+        func: io::read,
+    },
+    #[cfg(feature = "builtins-io")]
+    Builtin {
+        name: "write",
+        func: io::write,
+    },
+    #[cfg(feature = "builtins-io")]
+    Builtin {
+        name: "close",
+        func: io::close,
+    },
+];
+
+/// How many builtins there can be, core and registered: the VM addresses them with a byte.
+const MAX_BUILTINS: usize = 256;
+
+/// Builtins registered with [`register_global`], copied into every new [`Registry`].
+#[cfg(feature = "std")]
+static GLOBAL: std::sync::RwLock<Vec<Builtin>> = std::sync::RwLock::new(Vec::new());
+
+/// Looks up a core builtin or one registered with [`register_global`]. Builtins registered
+/// on a single runtime are found with [`Registry::lookup`].
+pub fn lookup(name: &str) -> Option<Builtin> {
+    BUILTINS
+        .iter()
+        .find(|builtin| builtin.name == name)
+        .cloned()
+        .or_else(|| global().into_iter().find(|builtin| builtin.name == name))
+}
+
+/// Registers a builtin for every interpreter created from now on, so a crate can ship a
+/// pack of builtins. Registering a name again replaces the builtin.
+#[cfg(feature = "std")]
+pub fn register_global(name: &'static str, func: BuiltinFn) -> Result<()> {
+    let mut builtins = GLOBAL.write().unwrap_or_else(|err| err.into_inner());
+    add(&mut builtins, Builtin { name, func })
+}
+
+#[cfg(feature = "std")]
+fn global() -> Vec<Builtin> {
+    GLOBAL.read().unwrap_or_else(|err| err.into_inner()).clone()
+}
+
+#[cfg(not(feature = "std"))]
+fn global() -> Vec<Builtin> {
+    Vec::new()
+}
+
+fn add(builtins: &mut Vec<Builtin>, builtin: Builtin) -> Result<()> {
+    if BUILTINS.contains(&builtin) {
+        bail!("cannot replace the builtin {}", builtin.name);
+    }
+    if let Some(existing) = builtins.iter_mut().find(|existing| **existing == builtin) {
+        *existing = builtin;
+    } else if BUILTINS.len() + builtins.len() < MAX_BUILTINS {
+        builtins.push(builtin);
+    } else {
+        bail!("cannot register {}: too many builtins", builtin.name);
+    }
+    Ok(())
+}
+
+/// The builtins an interpreter can call: the core ones, then those registered on it, which
+/// start as the ones registered with [`register_global`] when it was created.
+#[derive(Debug, Clone)]
+pub struct Registry(RefCell<Vec<Builtin>>);
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self(RefCell::new(global()))
+    }
+}
+
+impl Registry {
+    /// Registers a builtin for this interpreter only. Registering a name again replaces the
+    /// builtin.
+    pub fn register(&self, name: &'static str, func: BuiltinFn) -> Result<()> {
+        add(&mut self.0.borrow_mut(), Builtin { name, func })
+    }
+
+    pub fn lookup(&self, name: &str) -> Option<Builtin> {
+        self.position(name).and_then(|index| self.get(index))
+    }
+
+    /// Position of a builtin in the table, used by the compiler to address builtins by index.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        BUILTINS
+            .iter()
+            .map(|builtin| builtin.name)
+            .chain(self.0.borrow().iter().map(|builtin| builtin.name))
+            .position(|builtin| builtin == name)
+    }
+
+    pub fn get(&self, index: usize) -> Option<Builtin> {
+        match index.checked_sub(BUILTINS.len()) {
+            Some(index) => self.0.borrow().get(index).cloned(),
+            None => BUILTINS.get(index).cloned(),
+        }
+    }
+}
+
+fn expect_args(name: &'static str, args: &[Object], count: usize) -> Result<()> {
+    if args.len() != count {
+        return Err(EvalError::WrongArity {
+            function: Some(name),
+            expected: count,
+            got: args.len(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+fn len(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+    expect_args("len", &args, 1)?;
+    match &args[0] {
+        Object::String(string) => Ok(Object::Int(string.chars().count() as Int)),
+        Object::Array(elements) => Ok(Object::Int(elements.len() as Int)),
+        Object::Buffer(buffer) => Ok(Object::Int(buffer.borrow().chars().count() as Int)),
+        Object::Hash(pairs) => Ok(Object::Int(pairs.len() as Int)),
+        other => bail!("argument to len not supported, got {}", other.name()),
+    }
+}
+
+fn array(name: &str, args: &[Object]) -> Result<Shared<Vec<Object>>> {
+    match &args[0] {
+        Object::Array(elements) => Ok(elements.clone()),
+        other => bail!("argument to {name} must be an array, got {}", other.name()),
+    }
+}
+
+fn first(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+    expect_args("first", &args, 1)?;
+    Ok(array("first", &args)?
+        .first()
+        .cloned()
+        .unwrap_or(Object::Nil))
+}
+
+fn last(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+    expect_args("last", &args, 1)?;
+    Ok(array("last", &args)?.last().cloned().unwrap_or(Object::Nil))
+}
+
+fn rest(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+    expect_args("rest", &args, 1)?;
+    let elements = array("rest", &args)?;
+    if elements.is_empty() {
+        return Ok(Object::Nil);
+    }
+    Ok(Object::Array(Shared::new(elements[1..].to_vec())))
+}
+
+fn push(mut args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+    expect_args("push", &args, 2)?;
+    if let Object::Buffer(buffer) = &args[0] {
+        buffer.borrow_mut().push_str(&args[1].to_string());
+        return Ok(args.swap_remove(0));
+    }
+    let mut elements = array("push", &args)?;
+    elements.make_mut().push(args.remove(1));
+    Ok(Object::Array(elements))
+}
+
+/// `range(start, end)`: the ints from `start` up to, but not including, `end`.
+fn range(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+    expect_args("range", &args, 2)?;
+    match (&args[0], &args[1]) {
+        (Object::Int(start), Object::Int(end)) => Ok(Object::Array(Shared::new(
+            (*start..*end).map(Object::Int).collect(),
+        ))),
+        (Object::Int(_), other) | (other, _) => {
+            bail!("arguments to range must be ints, got {}", other.name())
+        }
+    }
+}
+
+/// `map(items, f)`: the results of calling `f` on each item.
+fn map(args: Vec<Object>, env: &GlobalEnv) -> Result<Object> {
+    expect_args("map", &args, 2)?;
+    let items = array("map", &args)?;
+    let results = items
+        .iter()
+        .map(|item| apply_function(args[1].clone(), vec![item.clone()], env))
+        .collect::<Result<_>>()?;
+    Ok(Object::Array(Shared::new(results)))
+}
+
+/// `filter(items, keep)`: the items `keep` returns something truthy for.
+fn filter(args: Vec<Object>, env: &GlobalEnv) -> Result<Object> {
+    expect_args("filter", &args, 2)?;
+    let mut kept = vec![];
+    for item in array("filter", &args)?.iter() {
+        if apply_function(args[1].clone(), vec![item.clone()], env)?.is_truthy() {
+            kept.push(item.clone());
+        }
+    }
+    Ok(Object::Array(Shared::new(kept)))
+}
+
+/// `reduce(items, initial, f)`: `initial` folded with each item in turn by `f(acc, item)`.
+fn reduce(mut args: Vec<Object>, env: &GlobalEnv) -> Result<Object> {
+    expect_args("reduce", &args, 3)?;
+    let items = array("reduce", &args)?;
+    let f = args.pop().expect("three arguments");
+    let initial = args.pop().expect("three arguments");
+    items.iter().try_fold(initial, |acc, item| {
+        apply_function(f.clone(), vec![acc, item.clone()], env)
+    })
+}
+
+fn buffer(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+    let initial = match args.as_slice() {
+        [] => String::new(),
+        [Object::String(initial)] => initial.to_string(),
+        _ => bail!("buffer expects an optional initial string"),
+    };
+    Ok(Object::Buffer(Rc::new(RefCell::new(initial))))
+}
+
+fn str(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+    expect_args("str", &args, 1)?;
+    Ok(Object::String(args[0].to_string().into()))
+}
+
+/// `inspect(value)`: `value` as Monkey source, see [`Inspect`].
+fn inspect(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+    expect_args("inspect", &args, 1)?;
+    Ok(Object::String(Inspect(&args[0]).to_string().into()))
+}
+
+/// `parse_float(string)`: the float `string` spells. It reads anything `str` writes for a
+/// float back as that same float.
+fn parse_float(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+    expect_args("parse_float", &args, 1)?;
+    let Object::String(text) = &args[0] else {
+        bail!(
+            "argument to parse_float must be a string, got {}",
+            args[0].name()
+        );
+    };
+    text.parse()
+        .map(Object::Float)
+        .map_err(|_| anyhow!("cannot parse {:?} as a float", &**text))
+}
+
+/// `format(template, args...)`: replaces each `{}` of the template with the next argument,
+/// written as `str` would. `{{` and `}}` stand for literal braces.
+fn format(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+    let Some(Object::String(template)) = args.first() else {
+        match args.first() {
+            Some(other) => bail!(
+                "first argument to format must be a string, got {}",
+                other.name()
+            ),
+            None => bail!("format expects a template string"),
+        }
+    };
+    let mut values = args[1..].iter();
+    let mut placeholders = 0;
+    let mut out = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('{', Some('{')) | ('}', Some('}')) => {
+                chars.next();
+                out.push(c);
+            }
+            ('{', Some('}')) => {
+                chars.next();
+                placeholders += 1;
+                if let Some(value) = values.next() {
+                    out.push_str(&value.to_string());
+                }
+            }
+            ('{' | '}', _) => bail!("invalid format template: unmatched '{c}'"),
+            (c, _) => out.push(c),
+        }
+    }
+    if placeholders != args.len() - 1 {
+        return Err(EvalError::WrongArity {
+            function: Some("format"),
+            expected: placeholders + 1,
+            got: args.len(),
+        }
+        .into());
+    }
+    Ok(Object::String(out.into()))
+}
+
+/// `format_number(n, options)`: `n` written for people, the same whatever the host's locale.
+/// The optional hash takes `"separators"`, to group the digits before the point in threes
+/// with commas, and `"precision"`, the number of digits after the point.
+fn format_number(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+    let (number, options) = match args.as_slice() {
+        [number] => (number, None),
+        [number, Object::Hash(options)] => (number, Some(options)),
+        [_, other] => bail!(
+            "options to format_number must be a hash, got {}",
+            other.name()
+        ),
+        _ => {
+            return Err(EvalError::WrongArity {
+                function: Some("format_number"),
+                expected: 2,
+                got: args.len(),
+            }
+            .into())
+        }
+    };
+    let mut separators = false;
+    let mut precision = None;
+    for (key, value) in options.iter().flat_map(|options| options.iter()) {
+        match (key, value) {
+            (HashKey::String(key), Object::Bool(value)) if key == "separators" => {
+                separators = *value
+            }
+            (HashKey::String(key), Object::Int(value)) if key == "precision" => {
+                let digits = usize::try_from(*value)
+                    .map_err(|_| anyhow!("precision must not be negative, got {value}"))?;
+                precision = Some(digits);
+            }
+            (HashKey::String(key), value) if key == "separators" => {
+                bail!(
+                    "format_number option separators must be a bool, got {}",
+                    value.name()
+                )
+            }
+            (HashKey::String(key), value) if key == "precision" => {
+                bail!(
+                    "format_number option precision must be an int, got {}",
+                    value.name()
+                )
+            }
+            (key, _) => bail!("unknown format_number option {key}"),
+        }
+    }
+    let text = match (number, precision) {
+        (Object::Int(value), None | Some(0)) => value.to_string(),
+        (Object::Int(value), Some(digits)) => format!("{value}.{}", "0".repeat(digits)),
+        (Object::Float(value), None) => value.to_string(),
+        (Object::Float(value), Some(digits)) => format!("{value:.digits$}"),
+        (other, _) => bail!(
+            "argument to format_number must be a number, got {}",
+            other.name()
+        ),
+    };
+    if !separators || !text.starts_with(|c: char| c == '-' || c.is_ascii_digit()) {
+        return Ok(Object::String(text.into()));
+    }
+    let (sign, unsigned) = text.split_at(usize::from(text.starts_with('-')));
+    let end = unsigned
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(unsigned.len());
+    let (whole, rest) = unsigned.split_at(end);
+    let mut grouped = String::from(sign);
+    for (index, digit) in whole.chars().enumerate() {
+        if index > 0 && (whole.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped.push_str(rest);
+    Ok(Object::String(grouped.into()))
+}
+
+/// Writes each argument on its own line.
+/// `uuid()`: a random version 4 UUID, such as `"9f1c0e6a-4b2d-4c3e-8f5a-1d2e3f4a5b6c"`.
+fn uuid(args: Vec<Object>, env: &GlobalEnv) -> Result<Object> {
+    use core::fmt::Write;
+
+    expect_args("uuid", &args, 0)?;
+    let runtime = env.borrow().runtime.clone();
+    let mut bytes = [0; 16];
+    bytes[..8].copy_from_slice(&runtime.random.next_u64().to_le_bytes());
+    bytes[8..].copy_from_slice(&runtime.random.next_u64().to_le_bytes());
+    bytes[6] = bytes[6] & 0x0f | 0x40;
+    bytes[8] = bytes[8] & 0x3f | 0x80;
+    let mut uuid = String::with_capacity(36);
+    for (i, byte) in bytes.iter().enumerate() {
+        if matches!(i, 4 | 6 | 8 | 10) {
+            uuid.push('-');
+        }
+        write!(uuid, "{byte:02x}").expect("writing to a string");
+    }
+    Ok(Object::String(uuid.into()))
+}
+
+const NANOID_ALPHABET: &[u8; 64] =
+    b"useandom-26T198340PX75pxJACKVERYMINDBUSHWOLF_GQZbfghjklqvwyzrict";
+
+/// `nanoid(len)`: a random id of `len` URL-safe characters, 21 if left out.
+fn nanoid(args: Vec<Object>, env: &GlobalEnv) -> Result<Object> {
+    let len = match args.as_slice() {
+        [] => 21,
+        [Object::Int(len)] => {
+            usize::try_from(*len).map_err(|_| anyhow!("length for nanoid must not be negative"))?
+        }
+        [other] => bail!("argument to nanoid must be an int, got {}", other.name()),
+        _ => {
+            return Err(EvalError::WrongArity {
+                function: Some("nanoid"),
+                expected: 1,
+                got: args.len(),
+            }
+            .into())
+        }
+    };
+    let runtime = env.borrow().runtime.clone();
+    let id = (0..len)
+        .map(|_| NANOID_ALPHABET[(runtime.random.next_u64() >> 58) as usize] as char)
+        .collect::<String>();
+    Ok(Object::String(id.into()))
+}
+
+fn puts(args: Vec<Object>, env: &GlobalEnv) -> Result<Object> {
+    let runtime = env.borrow().runtime.clone();
+    for arg in &args {
+        write_output(&runtime, &format!("{arg}\n"))?;
+    }
+    Ok(Object::Nil)
+}
+
+/// Writes the arguments separated by spaces, without a trailing newline.
+fn print(args: Vec<Object>, env: &GlobalEnv) -> Result<Object> {
+    let runtime = env.borrow().runtime.clone();
+    let text: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    write_output(&runtime, &text.join(" "))?;
+    Ok(Object::Nil)
+}
+
+/// `assert_eq(actual, expected)`: fails with the differences between the two values.
+fn assert_eq(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+    expect_args("assert_eq", &args, 2)?;
+    match diff::diff(&args[1], &args[0]).as_slice() {
+        [] => Ok(Object::Nil),
+        [line] => bail!("assert_eq failed: {line}"),
+        lines => bail!("assert_eq failed:\n  {}", lines.join("\n  ")),
+    }
+}
+
+/// Describes the interpreter running the script: its version, engine, capabilities and
+/// limits, and whether the optimizer is on.
+fn runtime(args: Vec<Object>, env: &GlobalEnv) -> Result<Object> {
+    expect_args("runtime", &args, 0)?;
+    let runtime = env.borrow().runtime.clone();
+    let hash = |pairs: Vec<(&str, Object)>| {
+        let pairs = pairs
+            .into_iter()
+            .map(|(key, value)| (HashKey::String(key.to_string()), value));
+        Object::Hash(Shared::new(pairs.collect()))
+    };
+    let limits = runtime.limits;
+    Ok(hash(vec![
+        ("version", Object::String(env!("CARGO_PKG_VERSION").into())),
+        ("engine", Object::String(runtime.engine.get().name().into())),
+        ("optimize", Object::Bool(runtime.optimize.get())),
+        (
+            "capabilities",
+            hash(vec![("io", Object::Bool(runtime.capabilities.io))]),
+        ),
+        (
+            "limits",
+            hash(vec![
+                ("max_depth", Object::Int(limits.max_depth as Int)),
+                (
+                    "fuel",
+                    limits
+                        .fuel
+                        .map_or(Object::Nil, |fuel| Object::Int(fuel as Int)),
+                ),
+            ]),
+        ),
+    ]))
+}
+
+fn write_output(runtime: &Runtime, text: &str) -> Result<()> {
+    runtime
+        .output
+        .write_str(text)
+        .map_err(|_| anyhow!("failed to write output"))
+}
+
+#[cfg(feature = "vm")]
+mod coroutines {
+    use alloc::vec::Vec;
+    use anyhow::{bail, Result};
+
+    use super::expect_args;
+    use crate::{
+        environment::GlobalEnv,
+        object::{Coroutine, Object},
+        vm,
+    };
+
+    /// `coroutine(f)`: a coroutine that runs `f` when resumed. `f` takes at most one
+    /// parameter, the value of the first `resume`.
+    pub fn coroutine(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+        expect_args("coroutine", &args, 1)?;
+        match &args[0] {
+            Object::Closure(closure) => Ok(Object::Coroutine(Coroutine::new(closure.clone()))),
+            Object::Function(_) => bail!("coroutines are not supported by the tree-walker"),
+            other => bail!(
+                "argument to coroutine must be a function, got {}",
+                other.name()
+            ),
+        }
+    }
+
+    /// `resume(co, value)`: runs `co` until it yields or returns, and gives back that value.
+    /// `value` becomes the result of the `yield` it was waiting in.
+    pub fn resume(mut args: Vec<Object>, env: &GlobalEnv) -> Result<Object> {
+        expect_args("resume", &args, 2)?;
+        let value = args.pop().expect("two arguments");
+        match &args[0] {
+            Object::Coroutine(coroutine) => vm::resume(coroutine, value, env),
+            other => bail!(
+                "argument to resume must be a coroutine, got {}",
+                other.name()
+            ),
+        }
+    }
+
+    /// `yield(value)`: suspends the running coroutine, handing `value` to `resume`.
+    pub fn yield_(mut args: Vec<Object>, env: &GlobalEnv) -> Result<Object> {
+        expect_args("yield", &args, 1)?;
+        vm::yield_value(args.pop().expect("one argument"), env)?;
+        Ok(Object::Nil)
+    }
+}
+
+#[cfg(feature = "builtins-io")]
+mod io {
+    use std::{
+        fs::{File, OpenOptions},
+        io::{Read, Write},
+    };
+
+    use anyhow::{bail, Result};
+
+    use super::expect_args;
+    use crate::{
+        environment::GlobalEnv,
+        eval::EvalError,
+        object::{Handle, Object, Resource},
+        prelude::*,
+    };
+
+    #[derive(Debug)]
+    struct FileResource(File);
+
+    impl Resource for FileResource {
+        fn kind(&self) -> &'static str {
+            "file"
+        }
+
+        fn read_to_string(&mut self) -> Result<String> {
+            let mut contents = String::new();
+            self.0.read_to_string(&mut contents)?;
+            Ok(contents)
+        }
+
+        fn write_str(&mut self, text: &str) -> Result<()> {
+            self.0.write_all(text.as_bytes())?;
+            Ok(())
+        }
+    }
+
+    fn handle(name: &str, args: &[Object]) -> Result<Handle> {
+        match &args[0] {
+            Object::Handle(handle) => Ok(handle.clone()),
+            other => bail!("argument to {name} must be a handle, got {}", other.name()),
+        }
+    }
+
+    pub fn open(args: Vec<Object>, env: &GlobalEnv) -> Result<Object> {
+        if !env.borrow().runtime.capabilities.io {
+            return Err(EvalError::NotPermitted("open").into());
+        }
+        let (path, mode) = match args.as_slice() {
+            [Object::String(path)] => (&**path, "r"),
+            [Object::String(path), Object::String(mode)] => (&**path, &**mode),
+            _ => bail!("open expects a path and an optional mode"),
+        };
+        let file = match mode {
+            "r" => File::open(path)?,
+            "w" => File::create(path)?,
+            "a" => OpenOptions::new().append(true).create(true).open(path)?,
+            mode => bail!("unknown file mode: {mode}"),
+        };
+        Ok(Object::Handle(Handle::new(
+            path.to_string(),
+            Box::new(FileResource(file)),
+        )))
+    }
+
+    pub fn read(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+        expect_args("read", &args, 1)?;
+        let contents = handle("read", &args)?.with(|resource| resource.read_to_string())?;
+        Ok(Object::String(contents.into()))
+    }
+
+    pub fn write(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+        expect_args("write", &args, 2)?;
+        let handle = handle("write", &args)?;
+        match &args[1] {
+            Object::String(text) => handle.with(|resource| resource.write_str(text))?,
+            other => bail!("argument to write must be a string, got {}", other.name()),
+        }
+        Ok(Object::Nil)
+    }
+
+    pub fn close(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+        expect_args("close", &args, 1)?;
+        handle("close", &args)?.close()?;
+        Ok(Object::Nil)
+    }
+}
+
+#[cfg(feature = "builtins-net")]
+mod url {
+    use alloc::collections::BTreeMap;
+
+    use anyhow::{anyhow, bail, Result};
+
+    use super::expect_args;
+    use crate::{
+        environment::GlobalEnv,
+        object::{HashKey, Object, Shared},
+        prelude::*,
+        token::Int,
+    };
+
+    fn string<'a>(name: &'static str, args: &'a [Object]) -> Result<&'a str> {
+        expect_args(name, args, 1)?;
+        match &args[0] {
+            Object::String(text) => Ok(text),
+            other => bail!("argument to {name} must be a string, got {}", other.name()),
+        }
+    }
+
+    fn optional(text: Option<String>) -> Object {
+        text.map_or(Object::Nil, |text| Object::String(text.into()))
+    }
+
+    /// `url_parse(url)`: the scheme, user, password, host, port, path, query and fragment
+    /// of `url`, nil where absent, and `params`, the query decoded into a hash.
+    pub fn url_parse(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+        let url = string("url_parse", &args)?;
+        let (scheme, rest) = url
+            .split_once(':')
+            .filter(|(scheme, _)| {
+                scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && scheme
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+            })
+            .ok_or_else(|| anyhow!("url has no scheme: {url}"))?;
+        let (rest, fragment) = match rest.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment)),
+            None => (rest, None),
+        };
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query)),
+            None => (rest, None),
+        };
+        let (mut user, mut password, mut host, mut port) = (None, None, None, Object::Nil);
+        let path = match rest.strip_prefix("//") {
+            Some(rest) => {
+                let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+                let host_port = match authority.rsplit_once('@') {
+                    Some((userinfo, host_port)) => {
+                        let (name, secret) = match userinfo.split_once(':') {
+                            Some((name, secret)) => (name, Some(secret)),
+                            None => (userinfo, None),
+                        };
+                        user = Some(decode(name, false)?);
+                        password = secret.map(|secret| decode(secret, false)).transpose()?;
+                        host_port
+                    }
+                    None => authority,
+                };
+                // The port follows the last colon, unless that is inside an IPv6 address.
+                let (name, number) = match host_port.rsplit_once(':') {
+                    Some((name, number)) if !number.contains(']') => (name, Some(number)),
+                    _ => (host_port, None),
+                };
+                if let Some(number) = number.filter(|number| !number.is_empty()) {
+                    let number: Int = number
+                        .parse()
+                        .ok()
+                        .filter(|number| (0..=65535).contains(number))
+                        .ok_or_else(|| anyhow!("invalid port in url: {url}"))?;
+                    port = Object::Int(number);
+                }
+                host = Some(name.to_lowercase());
+                path
+            }
+            None => rest,
+        };
+        let params = match query {
+            Some(query) => query_params(query)?,
+            None => BTreeMap::new(),
+        };
+        let pairs = [
+            ("scheme", optional(Some(scheme.to_lowercase()))),
+            ("user", optional(user)),
+            ("password", optional(password)),
+            ("host", optional(host)),
+            ("port", port),
+            ("path", Object::String(path.into())),
+            ("query", optional(query.map(String::from))),
+            ("fragment", optional(fragment.map(String::from))),
+            ("params", Object::Hash(Shared::new(params))),
+        ];
+        let pairs = pairs
+            .into_iter()
+            .map(|(key, value)| (HashKey::String(key.to_string()), value));
+        Ok(Object::Hash(Shared::new(pairs.collect())))
+    }
+
+    /// Decodes `a=1&b=x+y` into a hash of strings. A key given twice keeps its last value.
+    fn query_params(query: &str) -> Result<BTreeMap<HashKey, Object>> {
+        let mut params = BTreeMap::new();
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            params.insert(
+                HashKey::String(decode(key, true)?),
+                Object::String(decode(value, true)?.into()),
+            );
+        }
+        Ok(params)
+    }
+
+    /// Undoes percent-encoding, and with `plus` the `+` that forms write for a space.
+    fn decode(text: &str, plus: bool) -> Result<String> {
+        let mut bytes = vec![];
+        let mut rest = text.as_bytes();
+        while let Some((&byte, tail)) = rest.split_first() {
+            rest = tail;
+            match byte {
+                b'%' => {
+                    let digits = rest
+                        .get(..2)
+                        .and_then(|digits| core::str::from_utf8(digits).ok())
+                        .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                        .ok_or_else(|| anyhow!("invalid percent-encoding in {text}"))?;
+                    bytes.push(digits);
+                    rest = &rest[2..];
+                }
+                b'+' if plus => bytes.push(b' '),
+                byte => bytes.push(byte),
+            }
+        }
+        String::from_utf8(bytes).map_err(|_| anyhow!("percent-encoding is not UTF-8 in {text}"))
+    }
+
+    /// Percent-encodes every byte of `text` but letters, digits and `-._~`.
+    fn encode(text: &str) -> String {
+        let mut out = String::new();
+        for byte in text.bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                    out.push(char::from(byte))
+                }
+                byte => out.push_str(&format!("%{byte:02X}")),
+            }
+        }
+        out
+    }
+
+    /// `url_encode(string)` percent-encodes `string`; `url_encode(hash)` builds a query
+    /// string from its pairs, in key order.
+    pub fn url_encode(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+        expect_args("url_encode", &args, 1)?;
+        let encoded = match &args[0] {
+            Object::String(text) => encode(text),
+            Object::Hash(pairs) => {
+                let pairs: Vec<String> = pairs
+                    .iter()
+                    .map(|(key, value)| {
+                        let value = match value {
+                            Object::String(text) => text.to_string(),
+                            value => value.to_string(),
+                        };
+                        format!("{}={}", encode(&key.to_string()), encode(&value))
+                    })
+                    .collect();
+                pairs.join("&")
+            }
+            other => bail!(
+                "argument to url_encode must be a string or hash, got {}",
+                other.name()
+            ),
+        };
+        Ok(Object::String(encoded.into()))
+    }
+
+    /// `url_decode(string)`: `string` with its percent-encoding undone.
+    pub fn url_decode(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+        let decoded = decode(string("url_decode", &args)?, false)?;
+        Ok(Object::String(decoded.into()))
+    }
+}
+
+#[cfg(feature = "builtins-csv")]
+mod csv {
+    use alloc::collections::BTreeSet;
+    use core::mem;
+
+    use anyhow::{bail, Result};
+
+    use crate::{
+        environment::GlobalEnv,
+        eval::EvalError,
+        object::{HashKey, Object, Shared},
+        prelude::*,
+    };
+
+    /// Splits `text` into records of fields. Fields may be quoted, with `""` standing for a
+    /// quote, to hold commas and line breaks. Empty lines are skipped.
+    fn records(text: &str) -> Result<Vec<Vec<String>>> {
+        let mut records = vec![];
+        let mut record = vec![];
+        let mut field = String::new();
+        // Whether the field was quoted, so that `""` still counts as a field.
+        let mut quoted = false;
+        let mut line = 1;
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if field.is_empty() && !quoted => {
+                    quoted = true;
+                    let start = line;
+                    loop {
+                        match chars.next() {
+                            Some('"') if chars.peek() == Some(&'"') => {
+                                chars.next();
+                                field.push('"');
+                            }
+                            Some('"') => break,
+                            Some(c) => {
+                                line += usize::from(c == '\n');
+                                field.push(c);
+                            }
+                            None => bail!("unterminated quoted field starting on line {start}"),
+                        }
+                    }
+                    if !matches!(chars.peek(), None | Some(',' | '\r' | '\n')) {
+                        bail!("unexpected text after a quoted field on line {line}");
+                    }
+                }
+                ',' => {
+                    record.push(mem::take(&mut field));
+                    quoted = false;
+                }
+                '\r' | '\n' => {
+                    if c == '\r' && chars.peek() == Some(&'\n') {
+                        chars.next();
+                    }
+                    line += 1;
+                    if !record.is_empty() || !field.is_empty() || quoted {
+                        record.push(mem::take(&mut field));
+                        records.push(mem::take(&mut record));
+                    }
+                    quoted = false;
+                }
+                c => field.push(c),
+            }
+        }
+        if !record.is_empty() || !field.is_empty() || quoted {
+            record.push(field);
+            records.push(record);
+        }
+        Ok(records)
+    }
+
+    /// Parses CSV text into an array of records, each an array of string fields. With
+    /// `{"headers": true}` the first record names the fields and the rest become hashes.
+    pub fn csv_parse(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+        let (text, options) = match args.as_slice() {
+            [Object::String(text)] => (text, None),
+            [Object::String(text), Object::Hash(options)] => (text, Some(options)),
+            [Object::String(_), other] => {
+                bail!("options to csv_parse must be a hash, got {}", other.name())
+            }
+            [other, ..] if args.len() <= 2 => {
+                bail!(
+                    "argument to csv_parse must be a string, got {}",
+                    other.name()
+                )
+            }
+            _ => {
+                return Err(EvalError::WrongArity {
+                    function: Some("csv_parse"),
+                    expected: 2,
+                    got: args.len(),
+                }
+                .into())
+            }
+        };
+        let mut headers = false;
+        for (key, value) in options.iter().flat_map(|options| options.iter()) {
+            match (key, value) {
+                (HashKey::String(key), Object::Bool(value)) if key == "headers" => headers = *value,
+                (HashKey::String(key), value) if key == "headers" => bail!(
+                    "csv_parse option headers must be a bool, got {}",
+                    value.name()
+                ),
+                (key, _) => bail!("unknown csv_parse option {key}"),
+            }
+        }
+        let mut records = records(text)?.into_iter();
+        let string = |field: String| Object::String(field.into());
+        let rows: Vec<Object> = match headers {
+            false => records
+                .map(|record| Object::Array(Shared::new(record.into_iter().map(string).collect())))
+                .collect(),
+            true => {
+                let names = records.next().unwrap_or_default();
+                let mut rows = vec![];
+                for (index, record) in records.enumerate() {
+                    if record.len() != names.len() {
+                        bail!(
+                            "csv record {} has {} fields, expected {}",
+                            index + 2,
+                            record.len(),
+                            names.len()
+                        );
+                    }
+                    let pairs = names
+                        .iter()
+                        .map(|name| HashKey::String(name.clone()))
+                        .zip(record.into_iter().map(string));
+                    rows.push(Object::Hash(Shared::new(pairs.collect())));
+                }
+                rows
+            }
+        };
+        Ok(Object::Array(Shared::new(rows)))
+    }
+
+    fn field(value: Option<&Object>) -> Result<String> {
+        let text = match value {
+            None | Some(Object::Nil) => String::new(),
+            Some(Object::String(text)) => text.to_string(),
+            Some(value @ (Object::Int(_) | Object::Float(_) | Object::Bool(_))) => {
+                value.to_string()
+            }
+            Some(other) => bail!(
+                "csv fields must be strings, numbers, bools or nil, got {}",
+                other.name()
+            ),
+        };
+        if !text.contains([',', '"', '\r', '\n']) {
+            return Ok(text);
+        }
+        Ok(format!("\"{}\"", text.replace('"', "\"\"")))
+    }
+
+    fn line(out: &mut String, fields: Vec<String>) {
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+
+    /// Writes rows as CSV text. Rows are arrays of fields, or hashes, in which case a header
+    /// line lists their keys in sorted order and missing keys are left empty.
+    pub fn csv_stringify(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+        super::expect_args("csv_stringify", &args, 1)?;
+        let Object::Array(rows) = &args[0] else {
+            bail!(
+                "argument to csv_stringify must be an array, got {}",
+                args[0].name()
+            );
+        };
+        let mut out = String::new();
+        if rows.iter().all(|row| matches!(row, Object::Array(_))) {
+            for row in rows.iter() {
+                let Object::Array(fields) = row else {
+                    unreachable!("checked above")
+                };
+                line(
+                    &mut out,
+                    fields.iter().map(Some).map(field).collect::<Result<_>>()?,
+                );
+            }
+            return Ok(Object::String(out.into()));
+        }
+        let mut hashes = vec![];
+        for row in rows.iter() {
+            match row {
+                Object::Hash(pairs) => hashes.push(&**pairs),
+                other => bail!(
+                    "csv rows must be all arrays or all hashes, got {}",
+                    other.name()
+                ),
+            }
+        }
+        let names: BTreeSet<&HashKey> = hashes.iter().flat_map(|pairs| pairs.keys()).collect();
+        let header = names
+            .iter()
+            .map(|key| field(Some(&Object::from((*key).clone()))))
+            .collect::<Result<_>>()?;
+        line(&mut out, header);
+        for pairs in hashes {
+            let fields = names.iter().map(|key| field(pairs.get(*key)));
+            line(&mut out, fields.collect::<Result<_>>()?);
+        }
+        Ok(Object::String(out.into()))
+    }
+}
+
+#[cfg(any(feature = "builtins-toml", feature = "builtins-yaml"))]
+mod config {
+    use anyhow::{anyhow, bail, Result};
+
+    use super::expect_args;
+    use crate::{
+        environment::GlobalEnv,
+        object::{Object, Shared},
+        prelude::*,
+        token::Int,
+    };
+
+    fn text<'a>(name: &'static str, args: &'a [Object]) -> Result<&'a str> {
+        expect_args(name, args, 1)?;
+        match &args[0] {
+            Object::String(text) => Ok(text),
+            other => bail!("argument to {name} must be a string, got {}", other.name()),
+        }
+    }
+
+    fn int(value: i64) -> Result<Object> {
+        #[cfg(feature = "int-i32")]
+        let value = Int::try_from(value).map_err(|_| anyhow!("integer out of range: {value}"))?;
+        // A no-op with the default i64 ints.
+        #[cfg(not(feature = "int-i32"))]
+        #[allow(clippy::useless_conversion)]
+        let value = Int::from(value);
+        Ok(Object::Int(value))
+    }
+
+    /// `toml_parse(text)`: the TOML document as a hash. Dates and times become strings.
+    #[cfg(feature = "builtins-toml")]
+    pub fn toml_parse(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+        let table: toml::Table = text("toml_parse", &args)?
+            .parse()
+            .map_err(|err: toml::de::Error| anyhow!("{}", err.to_string().trim_end()))?;
+        toml_value(toml::Value::Table(table))
+    }
+
+    #[cfg(feature = "builtins-toml")]
+    fn toml_value(value: toml::Value) -> Result<Object> {
+        use crate::object::HashKey;
+
+        Ok(match value {
+            toml::Value::String(string) => Object::String(string.into()),
+            toml::Value::Integer(value) => int(value)?,
+            toml::Value::Float(value) => Object::Float(value),
+            toml::Value::Boolean(value) => Object::Bool(value),
+            toml::Value::Datetime(datetime) => Object::String(datetime.to_string().into()),
+            toml::Value::Array(values) => Object::Array(Shared::new(
+                values.into_iter().map(toml_value).collect::<Result<_>>()?,
+            )),
+            toml::Value::Table(table) => {
+                let pairs = table
+                    .into_iter()
+                    .map(|(key, value)| Ok((HashKey::String(key), toml_value(value)?)));
+                Object::Hash(Shared::new(pairs.collect::<Result<_>>()?))
+            }
+        })
+    }
+
+    /// `yaml_parse(text)`: the first YAML document in `text`, or nil if there is none.
+    #[cfg(feature = "builtins-yaml")]
+    pub fn yaml_parse(args: Vec<Object>, _env: &GlobalEnv) -> Result<Object> {
+        let documents = yaml_rust2::YamlLoader::load_from_str(text("yaml_parse", &args)?)
+            .map_err(|err| anyhow!("invalid YAML: {err}"))?;
+        match documents.into_iter().next() {
+            Some(document) => yaml_value(document),
+            None => Ok(Object::Nil),
+        }
+    }
+
+    #[cfg(feature = "builtins-yaml")]
+    fn yaml_value(value: yaml_rust2::Yaml) -> Result<Object> {
+        use yaml_rust2::Yaml;
+
+        Ok(match value {
+            Yaml::Null => Object::Nil,
+            Yaml::String(string) => Object::String(string.into()),
+            Yaml::Integer(value) => int(value)?,
+            Yaml::Real(text) => Object::Float(
+                text.parse()
+                    .map_err(|_| anyhow!("invalid YAML float: {text}"))?,
+            ),
+            Yaml::Boolean(value) => Object::Bool(value),
+            Yaml::Array(values) => Object::Array(Shared::new(
+                values.into_iter().map(yaml_value).collect::<Result<_>>()?,
+            )),
+            Yaml::Hash(pairs) => {
+                let pairs = pairs
+                    .into_iter()
+                    .map(|(key, value)| Ok((yaml_value(key)?.hash_key()?, yaml_value(value)?)));
+                Object::Hash(Shared::new(pairs.collect::<Result<_>>()?))
+            }
+            Yaml::Alias(_) | Yaml::BadValue => bail!("unsupported YAML value"),
+        })
+    }
+}
@@ -0,0 +1,235 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::fmt::Write;
+
+use crate::{
+    ast::{Expression, Function, Statement},
+    prelude::*,
+    visitor::{walk_expression, walk_function, walk_statement, Visitor},
+};
+
+/// The caller of calls made outside of any function.
+pub const PROGRAM: &str = "<program>";
+
+/// Which functions bound with `let` call which, found without running the program.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CallGraph {
+    /// Function names in the order they are declared. Functions declared inside another
+    /// are named after it, as in `outer.inner`.
+    pub functions: Vec<String>,
+    /// Caller and callee pairs, with [`PROGRAM`] as the caller of top-level calls.
+    pub calls: BTreeSet<(String, String)>,
+}
+
+/// Builds the call graph of `statements`. Calls are resolved the way the evaluator scopes
+/// names, so a parameter or local shadowing a function hides it. Calls made from an
+/// anonymous function count as calls of the function it is written in; calls to builtins
+/// and through values that aren't plain names are left out.
+pub fn call_graph(statements: &[Statement]) -> CallGraph {
+    let mut builder = Builder {
+        graph: CallGraph::default(),
+        scopes: vec![BTreeMap::new()],
+        callers: vec![],
+    };
+    // Top-level functions may call ones declared further down.
+    for statement in statements {
+        if let Statement::Let {
+            identifier,
+            expression: Expression::Function(_),
+        } = statement
+        {
+            let index = builder.graph.functions.len();
+            builder.graph.functions.push(identifier.get_name());
+            builder.scopes[0].insert(identifier.get_name(), Some(index));
+        }
+    }
+    statements
+        .iter()
+        .for_each(|statement| builder.visit_statement(statement));
+    builder.graph
+}
+
+impl CallGraph {
+    /// The graph in Graphviz DOT.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph calls {\n");
+        if self.calls.iter().any(|(caller, _)| caller == PROGRAM) {
+            let _ = writeln!(out, "    \"{PROGRAM}\" [shape=box];");
+        }
+        for function in &self.functions {
+            let _ = writeln!(out, "    \"{function}\";");
+        }
+        for (caller, callee) in &self.calls {
+            let _ = writeln!(out, "    \"{caller}\" -> \"{callee}\";");
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+struct Builder {
+    graph: CallGraph,
+    /// What each name in scope refers to: the index of a function, or `None` for any
+    /// other value.
+    scopes: Vec<BTreeMap<String, Option<usize>>>,
+    /// Indices of the named functions being walked.
+    callers: Vec<usize>,
+}
+
+impl Builder {
+    fn declare(&mut self, name: String, function: Option<usize>) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name, function);
+        }
+    }
+
+    fn resolve(&self, name: &str) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name).copied())?
+    }
+
+    fn enter(&mut self, function: &Function) {
+        let parameters = function.params.iter().chain(&function.rest);
+        let parameters = parameters.map(|p| (p.get_name(), None));
+        self.scopes.push(parameters.collect());
+        walk_function(self, function);
+        self.scopes.pop();
+    }
+}
+
+impl Visitor for Builder {
+    fn visit_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Let {
+                identifier,
+                expression: Expression::Function(function),
+            } => {
+                let name = identifier.get_name();
+                let declared = match self.scopes.len() {
+                    1 => self.resolve(&name),
+                    _ => None,
+                };
+                let index = declared.unwrap_or_else(|| {
+                    let qualified = match self.callers.last() {
+                        Some(&outer) => format!("{}.{name}", self.graph.functions[outer]),
+                        None => name.clone(),
+                    };
+                    self.graph.functions.push(qualified);
+                    self.graph.functions.len() - 1
+                });
+                // A function can call itself.
+                self.declare(name, Some(index));
+                self.callers.push(index);
+                self.enter(function);
+                self.callers.pop();
+            }
+            Statement::Let { identifier, .. } => {
+                walk_statement(self, statement);
+                self.declare(identifier.get_name(), None);
+            }
+            Statement::For {
+                variable,
+                value,
+                iterable,
+                body,
+            } => {
+                self.visit_expression(iterable);
+                let variables = [Some(variable), value.as_ref()];
+                self.scopes.push(
+                    variables
+                        .into_iter()
+                        .flatten()
+                        .map(|name| (name.get_name(), None))
+                        .collect(),
+                );
+                self.visit_block(body);
+                self.scopes.pop();
+            }
+            statement => walk_statement(self, statement),
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        if let Expression::Comprehension(comprehension) = expression {
+            self.visit_expression(&comprehension.iterable);
+            self.scopes.push(
+                comprehension
+                    .variables()
+                    .map(|name| (name.get_name(), None))
+                    .collect(),
+            );
+            if let Some(condition) = &comprehension.condition {
+                self.visit_expression(condition);
+            }
+            self.visit_expression(&comprehension.element);
+            self.scopes.pop();
+            return;
+        }
+        if let Expression::Call(call) = expression {
+            if let Expression::Identifier(name) = &*call.function {
+                if let Some(callee) = self.resolve(&name.0) {
+                    let caller = match self.callers.last() {
+                        Some(&caller) => self.graph.functions[caller].clone(),
+                        None => PROGRAM.to_string(),
+                    };
+                    let callee = self.graph.functions[callee].clone();
+                    self.graph.calls.insert((caller, callee));
+                }
+            }
+        }
+        walk_expression(self, expression);
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        self.enter(function);
+    }
+}
+
+#[cfg(test)]
+mod callgraph_tests {
+    use super::call_graph;
+    use crate::{lexer::Lexer, parser::Parser, prelude::*};
+
+    #[test]
+    fn finds_calls_between_functions() {
+        let source = "let is_even = fn(n) { if (n == 0) { true } else { is_odd(n - 1) } };\n\
+                      let is_odd = fn(n) { if (n == 0) { false } else { is_even(n - 1) } };\n\
+                      let apply = fn(f, x) { f(x) };\n\
+                      let main = fn(items) {\n\
+                      \x20   let check = fn(x) { is_even(x) };\n\
+                      \x20   let is_odd = 1;\n\
+                      \x20   puts(len(items));\n\
+                      \x20   map(items, fn(x) { check(x) })\n\
+                      };\n\
+                      let map = fn(items, f) { apply(f, first(items)) };\n\
+                      main([1, 2]);";
+        let statements: Vec<_> = Parser::new(Lexer::new(source).tokens())
+            .map(Result::unwrap)
+            .collect();
+        let graph = call_graph(&statements);
+        assert_eq!(
+            graph.functions,
+            vec!["is_even", "is_odd", "apply", "main", "map", "main.check"]
+        );
+        assert_eq!(
+            graph.to_dot(),
+            "digraph calls {\n\
+             \x20   \"<program>\" [shape=box];\n\
+             \x20   \"is_even\";\n\
+             \x20   \"is_odd\";\n\
+             \x20   \"apply\";\n\
+             \x20   \"main\";\n\
+             \x20   \"map\";\n\
+             \x20   \"main.check\";\n\
+             \x20   \"<program>\" -> \"main\";\n\
+             \x20   \"is_even\" -> \"is_odd\";\n\
+             \x20   \"is_odd\" -> \"is_even\";\n\
+             \x20   \"main\" -> \"main.check\";\n\
+             \x20   \"main\" -> \"map\";\n\
+             \x20   \"main.check\" -> \"is_even\";\n\
+             \x20   \"map\" -> \"apply\";\n\
+             }\n"
+        );
+    }
+}
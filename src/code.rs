@@ -0,0 +1,266 @@
+use core::fmt::Write;
+
+use crate::prelude::*;
+
+pub type Instructions = Vec<u8>;
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    Constant,
+    Pop,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    True,
+    False,
+    Null,
+    Equal,
+    NotEqual,
+    GreaterThan,
+    GreaterEqual,
+    LessThan,
+    LessEqual,
+    Minus,
+    Bang,
+    JumpNotTruthy,
+    Jump,
+    GetGlobal,
+    SetGlobal,
+    GetLocal,
+    SetLocal,
+    GetBuiltin,
+    GetFree,
+    CurrentClosure,
+    Array,
+    Hash,
+    Index,
+    Call,
+    ReturnValue,
+    Return,
+    Closure,
+    /// Replaces the value on top of the stack with what a loop over it steps through, see
+    /// [`crate::object::Object::loop_items`].
+    LoopItems,
+    Iterate,
+    /// Appends the value on top of the stack to the array under a comprehension's items and
+    /// index.
+    Collect,
+    /// Pops a value and jumps to the first operand if it is a closure over the compiled
+    /// function in the constant named by the second, where that function is inlined.
+    GuardFunction,
+    /// Pops a value and pushes a new cell holding it, for a variable closures assign.
+    NewCell,
+    /// Pops a cell and pushes the value it holds.
+    Deref,
+    /// Pops a value and a cell, puts the value in the cell and pushes it again.
+    SetCell,
+    /// Pops a closure and calls it when the current function returns or an error unwinds it.
+    Defer,
+}
+
+const OPCODES: &[Opcode] = &[
+    Opcode::Constant,
+    Opcode::Pop,
+    Opcode::Add,
+    Opcode::Sub,
+    Opcode::Mul,
+    Opcode::Div,
+    Opcode::Mod,
+    Opcode::True,
+    Opcode::False,
+    Opcode::Null,
+    Opcode::Equal,
+    Opcode::NotEqual,
+    Opcode::GreaterThan,
+    Opcode::GreaterEqual,
+    Opcode::LessThan,
+    Opcode::LessEqual,
+    Opcode::Minus,
+    Opcode::Bang,
+    Opcode::JumpNotTruthy,
+    Opcode::Jump,
+    Opcode::GetGlobal,
+    Opcode::SetGlobal,
+    Opcode::GetLocal,
+    Opcode::SetLocal,
+    Opcode::GetBuiltin,
+    Opcode::GetFree,
+    Opcode::CurrentClosure,
+    Opcode::Array,
+    Opcode::Hash,
+    Opcode::Index,
+    Opcode::Call,
+    Opcode::ReturnValue,
+    Opcode::Return,
+    Opcode::Closure,
+    Opcode::LoopItems,
+    Opcode::Iterate,
+    Opcode::Collect,
+    Opcode::GuardFunction,
+    Opcode::NewCell,
+    Opcode::Deref,
+    Opcode::SetCell,
+    Opcode::Defer,
+];
+
+impl Opcode {
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        OPCODES.get(byte as usize).copied()
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Opcode::Constant => "OpConstant",
+            Opcode::Pop => "OpPop",
+            Opcode::Add => "OpAdd",
+            Opcode::Sub => "OpSub",
+            Opcode::Mul => "OpMul",
+            Opcode::Div => "OpDiv",
+            Opcode::Mod => "OpMod",
+            Opcode::True => "OpTrue",
+            Opcode::False => "OpFalse",
+            Opcode::Null => "OpNull",
+            Opcode::Equal => "OpEqual",
+            Opcode::NotEqual => "OpNotEqual",
+            Opcode::GreaterThan => "OpGreaterThan",
+            Opcode::GreaterEqual => "OpGreaterEqual",
+            Opcode::LessThan => "OpLessThan",
+            Opcode::LessEqual => "OpLessEqual",
+            Opcode::Minus => "OpMinus",
+            Opcode::Bang => "OpBang",
+            Opcode::JumpNotTruthy => "OpJumpNotTruthy",
+            Opcode::Jump => "OpJump",
+            Opcode::GetGlobal => "OpGetGlobal",
+            Opcode::SetGlobal => "OpSetGlobal",
+            Opcode::GetLocal => "OpGetLocal",
+            Opcode::SetLocal => "OpSetLocal",
+            Opcode::GetBuiltin => "OpGetBuiltin",
+            Opcode::GetFree => "OpGetFree",
+            Opcode::CurrentClosure => "OpCurrentClosure",
+            Opcode::Array => "OpArray",
+            Opcode::Hash => "OpHash",
+            Opcode::Index => "OpIndex",
+            Opcode::Call => "OpCall",
+            Opcode::ReturnValue => "OpReturnValue",
+            Opcode::Return => "OpReturn",
+            Opcode::Closure => "OpClosure",
+            Opcode::LoopItems => "OpLoopItems",
+            Opcode::Iterate => "OpIterate",
+            Opcode::Collect => "OpCollect",
+            Opcode::GuardFunction => "OpGuardFunction",
+            Opcode::NewCell => "OpNewCell",
+            Opcode::Deref => "OpDeref",
+            Opcode::SetCell => "OpSetCell",
+            Opcode::Defer => "OpDefer",
+        }
+    }
+
+    pub fn operand_widths(self) -> &'static [usize] {
+        match self {
+            Opcode::Constant
+            | Opcode::JumpNotTruthy
+            | Opcode::Jump
+            | Opcode::GetGlobal
+            | Opcode::SetGlobal
+            | Opcode::Array
+            | Opcode::Hash => &[2],
+            Opcode::GetLocal
+            | Opcode::SetLocal
+            | Opcode::GetBuiltin
+            | Opcode::GetFree
+            | Opcode::Call
+            | Opcode::LoopItems => &[1],
+            Opcode::Closure | Opcode::Iterate => &[2, 1],
+            Opcode::GuardFunction => &[2, 2],
+            _ => &[],
+        }
+    }
+}
+
+pub fn make(op: Opcode, operands: &[usize]) -> Instructions {
+    let mut instruction = vec![op as u8];
+    for (operand, width) in operands.iter().zip(op.operand_widths()) {
+        match width {
+            2 => instruction.extend_from_slice(&(*operand as u16).to_be_bytes()),
+            _ => instruction.push(*operand as u8),
+        }
+    }
+    instruction
+}
+
+pub fn read_operands(op: Opcode, instructions: &[u8]) -> ([usize; 2], usize) {
+    let mut operands = [0; 2];
+    let mut offset = 0;
+    for (index, width) in op.operand_widths().iter().enumerate() {
+        operands[index] = match width {
+            2 => u16::from_be_bytes([instructions[offset], instructions[offset + 1]]) as usize,
+            _ => instructions[offset] as usize,
+        };
+        offset += width;
+    }
+    (operands, offset)
+}
+
+pub fn disassemble(instructions: &[u8]) -> String {
+    let mut out = String::new();
+    let mut ip = 0;
+    while ip < instructions.len() {
+        let Some(op) = Opcode::from_byte(instructions[ip]) else {
+            let _ = writeln!(out, "{ip:04} ERROR: unknown opcode {}", instructions[ip]);
+            ip += 1;
+            continue;
+        };
+        let (operands, read) = read_operands(op, &instructions[ip + 1..]);
+        let _ = write!(out, "{ip:04} {}", op.name());
+        for operand in &operands[..op.operand_widths().len()] {
+            let _ = write!(out, " {operand}");
+        }
+        out.push('\n');
+        ip += 1 + read;
+    }
+    out
+}
+
+#[cfg(test)]
+mod code_tests {
+    use super::{disassemble, make, read_operands, Opcode};
+
+    #[test]
+    fn make_instructions() {
+        assert_eq!(make(Opcode::Constant, &[65534]), vec![0, 255, 254]);
+        assert_eq!(make(Opcode::Add, &[]), vec![Opcode::Add as u8]);
+        assert_eq!(
+            make(Opcode::GetLocal, &[255]),
+            vec![Opcode::GetLocal as u8, 255]
+        );
+        assert_eq!(
+            make(Opcode::Closure, &[65534, 255]),
+            vec![Opcode::Closure as u8, 255, 254, 255]
+        );
+    }
+
+    #[test]
+    fn read_and_disassemble() {
+        let instruction = make(Opcode::Closure, &[65535, 255]);
+        assert_eq!(
+            read_operands(Opcode::Closure, &instruction[1..]),
+            ([65535, 255], 3)
+        );
+
+        let instructions = [
+            make(Opcode::Add, &[]),
+            make(Opcode::GetLocal, &[1]),
+            make(Opcode::Constant, &[2]),
+            make(Opcode::Constant, &[65535]),
+            make(Opcode::Closure, &[65535, 255]),
+        ]
+        .concat();
+        assert_eq!(
+            disassemble(&instructions),
+            "0000 OpAdd\n0001 OpGetLocal 1\n0003 OpConstant 2\n0006 OpConstant 65535\n0009 OpClosure 65535 255\n"
+        );
+    }
+}
@@ -0,0 +1,1312 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
+use anyhow::{bail, Result};
+use core::mem;
+
+use crate::{
+    ast::{
+        Block, Call, Comprehension, Expression, Function, If, InfixOperation, Literal,
+        PrefixOperation, Statement,
+    },
+    builtins,
+    code::{make, read_operands, Instructions, Opcode},
+    eval::EvalError,
+    macros,
+    object::{CompiledFunction, Object},
+    prelude::*,
+    token::Identifier,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolScope {
+    Global,
+    Local,
+    Builtin,
+    Free,
+    Function,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub name: String,
+    pub scope: SymbolScope,
+    pub index: usize,
+    /// A global bound afresh on each pass of a top-level loop. Closures capture it as they
+    /// do locals, instead of reading whatever the slot holds when they are called.
+    pub fresh: bool,
+    /// Held in a cell shared with the closures that capture it, as it is assigned after
+    /// they may have.
+    pub cell: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    outer: Option<Box<SymbolTable>>,
+    store: BTreeMap<String, Symbol>,
+    pub num_definitions: usize,
+    pub free_symbols: Vec<Symbol>,
+    /// Names whose locals, or loop globals, are held in cells, see [`captured_and_assigned`].
+    cells: BTreeSet<String>,
+}
+
+impl SymbolTable {
+    pub fn new_enclosed(outer: SymbolTable) -> Self {
+        Self {
+            outer: Some(Box::new(outer)),
+            ..Default::default()
+        }
+    }
+
+    /// Defines `name` in this scope. Redefining a name reuses its slot.
+    pub fn define(&mut self, name: &str) -> Symbol {
+        if let Some(symbol) = self.store.get(name) {
+            if matches!(symbol.scope, SymbolScope::Global | SymbolScope::Local) {
+                return symbol.clone();
+            }
+        }
+        let scope = match self.outer {
+            Some(_) => SymbolScope::Local,
+            None => SymbolScope::Global,
+        };
+        let symbol = Symbol {
+            name: name.to_string(),
+            scope,
+            index: self.num_definitions,
+            fresh: false,
+            cell: scope == SymbolScope::Local && self.cells.contains(name),
+        };
+        self.num_definitions += 1;
+        self.store.insert(name.to_string(), symbol.clone());
+        symbol
+    }
+
+    /// Defines `name` in a slot of its own, as a loop variable, returning the symbol it hides
+    /// so the loop can [`restore`](SymbolTable::restore) it.
+    pub fn define_fresh(&mut self, name: &str) -> (Symbol, Option<Symbol>) {
+        let hidden = self.store.remove(name);
+        let mut symbol = self.define(name);
+        if symbol.scope == SymbolScope::Global {
+            symbol.fresh = true;
+            symbol.cell = self.cells.contains(name);
+            self.store.insert(name.to_string(), symbol.clone());
+        }
+        (symbol, hidden)
+    }
+
+    /// Puts back the symbol `name` had before [`define_fresh`](SymbolTable::define_fresh).
+    pub fn restore(&mut self, name: &str, hidden: Option<Symbol>) {
+        match hidden {
+            Some(symbol) => self.store.insert(name.to_string(), symbol),
+            None => self.store.remove(name),
+        };
+    }
+
+    pub fn define_function_name(&mut self, name: &str) -> Symbol {
+        let symbol = Symbol {
+            name: name.to_string(),
+            scope: SymbolScope::Function,
+            index: 0,
+            fresh: false,
+            cell: false,
+        };
+        self.store.insert(name.to_string(), symbol.clone());
+        symbol
+    }
+
+    fn define_free(&mut self, original: Symbol) -> Symbol {
+        let symbol = Symbol {
+            name: original.name.clone(),
+            scope: SymbolScope::Free,
+            index: self.free_symbols.len(),
+            fresh: false,
+            cell: original.cell,
+        };
+        self.free_symbols.push(original);
+        self.store.insert(symbol.name.clone(), symbol.clone());
+        symbol
+    }
+
+    /// The symbols defined at the top level of this table.
+    pub fn globals(&self) -> impl Iterator<Item = &Symbol> {
+        self.store
+            .values()
+            .filter(|symbol| matches!(symbol.scope, SymbolScope::Global))
+    }
+
+    /// The symbol `name` resolves to here, without capturing it as a free variable.
+    pub fn lookup(&self, name: &str) -> Option<&Symbol> {
+        match self.store.get(name) {
+            Some(symbol) => Some(symbol),
+            None => self.outer.as_ref()?.lookup(name),
+        }
+    }
+
+    pub fn resolve(&mut self, name: &str) -> Option<Symbol> {
+        if let Some(symbol) = self.store.get(name) {
+            return Some(symbol.clone());
+        }
+        let symbol = self.outer.as_mut()?.resolve(name)?;
+        match symbol.scope {
+            SymbolScope::Global if !symbol.fresh => Some(symbol),
+            SymbolScope::Builtin => Some(symbol),
+            _ => Some(self.define_free(symbol)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct EmittedInstruction {
+    opcode: Opcode,
+    position: usize,
+}
+
+#[derive(Debug, Default)]
+struct CompilationScope {
+    instructions: Instructions,
+    last: Option<EmittedInstruction>,
+    previous: Option<EmittedInstruction>,
+    /// The loops being compiled, innermost last.
+    loops: Vec<Loop>,
+    /// For each `for` body being compiled, the names it binds and the symbols they hide.
+    /// As in the tree-walker, these bindings end with the loop.
+    for_bindings: Vec<Vec<(String, Option<Symbol>)>>,
+}
+
+/// Where `break` and `continue` jump in a loop being compiled.
+#[derive(Debug)]
+struct Loop {
+    /// Where `continue` jumps to.
+    start: usize,
+    /// The jumps `break` leaves, patched once the end of the loop is known.
+    breaks: Vec<usize>,
+    /// Values the loop keeps on the stack, which `break` pops.
+    stack: usize,
+}
+
+/// A function bound by a top-level `let`, kept so the VM can re-compile it once it is hot.
+#[derive(Debug, Clone)]
+pub struct GlobalFunction {
+    pub name: String,
+    pub function: Function,
+    /// The constant it was compiled to.
+    pub constant: usize,
+    /// The symbols its body was compiled against.
+    pub symbols: SymbolTable,
+}
+
+#[derive(Debug)]
+pub struct Bytecode {
+    pub instructions: Instructions,
+    pub constants: Vec<Object>,
+}
+
+/// Lowers statements into bytecode for the [`crate::vm::Vm`].
+#[derive(Debug)]
+pub struct Compiler {
+    pub constants: Vec<Object>,
+    pub symbol_table: SymbolTable,
+    /// Builtins that names no symbol is bound to resolve to, set to the runtime's by
+    /// [`crate::vm::eval`].
+    pub builtins: builtins::Registry,
+    /// Fold identifiers to lowercase, set from the runtime's
+    /// [`case_insensitive`](crate::runtime::Runtime::case_insensitive) by [`crate::vm::eval`].
+    pub case_insensitive: bool,
+    /// Record the functions top-level `let`s bind in [`functions`](Compiler::functions), set
+    /// from the runtime's [`inline_hot`](crate::runtime::Runtime::inline_hot) by
+    /// [`crate::vm::eval`].
+    pub inline_hot: bool,
+    /// The functions bound by top-level `let`s, by global slot.
+    pub functions: BTreeMap<usize, GlobalFunction>,
+    /// Where the code of each statement given to [`compile`](Compiler::compile) starts.
+    pub statement_starts: Vec<usize>,
+    /// Inline calls to small global functions, while [`recompile`](Compiler::recompile) runs.
+    inline: bool,
+    scopes: Vec<CompilationScope>,
+}
+
+impl Default for Compiler {
+    fn default() -> Self {
+        Self::new_with_state(SymbolTable::default(), vec![])
+    }
+}
+
+impl Compiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resumes compilation with the globals and constants of an earlier run, as the REPL does.
+    pub fn new_with_state(symbol_table: SymbolTable, constants: Vec<Object>) -> Self {
+        Self {
+            constants,
+            symbol_table,
+            builtins: builtins::Registry::default(),
+            case_insensitive: false,
+            inline_hot: false,
+            functions: BTreeMap::new(),
+            statement_starts: vec![],
+            inline: false,
+            scopes: vec![CompilationScope::default()],
+        }
+    }
+
+    pub fn compile(&mut self, statements: &[Statement]) -> Result<()> {
+        for statement in statements {
+            let start = self.scope().instructions.len();
+            self.statement_starts.push(start);
+            self.compile_statement(statement)?;
+        }
+        Ok(())
+    }
+
+    /// Compiles `global` again with the calls it makes to small global functions inlined,
+    /// returning the compiled function, which is added to the constants.
+    pub fn recompile(&mut self, global: &GlobalFunction) -> Result<Rc<CompiledFunction>> {
+        self.inline = true;
+        let index = self.compile_function(&global.function, Some(&global.name));
+        self.inline = false;
+        match &self.constants[index?] {
+            Object::CompiledFunction(function) => Ok(function.clone()),
+            other => bail!("not a function: {other}"),
+        }
+    }
+
+    /// The name `identifier` is bound and resolved under.
+    fn name(&self, identifier: &Identifier) -> String {
+        match self.case_insensitive {
+            true => identifier.0.to_lowercase(),
+            false => identifier.get_name(),
+        }
+    }
+
+    pub fn bytecode(self) -> Bytecode {
+        let mut scopes = self.scopes;
+        Bytecode {
+            instructions: mem::take(&mut scopes[0].instructions),
+            constants: self.constants,
+        }
+    }
+
+    fn compile_statement(&mut self, statement: &Statement) -> Result<()> {
+        match statement {
+            Statement::Expression(expression) => {
+                self.compile_expression(expression)?;
+                self.emit(Opcode::Pop, &[]);
+            }
+            Statement::Let {
+                identifier,
+                expression,
+            } => {
+                let name = self.name(identifier);
+                // A new binding in a `for` body is only visible once its value is computed.
+                let symbol = match self.binds_fresh(&name) {
+                    true => None,
+                    false => Some(self.symbol_table.define(&name)),
+                };
+                let constant = match expression {
+                    Expression::Function(function) => {
+                        Some(self.compile_function(function, Some(&name))?)
+                    }
+                    expression => {
+                        self.compile_expression(expression)?;
+                        None
+                    }
+                };
+                let symbol = match symbol {
+                    Some(symbol) => symbol,
+                    None => self.define_fresh_binding(&name),
+                };
+                if let (Some(constant), Expression::Function(function)) = (constant, expression) {
+                    if self.inline_hot && symbol.scope == SymbolScope::Global {
+                        let global = GlobalFunction {
+                            name,
+                            function: function.clone(),
+                            constant,
+                            symbols: self.symbol_table.clone(),
+                        };
+                        self.functions.insert(symbol.index, global);
+                    }
+                }
+                self.bind_symbol(&symbol);
+                self.emit_nil_result();
+            }
+            Statement::While { condition, body } => {
+                let loop_start = self.scope().instructions.len();
+                self.compile_expression(condition)?;
+                let jump_not_truthy = self.emit(Opcode::JumpNotTruthy, &[usize::MAX]);
+                self.compile_loop_body(body, loop_start, 0)?;
+                let after_loop = self.scope().instructions.len();
+                self.change_operand(jump_not_truthy, after_loop);
+                self.emit_nil_result();
+            }
+            Statement::For {
+                variable,
+                value,
+                iterable,
+                body,
+            } => {
+                // The items and the index of the next one stay on the stack while the loop
+                // runs. Each variable and each `let` in the body is one slot for the whole
+                // loop, which closures made in the body capture the value of, so each sees the
+                // pass it was made in, as in the tree-walker.
+                let names: Vec<String> = [Some(variable), value.as_ref()]
+                    .into_iter()
+                    .flatten()
+                    .map(|name| self.name(name))
+                    .collect();
+                self.compile_expression(iterable)?;
+                self.emit(Opcode::LoopItems, &[names.len()]);
+                let start = self.add_constant(Object::Int(0));
+                self.emit(Opcode::Constant, &[start]);
+                let loop_start = self.scope().instructions.len();
+                let iterate = self.emit(Opcode::Iterate, &[usize::MAX, names.len()]);
+                if self.scopes.len() == 1 {
+                    let cells = self.captured_and_assigned(body);
+                    self.symbol_table.cells.extend(cells);
+                }
+                let (symbols, hidden): (Vec<_>, Vec<_>) = names
+                    .iter()
+                    .map(|name| self.symbol_table.define_fresh(name))
+                    .unzip();
+                let bindings = names.iter().cloned().zip(hidden).collect();
+                self.scope_mut().for_bindings.push(bindings);
+                // Iterate pushes the values in order, so the last is on top.
+                for symbol in symbols.iter().rev() {
+                    self.bind_symbol(symbol);
+                }
+                let body = self.compile_loop_body(body, loop_start, 2);
+                let bindings = self.scope_mut().for_bindings.pop().unwrap_or_default();
+                for (name, shadowed) in bindings.into_iter().rev() {
+                    self.symbol_table.restore(&name, shadowed);
+                }
+                body?;
+                let after_loop = self.scope().instructions.len();
+                self.change_operand(iterate, after_loop);
+                self.emit_nil_result();
+            }
+            Statement::Break => {
+                let Some(stack) = self.scope().loops.last().map(|l| l.stack) else {
+                    return Err(EvalError::OutsideLoop("break").into());
+                };
+                for _ in 0..stack {
+                    self.emit(Opcode::Pop, &[]);
+                }
+                let jump = self.emit(Opcode::Jump, &[usize::MAX]);
+                if let Some(innermost) = self.scope_mut().loops.last_mut() {
+                    innermost.breaks.push(jump);
+                }
+            }
+            Statement::Continue => {
+                let Some(start) = self.scope().loops.last().map(|l| l.start) else {
+                    return Err(EvalError::OutsideLoop("continue").into());
+                };
+                self.emit(Opcode::Jump, &[start]);
+            }
+            Statement::Return(expression) => {
+                self.compile_expression(expression)?;
+                self.emit(Opcode::ReturnValue, &[]);
+            }
+            Statement::Block(block) => self.compile_block(block)?,
+            Statement::Defer(expression) => {
+                // A closure over the scope it is deferred in, which the frame calls later.
+                let function = Function {
+                    params: vec![],
+                    rest: None,
+                    body: Rc::new(Block(vec![Statement::Expression(expression.clone())])),
+                };
+                self.compile_function(&function, None)?;
+                self.emit(Opcode::Defer, &[]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether a `let` of `name` starts a binding of the innermost `for` body, which gets a
+    /// slot of its own that the loop gives back when it ends.
+    fn binds_fresh(&self, name: &str) -> bool {
+        self.scope()
+            .for_bindings
+            .last()
+            .is_some_and(|bindings| bindings.iter().all(|(bound, _)| bound != name))
+    }
+
+    fn define_fresh_binding(&mut self, name: &str) -> Symbol {
+        let (symbol, hidden) = self.symbol_table.define_fresh(name);
+        if let Some(bindings) = self.scope_mut().for_bindings.last_mut() {
+            bindings.push((name.to_string(), hidden));
+        }
+        symbol
+    }
+
+    /// Statements other than expressions evaluate to nil at the top level, as in
+    /// the tree-walker.
+    fn emit_nil_result(&mut self) {
+        if self.scopes.len() == 1 {
+            self.emit(Opcode::Null, &[]);
+            self.emit(Opcode::Pop, &[]);
+        }
+    }
+
+    /// Compiles the body of a loop that starts at `start` and keeps `stack` values on the
+    /// stack, followed by the jump back to the start. Breaks jump to just after it.
+    fn compile_loop_body(&mut self, body: &Block, start: usize, stack: usize) -> Result<()> {
+        self.scope_mut().loops.push(Loop {
+            start,
+            breaks: vec![],
+            stack,
+        });
+        let compiled = self.compile_block(body);
+        let innermost = self.scope_mut().loops.pop();
+        compiled?;
+        self.emit(Opcode::Jump, &[start]);
+        let after = self.scope().instructions.len();
+        for jump in innermost.map(|l| l.breaks).unwrap_or_default() {
+            self.change_operand(jump, after);
+        }
+        Ok(())
+    }
+
+    fn compile_block(&mut self, block: &Block) -> Result<()> {
+        for statement in &block.0 {
+            self.compile_statement(statement)?;
+        }
+        Ok(())
+    }
+
+    fn compile_expression(&mut self, expression: &Expression) -> Result<()> {
+        match expression {
+            Expression::Literal(literal) => match literal {
+                Literal::Int(value) => {
+                    let index = self.add_constant(Object::Int(*value));
+                    self.emit(Opcode::Constant, &[index]);
+                }
+                Literal::Float(value) => {
+                    let index = self.add_constant(Object::Float(*value));
+                    self.emit(Opcode::Constant, &[index]);
+                }
+                Literal::String(value) => {
+                    let index = self.add_constant(Object::String(value.as_str().into()));
+                    self.emit(Opcode::Constant, &[index]);
+                }
+                Literal::True => {
+                    self.emit(Opcode::True, &[]);
+                }
+                Literal::False => {
+                    self.emit(Opcode::False, &[]);
+                }
+                Literal::Nil => {
+                    self.emit(Opcode::Null, &[]);
+                }
+            },
+            Expression::Identifier(identifier) => {
+                let name = self.name(identifier);
+                match self.symbol_table.resolve(&name) {
+                    Some(symbol) => self.load_symbol(&symbol),
+                    None => match self.builtins.position(&name) {
+                        Some(index) => {
+                            self.emit(Opcode::GetBuiltin, &[index]);
+                        }
+                        None => return Err(EvalError::IdentifierNotFound(name).into()),
+                    },
+                }
+            }
+            Expression::Prefix(prefix) => {
+                self.compile_expression(&prefix.expression)?;
+                match prefix.operation {
+                    PrefixOperation::Bang => self.emit(Opcode::Bang, &[]),
+                    PrefixOperation::Minus => self.emit(Opcode::Minus, &[]),
+                };
+            }
+            Expression::Infix(infix) => {
+                // Down the left of a chain like `1 + 2 + 3` without recursing, as chains can
+                // be far longer than nesting the parser allows.
+                let mut chain = vec![infix];
+                let mut innermost = &*infix.left_expression;
+                while let Expression::Infix(left) = innermost {
+                    chain.push(left);
+                    innermost = &left.left_expression;
+                }
+                self.compile_expression(innermost)?;
+                for infix in chain.into_iter().rev() {
+                    self.compile_expression(&infix.right_expression)?;
+                    self.compile_operator(&infix.operation)?;
+                }
+            }
+            Expression::If(if_expression) => self.compile_if(if_expression)?,
+            Expression::Function(function) => {
+                self.compile_function(function, None)?;
+            }
+            Expression::Macro(_) => bail!("macros must be defined with a top-level let"),
+            Expression::Assign(identifier, value) => {
+                let name = self.name(identifier);
+                let Some(symbol) = self.symbol_table.resolve(&name) else {
+                    return Err(EvalError::UndeclaredAssignment(name).into());
+                };
+                if symbol.cell {
+                    self.get_symbol(&symbol);
+                    self.compile_expression(value)?;
+                    self.emit(Opcode::SetCell, &[]);
+                    return Ok(());
+                }
+                let (set, get) = match symbol.scope {
+                    SymbolScope::Global => (Opcode::SetGlobal, Opcode::GetGlobal),
+                    SymbolScope::Local => (Opcode::SetLocal, Opcode::GetLocal),
+                    _ => bail!("cannot assign to captured variable {name} in the vm"),
+                };
+                self.compile_expression(value)?;
+                self.emit(set, &[symbol.index]);
+                self.emit(get, &[symbol.index]);
+            }
+            Expression::Call(call) => {
+                if !(self.inline && self.compile_inlined(call)?) {
+                    self.compile_call(call)?;
+                }
+            }
+            Expression::Array(elements) => {
+                for element in elements {
+                    self.compile_expression(element)?;
+                }
+                self.emit(Opcode::Array, &[elements.len()]);
+            }
+            Expression::Comprehension(comprehension) => {
+                self.compile_comprehension(comprehension)?
+            }
+            Expression::Hash(pairs) => {
+                for (key, value) in pairs {
+                    self.compile_expression(key)?;
+                    self.compile_expression(value)?;
+                }
+                self.emit(Opcode::Hash, &[pairs.len() * 2]);
+            }
+            Expression::Index(index) => {
+                self.compile_expression(&index.left)?;
+                self.compile_expression(&index.index)?;
+                self.emit(Opcode::Index, &[]);
+            }
+        }
+        Ok(())
+    }
+
+    fn compile_call(&mut self, call: &Call) -> Result<()> {
+        self.compile_expression(&call.function)?;
+        for argument in &call.arguments {
+            self.compile_expression(argument)?;
+        }
+        self.emit(Opcode::Call, &[call.arguments.len()]);
+        Ok(())
+    }
+
+    /// Compiles `call` with the body of the global function it calls in its place, behind a
+    /// guard that makes the call as usual if the global holds something else by then. Emits
+    /// nothing and returns false unless the function is small and its body, apart from the
+    /// parameters, means the same here as where it was defined.
+    fn compile_inlined(&mut self, call: &Call) -> Result<bool> {
+        let Expression::Identifier(callee) = &*call.function else {
+            return Ok(false);
+        };
+        let name = self.name(callee);
+        let global = match self.symbol_table.lookup(&name) {
+            Some(symbol) if symbol.scope == SymbolScope::Global && !symbol.fresh => symbol.index,
+            _ => return Ok(false),
+        };
+        let Some(target) = self.functions.get(&global).cloned() else {
+            return Ok(false);
+        };
+        let Some((body, steps)) = inline_body(&target.function) else {
+            return Ok(false);
+        };
+        let params: Vec<String> = (target.function.params.iter())
+            .map(|param| self.name(param))
+            .collect();
+        let names: Vec<String> = steps.iter().flatten().map(|name| self.name(name)).collect();
+        let same_meaning = names
+            .iter()
+            .filter(|name| !params.contains(name))
+            .all(|name| {
+                let here = self.symbol_table.lookup(name);
+                *name != target.name
+                    && here
+                        .is_none_or(|symbol| symbol.scope == SymbolScope::Global && !symbol.fresh)
+                    && here == target.symbols.lookup(name)
+            });
+        if params.len() != call.arguments.len() || !same_meaning {
+            return Ok(false);
+        }
+
+        self.emit(Opcode::GetGlobal, &[global]);
+        let guard = self.emit(Opcode::GuardFunction, &[usize::MAX, target.constant]);
+        self.compile_call(call)?;
+        let jump = self.emit(Opcode::Jump, &[usize::MAX]);
+        let inlined = self.scope().instructions.len();
+        self.change_operand(guard, inlined);
+        // Arguments that can't change or be changed by the body replace the parameters.
+        // So can any, if the body reads each parameter once, in order, before anything else
+        // happens, which is when the call would have evaluated them.
+        let unchanging = call.arguments.iter().all(|argument| match argument {
+            Expression::Literal(_) => true,
+            Expression::Identifier(identifier) => self
+                .symbol_table
+                .lookup(&self.name(identifier))
+                .is_some_and(|symbol| symbol.scope == SymbolScope::Local),
+            _ => false,
+        });
+        let first_reads = steps
+            .iter()
+            .map_while(|step| step.map(|name| self.name(name)));
+        let in_order = first_reads.take(params.len()).eq(params.iter().cloned())
+            && params
+                .iter()
+                .all(|param| names.iter().filter(|name| *name == param).count() == 1);
+        // Calls in the inlined body stay calls.
+        self.inline = false;
+        let compiled = match unchanging || in_order {
+            true => self.compile_substituted(body, &params, &call.arguments),
+            false => self.compile_with_parameters(body, &params, &call.arguments),
+        };
+        self.inline = true;
+        compiled?;
+        let after = self.scope().instructions.len();
+        self.change_operand(jump, after);
+        Ok(true)
+    }
+
+    /// Compiles `body` with `arguments` in place of the `params` it reads.
+    fn compile_substituted(
+        &mut self,
+        body: &Expression,
+        params: &[String],
+        arguments: &[Expression],
+    ) -> Result<()> {
+        let case_insensitive = self.case_insensitive;
+        let body = macros::modify_expression(body.clone(), &mut |expression| match expression {
+            Expression::Identifier(identifier) => {
+                let name = match case_insensitive {
+                    true => identifier.0.to_lowercase(),
+                    false => identifier.get_name(),
+                };
+                match params.iter().position(|param| *param == name) {
+                    Some(index) => Ok(arguments[index].clone()),
+                    None => Ok(Expression::Identifier(identifier)),
+                }
+            }
+            expression => Ok(expression),
+        })?;
+        self.compile_expression(&body)
+    }
+
+    /// Compiles `body` after binding `arguments` to `params` in slots of their own.
+    fn compile_with_parameters(
+        &mut self,
+        body: &Expression,
+        params: &[String],
+        arguments: &[Expression],
+    ) -> Result<()> {
+        for argument in arguments {
+            self.compile_expression(argument)?;
+        }
+        let (symbols, hidden): (Vec<_>, Vec<_>) = params
+            .iter()
+            .map(|param| self.symbol_table.define_fresh(param))
+            .unzip();
+        for symbol in symbols.iter().rev() {
+            self.bind_symbol(symbol);
+        }
+        let compiled = self.compile_expression(body);
+        for (param, shadowed) in params.iter().zip(hidden).rev() {
+            self.symbol_table.restore(param, shadowed);
+        }
+        compiled
+    }
+
+    fn compile_operator(&mut self, operation: &InfixOperation) -> Result<()> {
+        let opcode = match operation {
+            InfixOperation::Add => Opcode::Add,
+            InfixOperation::Sub => Opcode::Sub,
+            InfixOperation::Mul => Opcode::Mul,
+            InfixOperation::Div => Opcode::Div,
+            InfixOperation::Mod => Opcode::Mod,
+            InfixOperation::Eq => Opcode::Equal,
+            InfixOperation::NotEq => Opcode::NotEqual,
+            InfixOperation::Gt => Opcode::GreaterThan,
+            InfixOperation::Gte => Opcode::GreaterEqual,
+            InfixOperation::Lt => Opcode::LessThan,
+            InfixOperation::Lte => Opcode::LessEqual,
+            operation => bail!("unknown operator: {operation}"),
+        };
+        self.emit(opcode, &[]);
+        Ok(())
+    }
+
+    fn compile_if(&mut self, if_expression: &If) -> Result<()> {
+        self.compile_expression(&if_expression.condition)?;
+        let jump_not_truthy = self.emit(Opcode::JumpNotTruthy, &[usize::MAX]);
+
+        self.compile_branch(&if_expression.consequence)?;
+        let jump = self.emit(Opcode::Jump, &[usize::MAX]);
+
+        let after_consequence = self.scope().instructions.len();
+        self.change_operand(jump_not_truthy, after_consequence);
+
+        match &if_expression.alternative {
+            Some(alternative) => self.compile_branch(alternative)?,
+            None => {
+                self.emit(Opcode::Null, &[]);
+            }
+        }
+        let after_alternative = self.scope().instructions.len();
+        self.change_operand(jump, after_alternative);
+        Ok(())
+    }
+
+    /// Compiles a branch of an `if` so that it leaves its value on the stack.
+    fn compile_branch(&mut self, block: &Block) -> Result<()> {
+        self.compile_block(block)?;
+        if self.last_instruction_is(Opcode::Pop) {
+            self.remove_last_pop();
+        } else {
+            self.emit(Opcode::Null, &[]);
+        }
+        Ok(())
+    }
+
+    /// Compiles a comprehension as a loop that collects into an array kept on the stack
+    /// under the items and index, which is left as the result.
+    fn compile_comprehension(&mut self, comprehension: &Comprehension) -> Result<()> {
+        let names: Vec<String> = comprehension
+            .variables()
+            .map(|name| self.name(name))
+            .collect();
+        self.emit(Opcode::Array, &[0]);
+        self.compile_expression(&comprehension.iterable)?;
+        self.emit(Opcode::LoopItems, &[names.len()]);
+        let start = self.add_constant(Object::Int(0));
+        self.emit(Opcode::Constant, &[start]);
+        let loop_start = self.scope().instructions.len();
+        let iterate = self.emit(Opcode::Iterate, &[usize::MAX, names.len()]);
+        let (symbols, hidden): (Vec<_>, Vec<_>) = names
+            .iter()
+            .map(|name| self.symbol_table.define_fresh(name))
+            .unzip();
+        for symbol in symbols.iter().rev() {
+            self.bind_symbol(symbol);
+        }
+        // `break` and `continue` inside belong to no loop, not to one around the comprehension.
+        let loops = mem::take(&mut self.scope_mut().loops);
+        let body = self.compile_comprehension_body(comprehension, loop_start);
+        self.scope_mut().loops = loops;
+        for (name, shadowed) in names.iter().zip(hidden).rev() {
+            self.symbol_table.restore(name, shadowed);
+        }
+        body?;
+        let after_loop = self.scope().instructions.len();
+        self.change_operand(iterate, after_loop);
+        Ok(())
+    }
+
+    fn compile_comprehension_body(
+        &mut self,
+        comprehension: &Comprehension,
+        loop_start: usize,
+    ) -> Result<()> {
+        if let Some(condition) = &comprehension.condition {
+            self.compile_expression(condition)?;
+            let skip = self.emit(Opcode::JumpNotTruthy, &[usize::MAX]);
+            self.compile_expression(&comprehension.element)?;
+            self.emit(Opcode::Collect, &[]);
+            self.change_operand(skip, loop_start);
+        } else {
+            self.compile_expression(&comprehension.element)?;
+            self.emit(Opcode::Collect, &[]);
+        }
+        self.emit(Opcode::Jump, &[loop_start]);
+        Ok(())
+    }
+
+    /// Compiles `function` to a constant and emits the closure over it, returning the constant.
+    fn compile_function(&mut self, function: &Function, name: Option<&str>) -> Result<usize> {
+        self.enter_scope();
+        self.symbol_table.cells = self.captured_and_assigned(&function.body);
+        if let Some(name) = name {
+            self.symbol_table.define_function_name(name);
+        }
+        for parameter in function.params.iter().chain(&function.rest) {
+            let name = self.name(parameter);
+            let symbol = self.symbol_table.define(&name);
+            if symbol.cell {
+                self.emit(Opcode::GetLocal, &[symbol.index]);
+                self.emit(Opcode::NewCell, &[]);
+                self.emit(Opcode::SetLocal, &[symbol.index]);
+            }
+        }
+        let body = self.compile_block(&function.body);
+        if let Err(err) = body {
+            self.leave_scope();
+            return Err(err);
+        }
+        if self.last_instruction_is(Opcode::Pop) {
+            self.replace_last_pop_with_return();
+        }
+        if !self.last_instruction_is(Opcode::ReturnValue) {
+            self.emit(Opcode::Return, &[]);
+        }
+
+        let num_locals = self.symbol_table.num_definitions;
+        let (instructions, free_symbols) = self.leave_scope();
+        // Captured as they are, so cells stay shared.
+        for symbol in &free_symbols {
+            self.get_symbol(symbol);
+        }
+        let compiled = CompiledFunction {
+            instructions,
+            num_locals,
+            num_parameters: function.params.len(),
+            rest: function.rest.is_some(),
+        };
+        let index = self.add_constant(Object::CompiledFunction(Rc::new(compiled)));
+        self.emit(Opcode::Closure, &[index, free_symbols.len()]);
+        Ok(index)
+    }
+
+    /// The names in `body` that closures made in it capture and that are assigned there or
+    /// in those closures. Their locals are held in cells, so an assignment is seen by the
+    /// function and its closures alike.
+    fn captured_and_assigned(&self, body: &Block) -> BTreeSet<String> {
+        let mut scan = Captures::default();
+        scan.visit_block(body);
+        let assigned: BTreeSet<String> = scan.assigned.iter().map(|name| self.name(name)).collect();
+        (scan.captured.iter())
+            .map(|name| self.name(name))
+            .filter(|name| assigned.contains(name))
+            .collect()
+    }
+
+    /// Binds `symbol` to the value on top of the stack, in a new cell if it is held in one.
+    fn bind_symbol(&mut self, symbol: &Symbol) {
+        if symbol.cell {
+            self.emit(Opcode::NewCell, &[]);
+        }
+        match symbol.scope {
+            SymbolScope::Global => self.emit(Opcode::SetGlobal, &[symbol.index]),
+            _ => self.emit(Opcode::SetLocal, &[symbol.index]),
+        };
+    }
+
+    /// Pushes the value of `symbol`.
+    fn load_symbol(&mut self, symbol: &Symbol) {
+        self.get_symbol(symbol);
+        if symbol.cell {
+            self.emit(Opcode::Deref, &[]);
+        }
+    }
+
+    /// Pushes what the slot of `symbol` holds, which is a cell if it is held in one.
+    fn get_symbol(&mut self, symbol: &Symbol) {
+        match symbol.scope {
+            SymbolScope::Global => self.emit(Opcode::GetGlobal, &[symbol.index]),
+            SymbolScope::Local => self.emit(Opcode::GetLocal, &[symbol.index]),
+            SymbolScope::Builtin => self.emit(Opcode::GetBuiltin, &[symbol.index]),
+            SymbolScope::Free => self.emit(Opcode::GetFree, &[symbol.index]),
+            SymbolScope::Function => self.emit(Opcode::CurrentClosure, &[]),
+        };
+    }
+
+    /// Adds `object` to the constant pool, reusing an existing slot when an identical
+    /// literal or function body was compiled before.
+    fn add_constant(&mut self, object: Object) -> usize {
+        let existing = self
+            .constants
+            .iter()
+            .position(|constant| same_constant(constant, &object));
+        match existing {
+            Some(index) => index,
+            None => {
+                self.constants.push(object);
+                self.constants.len() - 1
+            }
+        }
+    }
+
+    fn scope(&self) -> &CompilationScope {
+        self.scopes.last().expect("compiler always has a scope")
+    }
+
+    fn scope_mut(&mut self) -> &mut CompilationScope {
+        self.scopes.last_mut().expect("compiler always has a scope")
+    }
+
+    fn emit(&mut self, opcode: Opcode, operands: &[usize]) -> usize {
+        let instruction = make(opcode, operands);
+        let scope = self.scope_mut();
+        let position = scope.instructions.len();
+        scope.instructions.extend_from_slice(&instruction);
+        scope.previous = scope.last.take();
+        scope.last = Some(EmittedInstruction { opcode, position });
+        position
+    }
+
+    fn last_instruction_is(&self, opcode: Opcode) -> bool {
+        matches!(self.scope().last, Some(last) if last.opcode == opcode)
+    }
+
+    fn remove_last_pop(&mut self) {
+        let scope = self.scope_mut();
+        if let Some(last) = scope.last {
+            scope.instructions.truncate(last.position);
+            scope.last = scope.previous.take();
+        }
+    }
+
+    fn replace_last_pop_with_return(&mut self) {
+        let scope = self.scope_mut();
+        if let Some(last) = scope.last.as_mut() {
+            scope.instructions[last.position] = Opcode::ReturnValue as u8;
+            last.opcode = Opcode::ReturnValue;
+        }
+    }
+
+    fn replace_instruction(&mut self, position: usize, instruction: Instructions) {
+        let instructions = &mut self.scope_mut().instructions;
+        instructions[position..position + instruction.len()].copy_from_slice(&instruction);
+    }
+
+    fn change_operand(&mut self, position: usize, operand: usize) {
+        let instructions = &self.scope().instructions;
+        let opcode = Opcode::from_byte(instructions[position])
+            .expect("patched instruction was emitted by the compiler");
+        // Only the first operand changes.
+        let (mut operands, _) = read_operands(opcode, &instructions[position + 1..]);
+        operands[0] = operand;
+        let operands = &operands[..opcode.operand_widths().len()];
+        self.replace_instruction(position, make(opcode, operands));
+    }
+
+    fn enter_scope(&mut self) {
+        self.scopes.push(CompilationScope::default());
+        let outer = mem::take(&mut self.symbol_table);
+        self.symbol_table = SymbolTable::new_enclosed(outer);
+    }
+
+    fn leave_scope(&mut self) -> (Instructions, Vec<Symbol>) {
+        let scope = self.scopes.pop().expect("compiler always has a scope");
+        let outer = self
+            .symbol_table
+            .outer
+            .take()
+            .expect("leave_scope is paired with enter_scope");
+        let inner = mem::replace(&mut self.symbol_table, *outer);
+        (scope.instructions, inner.free_symbols)
+    }
+}
+
+/// Names a block assigns to, and names functions nested in it use.
+#[derive(Default)]
+struct Captures<'a> {
+    assigned: Vec<&'a Identifier>,
+    captured: Vec<&'a Identifier>,
+    /// Functions being walked inside the block.
+    nested: usize,
+}
+
+impl<'a> Captures<'a> {
+    fn visit_block(&mut self, block: &'a Block) {
+        for statement in &block.0 {
+            self.visit_statement(statement);
+        }
+    }
+
+    fn visit_statement(&mut self, statement: &'a Statement) {
+        match statement {
+            Statement::Let { expression, .. }
+            | Statement::Return(expression)
+            | Statement::Expression(expression) => self.visit_expression(expression),
+            // Compiled as a closure, so what it uses is captured.
+            Statement::Defer(expression) => {
+                self.nested += 1;
+                self.visit_expression(expression);
+                self.nested -= 1;
+            }
+            Statement::Block(block) => self.visit_block(block),
+            Statement::While { condition, body } => {
+                self.visit_expression(condition);
+                self.visit_block(body);
+            }
+            Statement::For { iterable, body, .. } => {
+                self.visit_expression(iterable);
+                self.visit_block(body);
+            }
+            Statement::Break | Statement::Continue => {}
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &'a Expression) {
+        match expression {
+            Expression::Identifier(identifier) if self.nested > 0 => self.captured.push(identifier),
+            Expression::Identifier(_) | Expression::Literal(_) => {}
+            Expression::Assign(identifier, value) => {
+                self.assigned.push(identifier);
+                if self.nested > 0 {
+                    self.captured.push(identifier);
+                }
+                self.visit_expression(value);
+            }
+            Expression::Function(function) | Expression::Macro(function) => {
+                self.nested += 1;
+                self.visit_block(&function.body);
+                self.nested -= 1;
+            }
+            Expression::Prefix(prefix) => self.visit_expression(&prefix.expression),
+            Expression::Infix(infix) => {
+                // Down the left of a chain in a loop, as the compiler does.
+                let mut infix = infix;
+                loop {
+                    self.visit_expression(&infix.right_expression);
+                    match &*infix.left_expression {
+                        Expression::Infix(left) => infix = left,
+                        left => break self.visit_expression(left),
+                    }
+                }
+            }
+            Expression::If(if_expression) => {
+                self.visit_expression(&if_expression.condition);
+                self.visit_block(&if_expression.consequence);
+                if let Some(alternative) = &if_expression.alternative {
+                    self.visit_block(alternative);
+                }
+            }
+            Expression::Call(call) => {
+                self.visit_expression(&call.function);
+                for argument in &call.arguments {
+                    self.visit_expression(argument);
+                }
+            }
+            Expression::Array(elements) => {
+                for element in elements {
+                    self.visit_expression(element);
+                }
+            }
+            Expression::Comprehension(comprehension) => {
+                self.visit_expression(&comprehension.iterable);
+                if let Some(condition) = &comprehension.condition {
+                    self.visit_expression(condition);
+                }
+                self.visit_expression(&comprehension.element);
+            }
+            Expression::Index(index) => {
+                self.visit_expression(&index.left);
+                self.visit_expression(&index.index);
+            }
+            Expression::Hash(pairs) => {
+                for (key, value) in pairs {
+                    self.visit_expression(key);
+                    self.visit_expression(value);
+                }
+            }
+        }
+    }
+}
+
+/// The most nodes the body of a function [`Compiler::compile_inlined`] inlines may have.
+const INLINE_SIZE: usize = 12;
+
+/// The expression the body of `function` consists of, if it is small enough to inline,
+/// with the steps evaluating it takes in order: reading an identifier, or `None` for
+/// anything else that could fail or have an effect.
+fn inline_body(function: &Function) -> Option<(&Expression, Vec<Option<&Identifier>>)> {
+    if function.rest.is_some() {
+        return None;
+    }
+    let [Statement::Expression(body) | Statement::Return(body)] = function.body.0.as_slice() else {
+        return None;
+    };
+    let mut steps = vec![];
+    let mut size = 0;
+    simple(body, &mut steps, &mut size).then_some((body, steps))
+}
+
+/// Whether `expression` only computes a value out of names, literals and calls, without
+/// branching, binding or defining functions, in no more than [`INLINE_SIZE`] nodes.
+fn simple<'a>(
+    expression: &'a Expression,
+    steps: &mut Vec<Option<&'a Identifier>>,
+    size: &mut usize,
+) -> bool {
+    *size += 1;
+    if *size > INLINE_SIZE {
+        return false;
+    }
+    let operands = match expression {
+        Expression::Literal(_) => return true,
+        Expression::Identifier(identifier) => {
+            steps.push(Some(identifier));
+            return true;
+        }
+        Expression::Prefix(prefix) => simple(&prefix.expression, steps, size),
+        Expression::Infix(infix) => {
+            simple(&infix.left_expression, steps, size)
+                && simple(&infix.right_expression, steps, size)
+        }
+        Expression::Index(index) => {
+            simple(&index.left, steps, size) && simple(&index.index, steps, size)
+        }
+        Expression::Call(call) => {
+            simple(&call.function, steps, size)
+                && (call.arguments.iter()).all(|argument| simple(argument, steps, size))
+        }
+        Expression::Array(elements) => elements.iter().all(|element| simple(element, steps, size)),
+        Expression::Hash(pairs) => pairs
+            .iter()
+            .all(|(key, value)| simple(key, steps, size) && simple(value, steps, size)),
+        _ => false,
+    };
+    steps.push(None);
+    operands
+}
+
+fn same_constant(a: &Object, b: &Object) -> bool {
+    match (a, b) {
+        (Object::Int(a), Object::Int(b)) => a == b,
+        // Bitwise, so `0.0` and `-0.0` stay apart and NaN matches itself.
+        (Object::Float(a), Object::Float(b)) => a.to_bits() == b.to_bits(),
+        (Object::String(a), Object::String(b)) => a == b,
+        (Object::CompiledFunction(a), Object::CompiledFunction(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod compiler_tests {
+    use super::{Compiler, SymbolScope, SymbolTable};
+    use crate::{
+        code::{disassemble, make, Opcode},
+        lexer::Lexer,
+        object::Object,
+        parser::Parser,
+        prelude::*,
+    };
+
+    fn compile(text: &str) -> Compiler {
+        let statements: Vec<_> = Parser::new(Lexer::new(text).tokens())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let mut compiler = Compiler::new();
+        compiler.compile(&statements).unwrap();
+        compiler
+    }
+
+    fn assert_instructions(text: &str, expected: &[Vec<u8>]) {
+        let bytecode = compile(text).bytecode();
+        assert_eq!(
+            disassemble(&bytecode.instructions),
+            disassemble(&expected.concat()),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn arithmetic_and_conditionals() {
+        assert_instructions(
+            "1 + 2",
+            &[
+                make(Opcode::Constant, &[0]),
+                make(Opcode::Constant, &[1]),
+                make(Opcode::Add, &[]),
+                make(Opcode::Pop, &[]),
+            ],
+        );
+        assert_instructions(
+            "if (true) { 10 }; 3333;",
+            &[
+                make(Opcode::True, &[]),
+                make(Opcode::JumpNotTruthy, &[10]),
+                make(Opcode::Constant, &[0]),
+                make(Opcode::Jump, &[11]),
+                make(Opcode::Null, &[]),
+                make(Opcode::Pop, &[]),
+                make(Opcode::Constant, &[1]),
+                make(Opcode::Pop, &[]),
+            ],
+        );
+        assert_instructions(
+            "let one = 1; one;",
+            &[
+                make(Opcode::Constant, &[0]),
+                make(Opcode::SetGlobal, &[0]),
+                make(Opcode::Null, &[]),
+                make(Opcode::Pop, &[]),
+                make(Opcode::GetGlobal, &[0]),
+                make(Opcode::Pop, &[]),
+            ],
+        );
+    }
+
+    #[test]
+    fn identical_constants_are_shared() {
+        let compiler = compile(r#"1 + 1.5 + 1 + 1.5; "a" + "a""#);
+        assert_eq!(compiler.constants.len(), 3);
+
+        let compiler = compile("[fn(x) { x * 2 }, fn(y) { y * 2 }, fn(x) { x + 2 }]");
+        assert_eq!(compiler.constants.len(), 3);
+        assert_instructions(
+            "[fn(x) { x }, fn(x) { x }]",
+            &[
+                make(Opcode::Closure, &[0, 0]),
+                make(Opcode::Closure, &[0, 0]),
+                make(Opcode::Array, &[2]),
+                make(Opcode::Pop, &[]),
+            ],
+        );
+    }
+
+    #[test]
+    fn closures() {
+        let compiler = compile("fn(a) { fn(b) { a + b } }");
+        let Object::CompiledFunction(inner) = &compiler.constants[0] else {
+            panic!("expected a compiled function");
+        };
+        assert_eq!(
+            disassemble(&inner.instructions),
+            disassemble(
+                &[
+                    make(Opcode::GetFree, &[0]),
+                    make(Opcode::GetLocal, &[0]),
+                    make(Opcode::Add, &[]),
+                    make(Opcode::ReturnValue, &[]),
+                ]
+                .concat()
+            )
+        );
+        let Object::CompiledFunction(outer) = &compiler.constants[1] else {
+            panic!("expected a compiled function");
+        };
+        assert_eq!(
+            disassemble(&outer.instructions),
+            disassemble(
+                &[
+                    make(Opcode::GetLocal, &[0]),
+                    make(Opcode::Closure, &[0, 1]),
+                    make(Opcode::ReturnValue, &[]),
+                ]
+                .concat()
+            )
+        );
+    }
+
+    #[test]
+    fn symbol_resolution() {
+        let mut global = SymbolTable::default();
+        global.define("a");
+        let mut local = SymbolTable::new_enclosed(global);
+        local.define("b");
+        let mut nested = SymbolTable::new_enclosed(local);
+        nested.define("c");
+
+        assert_eq!(nested.resolve("a").unwrap().scope, SymbolScope::Global);
+        assert_eq!(nested.resolve("c").unwrap().scope, SymbolScope::Local);
+        let free = nested.resolve("b").unwrap();
+        assert_eq!((free.scope, free.index), (SymbolScope::Free, 0));
+        assert_eq!(nested.free_symbols[0].scope, SymbolScope::Local);
+        assert!(nested.resolve("d").is_none());
+    }
+}
@@ -0,0 +1,72 @@
+use std::{cell::RefCell, fs, path::Path, rc::Rc};
+
+use anyhow::{Context, Result};
+
+use crate::{
+    environment::Environment,
+    eval::{Engine, Program},
+    lexer::Lexer,
+    parser::Parser,
+    runtime::{Capture, Output, Runtime},
+};
+
+/// One `NAME.mk` program of a conformance directory and what it produced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseResult {
+    pub name: String,
+    /// Contents of `NAME.out`.
+    pub expected: String,
+    pub actual: String,
+}
+
+impl CaseResult {
+    pub fn passed(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+
+/// Runs every `*.mk` program in `dir` on `engine`, in file name order, each in a fresh
+/// environment, and compares its output with the `.out` file next to it.
+pub fn run(dir: &Path, engine: Engine) -> Result<Vec<CaseResult>> {
+    let mut programs = vec![];
+    for entry in fs::read_dir(dir).with_context(|| format!("reading {}", dir.display()))? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "mk") {
+            programs.push(path);
+        }
+    }
+    programs.sort();
+
+    programs
+        .into_iter()
+        .map(|path| {
+            let source =
+                fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+            let expected_path = path.with_extension("out");
+            let expected = fs::read_to_string(&expected_path)
+                .with_context(|| format!("reading {}", expected_path.display()))?;
+            let name = path.file_stem().unwrap_or_default().to_string_lossy();
+            Ok(CaseResult {
+                name: name.into_owned(),
+                expected: expected.trim_end().to_string(),
+                actual: output(&source, engine),
+            })
+        })
+        .collect()
+}
+
+/// What the program printed, then its final value, or `error: ` and the root cause, which
+/// unlike the full message does not depend on whether the engine tracks source positions.
+pub fn output(source: &str, engine: Engine) -> String {
+    let mut parser = Parser::new(Lexer::new(source).tokens());
+    let printed = Capture::default();
+    let env = Environment::with_runtime(Runtime {
+        output: Output::new(printed.clone()),
+        ..Default::default()
+    });
+    let result = match Program::with_engine(engine).eval(&mut parser, Rc::new(RefCell::new(env))) {
+        Ok(value) => value.to_string(),
+        Err(err) => format!("error: {}", err.root_cause()),
+    };
+    printed.contents() + &result
+}
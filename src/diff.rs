@@ -0,0 +1,134 @@
+use core::fmt::Write;
+
+use crate::{
+    object::{HashKey, Object},
+    prelude::*,
+};
+
+/// Differences reported before the rest are summarised in one line.
+const MAX_DIFFERENCES: usize = 20;
+
+/// Describes how `actual` differs from `expected`, one line per changed, missing or
+/// unexpected element, each prefixed with its path such as `[2]["name"]`. Arrays and hashes
+/// are compared element by element; any other pair of values is compared whole.
+pub fn diff(expected: &Object, actual: &Object) -> Vec<String> {
+    let mut lines = vec![];
+    walk(&mut String::new(), expected, actual, &mut lines);
+    if lines.len() > MAX_DIFFERENCES {
+        let hidden = lines.len() - MAX_DIFFERENCES;
+        lines.truncate(MAX_DIFFERENCES);
+        lines.push(format!("... and {hidden} more"));
+    }
+    lines
+}
+
+fn walk(path: &mut String, expected: &Object, actual: &Object, lines: &mut Vec<String>) {
+    let len = path.len();
+    match (expected, actual) {
+        (Object::Array(expected), Object::Array(actual)) => {
+            for index in 0..expected.len().max(actual.len()) {
+                let _ = write!(path, "[{index}]");
+                match (expected.get(index), actual.get(index)) {
+                    (Some(expected), Some(actual)) => walk(path, expected, actual, lines),
+                    (Some(expected), None) => lines.push(format!("{path}: missing {expected}")),
+                    (None, Some(actual)) => lines.push(format!("{path}: unexpected {actual}")),
+                    (None, None) => unreachable!(),
+                }
+                path.truncate(len);
+            }
+        }
+        (Object::Hash(expected), Object::Hash(actual)) => {
+            let keys = expected
+                .keys()
+                .chain(actual.keys().filter(|key| !expected.contains_key(key)));
+            for key in keys {
+                write_key(path, key);
+                match (expected.get(key), actual.get(key)) {
+                    (Some(expected), Some(actual)) => walk(path, expected, actual, lines),
+                    (Some(expected), None) => lines.push(format!("{path}: missing {expected}")),
+                    (None, Some(actual)) => lines.push(format!("{path}: unexpected {actual}")),
+                    (None, None) => unreachable!(),
+                }
+                path.truncate(len);
+            }
+        }
+        (expected, actual) if expected != actual => {
+            let at = if path.is_empty() { "" } else { ": " };
+            lines.push(format!("{path}{at}expected {expected}, got {actual}"));
+        }
+        _ => {}
+    }
+}
+
+fn write_key(path: &mut String, key: &HashKey) {
+    let _ = match key {
+        HashKey::String(key) => write!(path, "[{key:?}]"),
+        key => write!(path, "[{key}]"),
+    };
+}
+
+#[cfg(test)]
+mod diff_tests {
+    use super::diff;
+    use crate::{
+        environment::Environment, eval::Program, lexer::Lexer, object::Object, parser::Parser,
+        prelude::*,
+    };
+    use alloc::rc::Rc;
+    use core::cell::RefCell;
+
+    fn value(text: &str) -> Object {
+        let mut parser = Parser::new(Lexer::new(text).tokens());
+        let env = Rc::new(RefCell::new(Environment::default()));
+        Program::default().eval(&mut parser, env).unwrap()
+    }
+
+    #[test]
+    fn reports_changed_missing_and_unexpected_elements() {
+        let expected = value(r#"[1, 2, {"name": "a", "tags": [1]}, 4]"#);
+        let actual = value(r#"[1, 3, {"name": "b", "tags": [1, 2], "id": 7}]"#);
+        assert_eq!(
+            diff(&expected, &actual),
+            vec![
+                "[1]: expected 2, got 3",
+                "[2][\"name\"]: expected a, got b",
+                "[2][\"tags\"][1]: unexpected 2",
+                "[2][\"id\"]: unexpected 7",
+                "[3]: missing 4",
+            ]
+        );
+        assert_eq!(
+            diff(&value("5"), &value("true")),
+            vec!["expected 5, got true"]
+        );
+        assert!(diff(&value("[1, [2]]"), &value("[1, [2]]")).is_empty());
+    }
+
+    #[test]
+    fn long_diffs_are_truncated() {
+        let numbers: Vec<String> = (1..=22).map(|n| n.to_string()).collect();
+        let lines = diff(&value("[]"), &value(&format!("[{}]", numbers.join(", "))));
+        assert_eq!(lines.len(), 21);
+        assert_eq!(lines[20], "... and 2 more");
+    }
+
+    #[test]
+    fn assert_eq_reports_the_diff() {
+        let assert_eq = crate::builtins::lookup("assert_eq").unwrap().func;
+        let env = Rc::new(RefCell::new(Environment::default()));
+        let failure = |actual: &str, expected: &str| {
+            assert_eq(vec![value(actual), value(expected)], &env)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            assert_eq(vec![value("[1, 2]"), value("[1, 2]")], &env).unwrap(),
+            Object::Nil
+        );
+        assert_eq!(failure("1", "2"), "assert_eq failed: expected 2, got 1");
+        assert_eq!(
+            failure("[1, 2, 3]", "[0, 2]"),
+            "assert_eq failed:\n  [0]: expected 0, got 1\n  [2]: unexpected 3"
+        );
+    }
+}
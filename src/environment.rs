@@ -1,24 +1,101 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use alloc::{collections::BTreeMap, rc::Rc};
+use core::cell::RefCell;
 
-use crate::object::Object;
+use crate::{
+    ast::Expression,
+    eval::EvalError,
+    object::Object,
+    prelude::*,
+    runtime::{Capabilities, Runtime},
+};
 
 #[derive(Debug, Default)]
 pub struct Environment {
-    pub store: HashMap<String, Object>,
+    pub store: BTreeMap<Rc<str>, Object>,
     pub outer: Option<GlobalEnv>,
+    pub runtime: Rc<Runtime>,
+    /// Deferred expressions of this frame, each with the scope it was deferred in.
+    pub deferred: Vec<(Expression, GlobalEnv)>,
+    /// Where bindings of this environment holding nil got it, with
+    /// [`Runtime::trace_nil`] set.
+    pub nil_origins: BTreeMap<Rc<str>, Rc<str>>,
+    /// Set on [`Environment::child`] roots: bindings past this point can be read but not
+    /// reassigned.
+    pub isolated: bool,
 }
 
 pub type GlobalEnv = Rc<RefCell<Environment>>;
 
 impl Environment {
-    pub fn new_enclosed(outer: GlobalEnv, store: HashMap<String, Object>) -> GlobalEnv {
+    /// A top-level environment, starting with the bindings saved in the runtime's store.
+    pub fn with_runtime(runtime: Runtime) -> Self {
+        #[cfg(feature = "std")]
+        let store = runtime
+            .store
+            .iter()
+            .flat_map(|store| store.bindings())
+            .map(|(name, value)| (runtime.intern_identifier(name), value.clone()))
+            .collect();
+        #[cfg(not(feature = "std"))]
+        let store = BTreeMap::new();
+        Environment {
+            store,
+            outer: None,
+            runtime: Rc::new(runtime),
+            deferred: vec![],
+            nil_origins: BTreeMap::new(),
+            isolated: false,
+        }
+    }
+
+    /// A child interpreter's globals: it reads `prelude` but cannot reassign its bindings,
+    /// its own `let`s stay in the child, and it runs on a clone of the prelude's runtime
+    /// whose builtins run with `capabilities`. Children
+    /// share nothing mutable with each other, so a host can run many plugins off one prelude.
+    ///
+    /// Prelude bindings are resolved by the tree-walking evaluator only; the VM sees the
+    /// child's globals.
+    pub fn child(prelude: &GlobalEnv, capabilities: Capabilities) -> GlobalEnv {
+        let runtime = Runtime {
+            capabilities,
+            ..Runtime::clone(&prelude.borrow().runtime)
+        };
         Rc::new(RefCell::new(Environment {
+            store: BTreeMap::new(),
+            outer: Some(prelude.clone()),
+            runtime: Rc::new(runtime),
+            deferred: vec![],
+            nil_origins: BTreeMap::new(),
+            isolated: true,
+        }))
+    }
+
+    pub fn new_enclosed(outer: GlobalEnv, store: BTreeMap<Rc<str>, Object>) -> GlobalEnv {
+        let runtime = outer.borrow().runtime.clone();
+        Self::new_frame(outer, store, runtime)
+    }
+
+    /// A call frame under `outer` that runs on the caller's `runtime`, which can differ
+    /// from the one the function was defined in when a child calls into its prelude.
+    pub fn new_frame(
+        outer: GlobalEnv,
+        store: BTreeMap<Rc<str>, Object>,
+        runtime: Rc<Runtime>,
+    ) -> GlobalEnv {
+        let frame = Rc::new(RefCell::new(Environment {
             store,
             outer: Some(outer),
-        }))
+            runtime: runtime.clone(),
+            deferred: vec![],
+            nil_origins: BTreeMap::new(),
+            isolated: false,
+        }));
+        runtime.track(&frame);
+        frame
     }
 
     pub fn get(&self, name: &str) -> Option<Object> {
+        let name = &*self.runtime.identifier(name);
         if let Some(obj) = self.store.get(name) {
             return Some(obj.clone());
         } else if let Some(outer) = self.outer.clone() {
@@ -27,7 +104,79 @@ impl Environment {
         None
     }
 
-    pub fn set(&mut self, name: String, val: &Object) {
-        self.store.insert(name, val.clone());
+    /// Rebinds `name` in the innermost environment that defines it.
+    pub fn assign(&mut self, name: &str, val: Object) -> Result<(), EvalError> {
+        let name = &*self.runtime.identifier(name);
+        if let Some(slot) = self.store.get_mut(name) {
+            *slot = val;
+            return Ok(());
+        }
+        match &self.outer {
+            Some(outer) if self.isolated => match outer.borrow().get(name) {
+                Some(_) => Err(EvalError::ReadOnlyBinding(name.to_string())),
+                None => Err(EvalError::UndeclaredAssignment(name.to_string())),
+            },
+            Some(outer) => outer.borrow_mut().assign(name, val),
+            None => Err(EvalError::UndeclaredAssignment(name.to_string())),
+        }
+    }
+
+    /// Writes this environment's bindings to the runtime's store, if it has one.
+    #[cfg(feature = "std")]
+    pub fn sync_store(&self) -> anyhow::Result<()> {
+        match &self.runtime.store {
+            Some(store) => store.sync(self.store.iter().map(|(name, value)| (&**name, value))),
+            None => Ok(()),
+        }
+    }
+
+    /// Where the nil bound to `name` came from, if it is traced.
+    pub fn nil_origin(&self, name: &str) -> Option<Rc<str>> {
+        let name = &*self.runtime.identifier(name);
+        if self.store.contains_key(name) {
+            return self.nil_origins.get(name).cloned();
+        }
+        self.outer.as_ref()?.borrow().nil_origin(name)
+    }
+
+    /// Records where the binding `name` resolves to got its nil, or forgets it for `None`.
+    pub fn set_nil_origin(&mut self, name: &str, origin: Option<Rc<str>>) {
+        let name = &*self.runtime.identifier(name);
+        match &self.outer {
+            Some(outer) if !self.store.contains_key(name) => {
+                outer.borrow_mut().set_nil_origin(name, origin)
+            }
+            _ => match origin {
+                Some(origin) => {
+                    let name = self.runtime.intern_identifier(name);
+                    self.nil_origins.insert(name, origin);
+                }
+                None => {
+                    self.nil_origins.remove(name);
+                }
+            },
+        }
+    }
+
+    /// This environment's own bindings, in name order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Object)> {
+        self.store.iter().map(|(name, value)| (&**name, value))
+    }
+
+    /// The names bound in this environment, in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.store.keys().map(|name| &**name)
+    }
+
+    /// Drops every binding and pending deferred expression, keeping the runtime.
+    pub fn clear(&mut self) {
+        self.store.clear();
+        self.nil_origins.clear();
+        self.deferred.clear();
+    }
+
+    pub fn set(&mut self, name: String, val: Object) {
+        let name = self.runtime.intern_identifier(&name);
+        self.store.insert(name, val);
     }
 }
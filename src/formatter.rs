@@ -0,0 +1,572 @@
+use core::fmt::Write;
+
+use anyhow::{bail, Result};
+
+use crate::{
+    ast::{Block, Expression, Function, InfixOperation, Literal, Statement},
+    lexer::Lexer,
+    parser::Parser,
+    prelude::*,
+    token::{TokenType, INFIX_OPERATORS},
+};
+
+/// How tightly a prefix operator binds its operand, as in the parser.
+const PREFIX: usize = 6;
+/// Identifiers, literals and anything else that never needs parentheses.
+const ATOM: usize = 9;
+
+/// How [`format_with`] lays out a program.
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    /// Spaces per level of block nesting.
+    pub indent: usize,
+    /// `a + b` rather than `a+b`.
+    pub operator_spaces: bool,
+    /// End let, return, defer and expression statements with `;`. Without it a `;` is still
+    /// written where the next line would otherwise continue the statement, as in `f` followed
+    /// by `(1)`.
+    pub semicolons: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent: 4,
+            operator_spaces: true,
+            semicolons: true,
+        }
+    }
+}
+
+/// Formats `source` with the default options.
+pub fn format_source(source: &str) -> Result<String> {
+    format_with(source, &FormatOptions::default())
+}
+
+/// Parses `source` and prints it back in canonical layout: one statement per line, blocks
+/// indented, and only the parentheses the grouping needs. Comments are not kept.
+pub fn format_with(source: &str, options: &FormatOptions) -> Result<String> {
+    let mut parser = Parser::new(Lexer::new(source).tokens());
+    let statements: Vec<Statement> = parser.by_ref().flatten().collect();
+    if let Some(err) = parser.errors().first() {
+        bail!("{err}");
+    }
+    Ok(format_statements(&statements, options))
+}
+
+/// Prints `statements` as source, one per line.
+pub fn format_statements(statements: &[Statement], options: &FormatOptions) -> String {
+    let mut printer = Printer {
+        options,
+        out: String::new(),
+        depth: 0,
+    };
+    printer.statements(statements);
+    printer.out
+}
+
+struct Printer<'a> {
+    options: &'a FormatOptions,
+    out: String,
+    depth: usize,
+}
+
+impl Printer<'_> {
+    fn statements(&mut self, statements: &[Statement]) {
+        // Where the previous statement ended without a `;`, if it ended in an expression.
+        let mut open = None;
+        for statement in flatten(statements) {
+            let indent = self.depth * self.options.indent;
+            let _ = write!(self.out, "{:indent$}", "");
+            let start = self.out.len();
+            let ends_in_expression = self.statement(statement);
+            if let Some(end) = open.take() {
+                if self.out[start..].starts_with(['(', '[', '-']) {
+                    self.out.insert(end, ';');
+                }
+            }
+            if ends_in_expression {
+                match self.options.semicolons {
+                    true => self.out.push(';'),
+                    false => open = Some(self.out.len()),
+                }
+            }
+            self.out.push('\n');
+        }
+    }
+
+    /// Writes `statement` without its `;`, returning whether it takes one.
+    fn statement(&mut self, statement: &Statement) -> bool {
+        match statement {
+            Statement::Let {
+                identifier,
+                expression,
+            } => {
+                let _ = write!(self.out, "let {identifier} = ");
+                self.expression(expression, 0);
+            }
+            Statement::Return(expression) => {
+                self.out.push_str("return ");
+                self.expression(expression, 0);
+            }
+            Statement::Defer(expression) => {
+                self.out.push_str("defer ");
+                self.expression(expression, 0);
+            }
+            Statement::Expression(expression) => self.expression(expression, 0),
+            Statement::While { condition, body } => {
+                self.out.push_str("while (");
+                self.expression(condition, 0);
+                self.out.push_str(") ");
+                self.block(body);
+                return false;
+            }
+            Statement::For {
+                variable,
+                value,
+                iterable,
+                body,
+            } => {
+                let _ = write!(self.out, "for ({variable}");
+                if let Some(value) = value {
+                    let _ = write!(self.out, ", {value}");
+                }
+                self.out.push_str(" in ");
+                self.expression(iterable, 0);
+                self.out.push_str(") ");
+                self.block(body);
+                return false;
+            }
+            Statement::Break => self.out.push_str("break"),
+            Statement::Continue => self.out.push_str("continue"),
+            Statement::Block(_) => unreachable!("blocks are flattened"),
+        }
+        true
+    }
+
+    fn block(&mut self, block: &Block) {
+        if block.0.is_empty() {
+            self.out.push_str("{}");
+            return;
+        }
+        self.out.push_str("{\n");
+        self.depth += 1;
+        self.statements(&block.0);
+        self.depth -= 1;
+        let indent = self.depth * self.options.indent;
+        let _ = write!(self.out, "{:indent$}}}", "");
+    }
+
+    /// Writes `expression`, in parentheses if it binds looser than `min`.
+    fn expression(&mut self, expression: &Expression, min: usize) {
+        if binding(expression) < min {
+            self.out.push('(');
+            self.expression(expression, 0);
+            self.out.push(')');
+            return;
+        }
+        match expression {
+            Expression::Identifier(identifier) => {
+                let _ = write!(self.out, "{identifier}");
+            }
+            Expression::Literal(literal) => {
+                let _ = write!(self.out, "{literal}");
+            }
+            Expression::Prefix(prefix) => {
+                let _ = write!(self.out, "{}", prefix.operation);
+                self.expression(&prefix.expression, PREFIX);
+            }
+            Expression::Infix(infix) => {
+                let precedence = precedence(&infix.operation);
+                self.expression(&infix.left_expression, precedence);
+                let _ = match self.options.operator_spaces {
+                    true => write!(self.out, " {} ", infix.operation),
+                    false => write!(self.out, "{}", infix.operation),
+                };
+                // Operators are left associative, so an equal precedence on the right
+                // needs grouping.
+                self.expression(&infix.right_expression, precedence + 1);
+            }
+            Expression::If(if_expression) => {
+                self.out.push_str("if (");
+                self.expression(&if_expression.condition, 0);
+                self.out.push_str(") ");
+                self.block(&if_expression.consequence);
+                if let Some(alternative) = &if_expression.alternative {
+                    self.out.push_str(" else ");
+                    self.block(alternative);
+                }
+            }
+            Expression::Function(function) => self.function("fn", function),
+            Expression::Macro(function) => self.function("macro", function),
+            Expression::Call(call) => {
+                self.expression(&call.function, TokenType::LParen.precedence());
+                self.list('(', &call.arguments, ')');
+            }
+            Expression::Array(elements) => self.list('[', elements, ']'),
+            Expression::Comprehension(comprehension) => {
+                self.out.push('[');
+                self.expression(&comprehension.element, 0);
+                let variables: Vec<String> =
+                    comprehension.variables().map(|v| v.to_string()).collect();
+                let _ = write!(self.out, " for {} in ", variables.join(", "));
+                self.expression(&comprehension.iterable, 0);
+                if let Some(condition) = &comprehension.condition {
+                    self.out.push_str(" if ");
+                    self.expression(condition, 0);
+                }
+                self.out.push(']');
+            }
+            Expression::Index(index) => {
+                self.expression(&index.left, TokenType::LParen.precedence());
+                self.out.push('[');
+                self.expression(&index.index, 0);
+                self.out.push(']');
+            }
+            Expression::Hash(pairs) => {
+                self.out.push('{');
+                for (position, (key, value)) in pairs.iter().enumerate() {
+                    if position > 0 {
+                        self.out.push_str(", ");
+                    }
+                    self.expression(key, 0);
+                    self.out.push_str(": ");
+                    self.expression(value, 0);
+                }
+                self.out.push('}');
+            }
+            Expression::Assign(name, value) => {
+                let _ = write!(self.out, "{name} = ");
+                self.expression(value, 0);
+            }
+        }
+    }
+
+    fn function(&mut self, keyword: &str, function: &Function) {
+        let _ = write!(self.out, "{keyword}({}) ", function.signature());
+        self.block(&function.body);
+    }
+
+    fn list(&mut self, open: char, elements: &[Expression], close: char) {
+        self.out.push(open);
+        for (position, element) in elements.iter().enumerate() {
+            if position > 0 {
+                self.out.push_str(", ");
+            }
+            self.expression(element, 0);
+        }
+        self.out.push(close);
+    }
+}
+
+/// Statements of nested blocks inline, as blocks don't open a scope.
+fn flatten(statements: &[Statement]) -> Vec<&Statement> {
+    statements
+        .iter()
+        .flat_map(|statement| match statement {
+            Statement::Block(block) => flatten(&block.0),
+            statement => vec![statement],
+        })
+        .collect()
+}
+
+fn binding(expression: &Expression) -> usize {
+    match expression {
+        Expression::Assign(..) => 1,
+        Expression::Infix(infix) => precedence(&infix.operation),
+        Expression::Prefix(_) => PREFIX,
+        Expression::Literal(Literal::Int(int)) if *int < 0 => PREFIX,
+        Expression::Literal(Literal::Float(float)) if float.is_sign_negative() => PREFIX,
+        Expression::Call(_) | Expression::Index(_) => TokenType::LBracket.precedence(),
+        _ => ATOM,
+    }
+}
+
+fn precedence(operation: &InfixOperation) -> usize {
+    INFIX_OPERATORS
+        .iter()
+        .find(|token| token.operation().as_ref() == Some(operation))
+        .map_or(0, TokenType::precedence)
+}
+
+#[cfg(test)]
+mod formatter_tests {
+    use alloc::rc::Rc;
+
+    use proptest::prelude::*;
+
+    use super::{format_source, format_statements, format_with, FormatOptions};
+    use crate::{
+        ast::{
+            Block, Call, Comprehension, Expression, Function, If, Index, Infix, InfixOperation,
+            Literal, Prefix, PrefixOperation, Statement,
+        },
+        lexer::Lexer,
+        parser::Parser,
+        prelude::*,
+        token::{Identifier, Int},
+    };
+
+    #[test]
+    fn formats_canonically() {
+        let source = "let add=fn(a,b){a+b} // sum\n\
+                      let x = ((1 + 2)) * -(3 - 4) - (5 - 6);\
+                      if(add(x,1)>2){puts(\"big\");[x,{\"k\":x[0]}]}else{}\n\
+                      while (x < 10) { x = x + 1 } for(k ,v in {}){k} defer f(g)(h);\n\
+                      [ x*2 for x  in xs if x>1 ];[k for k,v in {}]";
+        let formatted = format_source(source).unwrap();
+        assert_eq!(
+            formatted,
+            "let add = fn(a, b) {\n    a + b;\n};\n\
+             let x = (1 + 2) * -(3 - 4) - (5 - 6);\n\
+             if (add(x, 1) > 2) {\n    puts(\"big\");\n    [x, {\"k\": x[0]}];\n} else {};\n\
+             while (x < 10) {\n    x = x + 1;\n}\n\
+             for (k, v in {}) {\n    k;\n}\n\
+             defer f(g)(h);\n\
+             [x * 2 for x in xs if x > 1];\n\
+             [k for k, v in {}];\n"
+        );
+        assert_eq!(format_source(&formatted).unwrap(), formatted);
+
+        let statements = |source: &str| -> Vec<_> {
+            Parser::new(Lexer::new(source).tokens())
+                .map(Result::unwrap)
+                .collect()
+        };
+        assert_eq!(statements(&formatted), statements(source));
+    }
+
+    #[test]
+    fn options() {
+        let options = FormatOptions {
+            indent: 2,
+            operator_spaces: false,
+            semicolons: false,
+        };
+        let source = "let f = fn(n) { let m = n * (n - 1); m }; f; (1 + 2) * 3; -f(1); [1]";
+        assert_eq!(
+            format_with(source, &options).unwrap(),
+            "let f = fn(n) {\n  let m = n*(n-1)\n  m\n}\nf;\n(1+2)*3;\n-f(1);\n[1]\n"
+        );
+        assert_eq!(
+            format_source("let = 1;").unwrap_err().to_string(),
+            "line 1, column 5: expected identifier, got '=' instead"
+        );
+    }
+
+    fn identifier() -> impl Strategy<Value = Identifier> {
+        prop::sample::select(vec!["a", "b", "x", "total", "f_2"])
+            .prop_map(|name| Identifier(name.to_string()))
+    }
+
+    fn literal() -> impl Strategy<Value = Literal> {
+        prop_oneof![
+            (0..=Int::MAX).prop_map(Literal::Int),
+            any::<f64>()
+                .prop_filter("only finite, positive floats have a literal", |float| {
+                    float.is_finite() && float.is_sign_positive()
+                })
+                .prop_map(Literal::Float),
+            any::<String>().prop_map(Literal::String),
+            Just(Literal::True),
+            Just(Literal::False),
+            Just(Literal::Nil),
+        ]
+    }
+
+    fn infix_operation() -> impl Strategy<Value = InfixOperation> {
+        prop::sample::select(vec![
+            InfixOperation::Add,
+            InfixOperation::Sub,
+            InfixOperation::Eq,
+            InfixOperation::NotEq,
+            InfixOperation::Lt,
+            InfixOperation::Lte,
+            InfixOperation::Gt,
+            InfixOperation::Gte,
+            InfixOperation::Mul,
+            InfixOperation::Div,
+            InfixOperation::Mod,
+        ])
+    }
+
+    /// Whether `expression` printed as a statement would start with `{`, which opens a block.
+    fn starts_with_brace(expression: &Expression) -> bool {
+        match expression {
+            Expression::Hash(_) => true,
+            Expression::Infix(infix) => starts_with_brace(&infix.left_expression),
+            Expression::Call(call) => starts_with_brace(&call.function),
+            Expression::Index(index) => starts_with_brace(&index.left),
+            _ => false,
+        }
+    }
+
+    /// Statements with no loops in them, using `expression`.
+    fn simple_statement(
+        expression: impl Strategy<Value = Expression> + Clone + 'static,
+    ) -> impl Strategy<Value = Statement> + Clone {
+        prop_oneof![
+            (identifier(), expression.clone()).prop_map(|(identifier, expression)| {
+                Statement::Let {
+                    identifier,
+                    expression,
+                }
+            }),
+            expression.clone().prop_map(Statement::Return),
+            expression.clone().prop_map(Statement::Defer),
+            expression
+                .prop_filter("a leading `{` opens a block", |e| !starts_with_brace(e))
+                .prop_map(Statement::Expression),
+            Just(Statement::Break),
+            Just(Statement::Continue),
+        ]
+    }
+
+    fn block(
+        expression: impl Strategy<Value = Expression> + Clone + 'static,
+    ) -> impl Strategy<Value = Block> + Clone {
+        prop::collection::vec(simple_statement(expression), 0..3).prop_map(Block)
+    }
+
+    fn function() -> impl Strategy<Value = (Vec<Identifier>, Option<Identifier>)> {
+        (
+            prop::sample::subsequence(vec!["a", "b", "x"], 0..3),
+            prop::option::of(Just("rest")),
+        )
+            .prop_map(|(params, rest)| {
+                let identifier = |name: &str| Identifier(name.to_string());
+                (
+                    params.into_iter().map(identifier).collect(),
+                    rest.map(identifier),
+                )
+            })
+    }
+
+    /// Expressions of every kind, as the parser would build them.
+    fn expression() -> impl Strategy<Value = Expression> + Clone {
+        let leaf = prop_oneof![
+            identifier().prop_map(Expression::Identifier),
+            literal().prop_map(Expression::Literal),
+        ];
+        leaf.prop_recursive(4, 48, 4, |inner| {
+            let boxed = inner.clone().prop_map(Box::new);
+            prop_oneof![
+                (
+                    prop::sample::select(vec![PrefixOperation::Bang, PrefixOperation::Minus]),
+                    boxed.clone()
+                )
+                    .prop_map(|(operation, expression)| Expression::Prefix(
+                        Prefix {
+                            expression,
+                            operation
+                        }
+                    )),
+                (boxed.clone(), infix_operation(), boxed.clone()).prop_map(
+                    |(left_expression, operation, right_expression)| Expression::Infix(Infix {
+                        left_expression,
+                        right_expression,
+                        operation
+                    })
+                ),
+                (
+                    boxed.clone(),
+                    block(inner.clone()),
+                    prop::option::of(block(inner.clone()))
+                )
+                    .prop_map(|(condition, consequence, alternative)| {
+                        Expression::If(If {
+                            condition,
+                            consequence,
+                            alternative,
+                        })
+                    }),
+                (function(), block(inner.clone()), any::<bool>()).prop_map(
+                    |((params, rest), body, is_macro)| {
+                        let function = Function {
+                            params,
+                            rest,
+                            body: Rc::new(body),
+                        };
+                        match is_macro {
+                            true => Expression::Macro(function),
+                            false => Expression::Function(function),
+                        }
+                    }
+                ),
+                (boxed.clone(), prop::collection::vec(inner.clone(), 0..3)).prop_map(
+                    |(function, arguments)| Expression::Call(Call {
+                        arguments,
+                        function
+                    })
+                ),
+                prop::collection::vec(inner.clone(), 0..3).prop_map(Expression::Array),
+                (
+                    boxed.clone(),
+                    identifier(),
+                    prop::option::of(identifier()),
+                    boxed.clone(),
+                    prop::option::of(boxed.clone())
+                )
+                    .prop_map(|(element, variable, value, iterable, condition)| {
+                        Expression::Comprehension(Comprehension {
+                            element,
+                            variable,
+                            value,
+                            iterable,
+                            condition,
+                        })
+                    }),
+                (boxed.clone(), boxed.clone())
+                    .prop_map(|(left, index)| Expression::Index(Index { left, index })),
+                prop::collection::vec((inner.clone(), inner), 0..3).prop_map(Expression::Hash),
+                (identifier(), boxed).prop_map(|(name, value)| Expression::Assign(name, value)),
+            ]
+        })
+    }
+
+    /// A program of statements of every kind, loops included.
+    fn program() -> impl Strategy<Value = Vec<Statement>> {
+        let expression = expression();
+        let statement = prop_oneof![
+            3 => simple_statement(expression.clone()),
+            1 => (expression.clone(), block(expression.clone()))
+                .prop_map(|(condition, body)| Statement::While { condition, body }),
+            1 => (
+                identifier(),
+                prop::option::of(identifier()),
+                expression.clone(),
+                block(expression)
+            )
+                .prop_map(|(variable, value, iterable, body)| Statement::For {
+                    variable,
+                    value,
+                    iterable,
+                    body
+                }),
+        ];
+        prop::collection::vec(statement, 1..4)
+    }
+
+    fn format_options() -> impl Strategy<Value = FormatOptions> {
+        (0..4usize, any::<bool>(), any::<bool>()).prop_map(
+            |(indent, operator_spaces, semicolons)| FormatOptions {
+                indent,
+                operator_spaces,
+                semicolons,
+            },
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn formatted_programs_parse_back(statements in program(), options in format_options()) {
+            let source = format_statements(&statements, &options);
+            let mut parser = Parser::new(Lexer::new(&source).tokens());
+            let parsed: Vec<Statement> = parser.by_ref().flatten().collect();
+            prop_assert!(parser.errors().is_empty(), "{}: {:?}", source, parser.errors());
+            prop_assert_eq!(&parsed, &statements, "{}", source);
+            // Formatting the printed source again changes nothing.
+            prop_assert_eq!(format_with(&source, &options).unwrap(), source.clone());
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use alloc::{
+    collections::BTreeMap,
+    rc::{Rc, Weak},
+};
+use core::mem;
+
+use crate::{
+    environment::GlobalEnv,
+    object::{Closure, HashKey, Object, Shared},
+    prelude::*,
+    runtime::Runtime,
+};
+
+/// Call frames the runtime creates before its first collection.
+pub const COLLECT_AFTER: usize = 256;
+
+/// Frees call frames that only reference cycles keep alive, such as a frame holding a
+/// closure that captured it. Returns how many frames were freed.
+///
+/// Every environment, array, hash and closure reachable from the frames the runtime
+/// created is visited, counting the references each gets from the others. Anything with
+/// more references than that is held from outside, by the host, a running call or a VM
+/// global, and so is everything it reaches. The stores of the frames left over are
+/// cleared, which breaks their cycles.
+pub fn collect_cycles(runtime: &Runtime) -> usize {
+    let frames: Vec<GlobalEnv> = {
+        let mut tracked = runtime.tracked.borrow_mut();
+        tracked.retain(|frame| frame.strong_count() > 0);
+        tracked.iter().filter_map(Weak::upgrade).collect()
+    };
+    let mut graph = Graph::default();
+    for frame in frames {
+        graph.add(Node::Env(frame));
+    }
+    let mut next = 0;
+    while next < graph.nodes.len() {
+        graph.expand(next);
+        next += 1;
+    }
+
+    let mut live = vec![false; graph.nodes.len()];
+    // The graph holds one reference to each node itself.
+    let mut pending: Vec<usize> = (0..graph.nodes.len())
+        .filter(|&index| {
+            graph.held[index] || graph.nodes[index].strong_count() - 1 > graph.internal[index]
+        })
+        .collect();
+    while let Some(index) = pending.pop() {
+        if !mem::replace(&mut live[index], true) {
+            pending.extend(&graph.edges[index]);
+        }
+    }
+
+    let garbage: Vec<GlobalEnv> = graph
+        .nodes
+        .into_iter()
+        .zip(live)
+        .filter_map(|(node, live)| match node {
+            Node::Env(env) if !live => Some(env),
+            _ => None,
+        })
+        .collect();
+    for env in &garbage {
+        let store = mem::take(&mut env.borrow_mut().store);
+        drop(store);
+    }
+    garbage.len()
+}
+
+/// A reference counted value that can lead to an environment.
+enum Node {
+    Env(GlobalEnv),
+    Array(Shared<Vec<Object>>),
+    Hash(Shared<BTreeMap<HashKey, Object>>),
+    Closure(Rc<Closure>),
+}
+
+impl Node {
+    fn address(&self) -> usize {
+        match self {
+            Node::Env(env) => Rc::as_ptr(env) as *const () as usize,
+            Node::Array(array) => array.as_ptr() as *const () as usize,
+            Node::Hash(hash) => hash.as_ptr() as *const () as usize,
+            Node::Closure(closure) => Rc::as_ptr(closure) as *const () as usize,
+        }
+    }
+
+    fn strong_count(&self) -> usize {
+        match self {
+            Node::Env(env) => Rc::strong_count(env),
+            Node::Array(array) => array.strong_count(),
+            Node::Hash(hash) => hash.strong_count(),
+            Node::Closure(closure) => Rc::strong_count(closure),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Graph {
+    nodes: Vec<Node>,
+    indices: BTreeMap<usize, usize>,
+    edges: Vec<Vec<usize>>,
+    /// References each node gets from other nodes.
+    internal: Vec<usize>,
+    /// Environments borrowed while collecting, which must be in use.
+    held: Vec<bool>,
+}
+
+impl Graph {
+    fn add(&mut self, node: Node) -> usize {
+        let address = node.address();
+        if let Some(&index) = self.indices.get(&address) {
+            return index;
+        }
+        self.nodes.push(node);
+        self.edges.push(vec![]);
+        self.internal.push(0);
+        self.held.push(false);
+        self.indices.insert(address, self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+
+    fn expand(&mut self, index: usize) {
+        let mut children = vec![];
+        match &self.nodes[index] {
+            Node::Env(env) => match env.try_borrow() {
+                Ok(env) => {
+                    children.extend(env.outer.clone().map(Node::Env));
+                    env.store
+                        .values()
+                        .for_each(|value| references(value, &mut children));
+                }
+                Err(_) => {
+                    self.held[index] = true;
+                    return;
+                }
+            },
+            Node::Array(array) => array
+                .iter()
+                .for_each(|value| references(value, &mut children)),
+            Node::Hash(hash) => hash
+                .values()
+                .for_each(|value| references(value, &mut children)),
+            Node::Closure(closure) => closure
+                .free
+                .iter()
+                .for_each(|value| references(value, &mut children)),
+        }
+        for child in children {
+            let child = self.add(child);
+            self.edges[index].push(child);
+            self.internal[child] += 1;
+        }
+    }
+}
+
+/// The nodes `value` refers to directly.
+fn references(value: &Object, nodes: &mut Vec<Node>) {
+    match value {
+        Object::Function(function) | Object::Macro(function) => {
+            nodes.push(Node::Env(function.env.clone()))
+        }
+        Object::Array(array) => nodes.push(Node::Array(array.clone())),
+        Object::Hash(hash) => nodes.push(Node::Hash(hash.clone())),
+        Object::Closure(closure) => nodes.push(Node::Closure(closure.clone())),
+        Object::Return(value) => references(value, nodes),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod gc_tests {
+    use alloc::rc::Rc;
+    use core::cell::RefCell;
+
+    use super::{collect_cycles, COLLECT_AFTER};
+    use crate::{
+        environment::Environment, eval::Program, lexer::Lexer, object::Object, parser::Parser,
+    };
+
+    #[test]
+    fn frames_kept_alive_by_closures_are_freed() {
+        let env = Rc::new(RefCell::new(Environment::default()));
+        let run = |text: &str| {
+            let mut parser = Parser::new(Lexer::new(text).tokens());
+            Program::default().eval(&mut parser, env.clone()).unwrap()
+        };
+        let runtime = env.borrow().runtime.clone();
+        let live = || {
+            let tracked = runtime.tracked.borrow();
+            tracked
+                .iter()
+                .filter(|frame| frame.strong_count() > 0)
+                .count()
+        };
+
+        run("let helper = fn(x) { let inner = fn() { x }; inner() };\n\
+             let counter = fn() { let count = 0; fn() { count = count + 1; count } };\n\
+             let next = counter();\n\
+             let pair = [counter(), {\"next\": counter()}];");
+        run("helper(1); helper(2); helper(3);");
+        // The counters' frames, the helpers' and the pooled frame `inner` ran in.
+        assert_eq!(live(), 7);
+        assert_eq!(collect_cycles(&runtime), 3);
+        assert_eq!(live(), 4);
+        assert_eq!(run("next(); next()"), Object::Int(2));
+        assert_eq!(run("pair[0](); pair[1][\"next\"]()"), Object::Int(1));
+
+        run("let i = 0; while (i < 2000) { helper(i); i = i + 1; }");
+        assert!(runtime.tracked.borrow().len() <= 2 * COLLECT_AFTER);
+        collect_cycles(&runtime);
+        assert_eq!(live(), 4);
+    }
+}
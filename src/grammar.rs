@@ -0,0 +1,172 @@
+use core::fmt::Write;
+
+use crate::{
+    prelude::*,
+    token::{TokenType, INFIX_OPERATORS, KEYWORDS, PREFIX_OPERATORS},
+};
+
+const STATEMENTS: &str = r#"program    = { statement } ;
+statement  = let | return | defer | while | for | break | continue | expression [ ";" ] ;
+let        = "let" binding { "," binding } [ ";" ] ;
+binding    = identifier [ "=" expression ] ;
+return     = "return" expression [ ";" ] ;
+defer      = "defer" expression [ ";" ] ;
+while      = "while" "(" expression ")" block ;
+for        = "for" "(" identifier [ "," identifier ] "in" expression ")" block ;
+break      = "break" [ ";" ] ;
+continue   = "continue" [ ";" ] ;
+block      = "{" { statement } "}" ;
+"#;
+
+const PRIMARY: &str = r#"primary    = identifier | integer | float | string | "true" | "false" | "nil"
+           | "(" expression ")" | array | hash | if | function | macro ;
+array      = "[" [ expression ( "for" identifier [ "," identifier ] "in" expression
+                                [ "if" expression ] | { "," expression } ) ] "]" ;
+hash       = "{" [ pair { "," pair } ] "}" ;
+pair       = expression ":" expression ;
+if         = "if" "(" expression ")" block [ "else" block ] ;
+function   = "fn" "(" [ parameters ] ")" block ;
+macro      = "macro" "(" [ parameters ] ")" block ;
+parameters = identifier { "," identifier } [ "," "..." identifier ] | "..." identifier ;
+identifier = letter { letter | digit | "_" } - keyword ;
+integer    = digits | "0x" hexdigit { [ "_" ] hexdigit } | "0o" octdigit { [ "_" ] octdigit }
+           | "0b" bindigit { [ "_" ] bindigit } ;
+float      = digits ( "." digits [ exponent ] | exponent ) ;
+exponent   = ( "e" | "E" ) [ "+" | "-" ] digits ;
+digits     = digit { [ "_" ] digit } ;
+string     = '"' { character - ( '"' | "\" ) | escape } '"' ;
+escape     = "\" ( "n" | "r" | "t" | '"' | "\" | "u{" hexdigit { hexdigit } "}" ) ;
+comment    = "//" { character - newline } | "/*" { comment | character } "*/" ;
+"#;
+
+/// Infix operators grouped by precedence, loosest binding first.
+fn levels() -> Vec<(usize, Vec<&'static TokenType>)> {
+    let mut levels: Vec<(usize, Vec<&TokenType>)> = vec![];
+    for operator in INFIX_OPERATORS {
+        let precedence = operator.precedence();
+        match levels.iter_mut().find(|(level, _)| *level == precedence) {
+            Some((_, operators)) => operators.push(operator),
+            None => levels.push((precedence, vec![operator])),
+        }
+    }
+    levels.sort_by_key(|(precedence, _)| *precedence);
+    levels
+}
+
+fn alternatives<'a>(tokens: impl IntoIterator<Item = &'a TokenType>) -> String {
+    let quoted: Vec<String> = tokens
+        .into_iter()
+        .map(|token| format!("\"{token}\""))
+        .collect();
+    quoted.join(" | ")
+}
+
+/// The grammar in EBNF, with one rule per precedence level of the parser.
+pub fn ebnf() -> String {
+    let mut out = String::from(STATEMENTS);
+    let levels = levels();
+    let rule = |index: usize| match levels.get(index) {
+        Some((precedence, _)) => format!("binary_{precedence}"),
+        None => "prefix".to_string(),
+    };
+    let _ = writeln!(
+        out,
+        "{:<10} = identifier \"=\" expression | {} ;",
+        "expression",
+        rule(0)
+    );
+    for (index, (_, operators)) in levels.iter().enumerate() {
+        let operand = rule(index + 1);
+        let _ = writeln!(
+            out,
+            "{:<10} = {operand} {{ ( {} ) {operand} }} ;",
+            rule(index),
+            alternatives(operators.iter().copied())
+        );
+    }
+    let _ = writeln!(
+        out,
+        "{:<10} = ( {} ) prefix | postfix ;",
+        "prefix",
+        alternatives(PREFIX_OPERATORS)
+    );
+    let _ = writeln!(
+        out,
+        "{:<10} = primary {{ \"(\" [ expression {{ \",\" expression }} ] \")\" | \"[\" expression \"]\" }} ;",
+        "postfix"
+    );
+    out.push_str(PRIMARY);
+    let keywords = KEYWORDS.iter().map(|(keyword, _)| format!("\"{keyword}\""));
+    let _ = writeln!(
+        out,
+        "{:<10} = {} ;",
+        "keyword",
+        keywords.collect::<Vec<_>>().join(" | ")
+    );
+    out
+}
+
+/// Keywords and operators with their precedences as JSON, for editors and docs.
+pub fn json() -> String {
+    let strings = |items: Vec<String>| {
+        let quoted: Vec<String> = items.iter().map(|item| format!("\"{item}\"")).collect();
+        format!("[{}]", quoted.join(", "))
+    };
+    let operators = |tokens: &[TokenType]| {
+        let entries: Vec<String> = tokens
+            .iter()
+            .map(|token| {
+                format!(
+                    "{{\"operator\": \"{token}\", \"precedence\": {}}}",
+                    token.precedence()
+                )
+            })
+            .collect();
+        format!("[\n    {}\n  ]", entries.join(",\n    "))
+    };
+    let keywords = KEYWORDS.iter().map(|(keyword, _)| keyword.to_string());
+    let prefix = PREFIX_OPERATORS.iter().map(|token| token.to_string());
+    format!(
+        "{{\n  \"keywords\": {},\n  \"prefix_operators\": {},\n  \"infix_operators\": {},\n  \"postfix_operators\": {}\n}}\n",
+        strings(keywords.collect()),
+        strings(prefix.collect()),
+        operators(INFIX_OPERATORS),
+        operators(&[TokenType::LParen, TokenType::LBracket]),
+    )
+}
+
+#[cfg(test)]
+mod grammar_tests {
+    use super::{ebnf, json};
+    use crate::{
+        lexer::Lexer,
+        prelude::*,
+        token::{TokenType, KEYWORDS},
+    };
+
+    #[test]
+    fn keywords_lex_to_their_tokens() {
+        for (keyword, token) in KEYWORDS {
+            let tokens: Vec<TokenType> = Lexer::new(keyword).map(|token| token.kind).collect();
+            assert_eq!(tokens, vec![token.clone()]);
+        }
+    }
+
+    #[test]
+    fn ebnf_follows_precedence_table() {
+        let grammar = ebnf();
+        assert!(grammar.contains("expression = identifier \"=\" expression | binary_2 ;\n"));
+        assert!(grammar.contains(r#"binary_2   = binary_3 { ( "==" | "!=" ) binary_3 } ;"#));
+        assert!(grammar.contains(r#"binary_5   = prefix { ( "*" | "/" | "%" ) prefix } ;"#));
+        assert!(grammar.contains(r#"prefix     = ( "!" | "-" ) prefix | postfix ;"#));
+        assert!(grammar.contains(r#""while" | "for" | "in" | "break" | "continue" | "nil" ;"#));
+    }
+
+    #[test]
+    fn json_lists_operators() {
+        let json = json();
+        assert!(json.contains(r#""prefix_operators": ["!", "-"]"#));
+        assert!(json.contains(r#"{"operator": "%", "precedence": 5}"#));
+        assert!(json.contains(r#"{"operator": "[", "precedence": 8}"#));
+    }
+}
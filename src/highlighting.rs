@@ -0,0 +1,149 @@
+use crate::{
+    prelude::*,
+    token::{TokenType, INFIX_OPERATORS, KEYWORDS, PREFIX_OPERATORS},
+};
+
+/// Keywords split into language constants (`true`, `false`, `nil`) and the rest.
+fn keywords() -> (Vec<&'static str>, Vec<&'static str>) {
+    let (constants, keywords): (Vec<_>, Vec<_>) = KEYWORDS.iter().partition(|(_, token)| {
+        matches!(token, TokenType::True | TokenType::False | TokenType::Nil)
+    });
+    let names = |entries: Vec<&(&'static str, TokenType)>| {
+        entries.into_iter().map(|(name, _)| *name).collect()
+    };
+    (names(constants), names(keywords))
+}
+
+/// Operator spellings, longest first so `<=` is matched before `<`.
+fn operators() -> Vec<String> {
+    let mut operators: Vec<String> = INFIX_OPERATORS
+        .iter()
+        .chain(PREFIX_OPERATORS)
+        .map(|token| token.to_string())
+        .collect();
+    operators.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+    operators.dedup();
+    operators
+}
+
+fn json_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// A TextMate grammar (also understood by VS Code) as JSON.
+pub fn tmlanguage() -> String {
+    let (constants, keywords) = keywords();
+    let operators: Vec<String> = operators()
+        .iter()
+        .map(|operator| {
+            operator
+                .chars()
+                .map(|char| match char {
+                    '+' | '*' | '/' | '|' | '(' | ')' | '[' | ']' => format!("\\{char}"),
+                    char => char.to_string(),
+                })
+                .collect()
+        })
+        .collect();
+    let rule = |scope: &str, body: String| format!("    {{\"name\": \"{scope}.monkey\", {body}}}");
+    let matches = |pattern: &str| format!("\"match\": {}", json_string(pattern));
+    let region = |begin: &str, end: &str| {
+        format!(
+            "\"begin\": {}, \"end\": {}",
+            json_string(begin),
+            json_string(end)
+        )
+    };
+    let patterns = [
+        rule("comment.line.double-slash", matches("//.*$")),
+        rule("comment.block", region("/\\*", "\\*/")),
+        rule(
+            "string.quoted.double",
+            format!(
+                "{}, \"patterns\": [{{\"name\": \"constant.character.escape.monkey\", {}}}]",
+                region("\"", "\""),
+                matches("\\\\(u\\{[0-9a-fA-F]*\\}|.)")
+            ),
+        ),
+        rule(
+            "constant.numeric",
+            matches("\\b(0[xob][0-9a-fA-F_]+|[0-9][0-9_]*(\\.[0-9][0-9_]*)?([eE][+-]?[0-9][0-9_]*)?)\\b"),
+        ),
+        rule(
+            "constant.language",
+            matches(&format!("\\b({})\\b", constants.join("|"))),
+        ),
+        rule(
+            "keyword.control",
+            matches(&format!("\\b({})\\b", keywords.join("|"))),
+        ),
+        rule("keyword.operator", matches(&operators.join("|"))),
+    ];
+    format!(
+        "{{\n  \"name\": \"Monkey\",\n  \"scopeName\": \"source.monkey\",\n  \"fileTypes\": [\"mk\"],\n  \"patterns\": [\n{}\n  ]\n}}\n",
+        patterns.join(",\n")
+    )
+}
+
+/// A Vim syntax file.
+pub fn vim() -> String {
+    let (constants, keywords) = keywords();
+    let operators: Vec<String> = operators()
+        .iter()
+        .map(|operator| operator.replace('*', "\\*"))
+        .collect();
+    format!(
+        r#"" Vim syntax file for Monkey, generated by `rustmonk gen-syntax --format=vim`.
+if exists("b:current_syntax")
+  finish
+endif
+
+syntax keyword monkeyKeyword {}
+syntax keyword monkeyConstant {}
+syntax match monkeyNumber "\<\(0[xob][0-9a-fA-F_]\+\|\d[0-9_]*\(\.\d[0-9_]*\)\?\([eE][+-]\?\d[0-9_]*\)\?\)\>"
+syntax region monkeyString start=+"+ skip=+\\.+ end=+"+
+syntax match monkeyOperator "{}"
+syntax match monkeyComment "//.*$"
+syntax region monkeyComment start="/\*" end="\*/" contains=monkeyComment
+
+highlight default link monkeyKeyword Keyword
+highlight default link monkeyConstant Constant
+highlight default link monkeyNumber Number
+highlight default link monkeyString String
+highlight default link monkeyOperator Operator
+highlight default link monkeyComment Comment
+
+let b:current_syntax = "monkey"
+"#,
+        keywords.join(" "),
+        constants.join(" "),
+        operators.join("\\|")
+    )
+}
+
+#[cfg(test)]
+mod highlighting_tests {
+    use super::{tmlanguage, vim};
+
+    #[test]
+    fn tmlanguage_uses_token_tables() {
+        let grammar = tmlanguage();
+        assert!(grammar.contains(
+            r#""match": "\\b(fn|macro|let|if|else|return|defer|while|for|in|break|continue)\\b""#
+        ));
+        assert!(grammar.contains(r#""match": "\\b(false|true|nil)\\b""#));
+        assert!(grammar.contains(r#""match": "!=|<=|==|>=|!|%|\\*|\\+|-|\\/|<|>""#));
+        assert!(grammar.contains(r#""begin": "/\\*", "end": "\\*/""#));
+    }
+
+    #[test]
+    fn vim_uses_token_tables() {
+        let syntax = vim();
+        assert!(syntax.contains(
+            "syntax keyword monkeyKeyword fn macro let if else return defer while for in break continue\n"
+        ));
+        assert!(syntax.contains("syntax keyword monkeyConstant false true nil\n"));
+        assert!(syntax
+            .contains(r#"syntax match monkeyOperator "!=\|<=\|==\|>=\|!\|%\|\*\|+\|-\|/\|<\|>""#));
+    }
+}
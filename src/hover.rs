@@ -0,0 +1,113 @@
+use core::ops::Range;
+
+use crate::{
+    builtins,
+    environment::GlobalEnv,
+    lexer::Lexer,
+    object::Object,
+    prelude::*,
+    token::{Span, TokenType},
+};
+
+/// What editor tooling shows for the identifier under the cursor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hover {
+    pub name: String,
+    /// Byte offsets of the identifier in the source.
+    pub range: Range<usize>,
+    /// The type name, as in error messages.
+    pub kind: &'static str,
+    pub value: String,
+}
+
+/// Looks up the identifier of `source` at `position` in the live session `env`, including
+/// globals defined by programs that ran on the VM. Returns `None` when the position isn't on
+/// an identifier or the identifier isn't bound.
+pub fn hover(source: &str, position: Span, env: &GlobalEnv) -> Option<Hover> {
+    let token = Lexer::new(source).find(|token| {
+        let width = source[token.range.clone()].chars().count();
+        token.span.line == position.line
+            && (token.span.column..token.span.column + width).contains(&position.column)
+    })?;
+    let TokenType::Identifier(name) = token.kind else {
+        return None;
+    };
+    let value = lookup(&name.0, env)?;
+    Some(Hover {
+        name: name.0,
+        range: token.range,
+        kind: value.name(),
+        value: value.to_string(),
+    })
+}
+
+fn lookup(name: &str, env: &GlobalEnv) -> Option<Object> {
+    if let Some(value) = env.borrow().get(name) {
+        return Some(value);
+    }
+    #[cfg(feature = "vm")]
+    {
+        let runtime = env.borrow().runtime.clone();
+        let state = runtime.vm.borrow();
+        let global = state
+            .symbol_table
+            .globals()
+            .find(|symbol| symbol.name == name)
+            .and_then(|symbol| state.globals.get(symbol.index).cloned());
+        if global.is_some() {
+            return global;
+        }
+    }
+    builtins::lookup(name).map(Object::Builtin)
+}
+
+#[cfg(test)]
+mod hover_tests {
+    use alloc::rc::Rc;
+    use core::cell::RefCell;
+
+    use super::{hover, Hover};
+    use crate::{
+        environment::Environment,
+        eval::{Engine, Program},
+        lexer::Lexer,
+        parser::Parser,
+        prelude::*,
+        token::Span,
+    };
+
+    #[test]
+    fn describes_the_identifier_under_the_cursor() {
+        let engines = [
+            Engine::Tree,
+            #[cfg(feature = "vm")]
+            Engine::Vm,
+        ];
+        for engine in engines {
+            let source = "let total = 40 + 2;\nlet names = [\"a\"];\nlen(names)";
+            let env = Rc::new(RefCell::new(Environment::default()));
+            let mut parser = Parser::new(Lexer::new(source).tokens());
+            Program::with_engine(engine)
+                .eval(&mut parser, env.clone())
+                .unwrap();
+
+            assert_eq!(
+                hover(source, Span::new(1, 9), &env),
+                Some(Hover {
+                    name: "total".to_string(),
+                    range: 4..9,
+                    kind: "int",
+                    value: "42".to_string(),
+                })
+            );
+            let names = hover(source, Span::new(3, 5), &env).unwrap();
+            assert_eq!((names.kind, names.value.as_str()), ("array", r#"["a"]"#));
+            assert_eq!(
+                hover(source, Span::new(3, 1), &env).unwrap().kind,
+                "builtin"
+            );
+            assert_eq!(hover(source, Span::new(1, 13), &env), None);
+            assert_eq!(hover("missing", Span::new(1, 1), &env), None);
+        }
+    }
+}
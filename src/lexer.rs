@@ -1,92 +1,398 @@
 #![allow(dead_code)]
-use crate::token::{Identifier, TokenType};
-use std::iter::{self, Peekable};
-use std::str::Chars;
+use crate::prelude::*;
+use crate::token::{Identifier, Int, Span, Token, TokenType, KEYWORDS};
+use alloc::collections::VecDeque;
+use core::iter::{self, Peekable};
 
 pub struct Lexer<'a> {
-    chars_iter: Peekable<Chars<'a>>,
+    chars_iter: Box<dyn Iterator<Item = char> + 'a>,
+    /// Characters pulled from `chars_iter` for lookahead but not consumed yet.
+    lookahead: VecDeque<char>,
+    case_insensitive: bool,
+    line: usize,
+    column: usize,
+    offset: usize,
 }
 
 impl<'a> Iterator for Lexer<'a> {
-    type Item = TokenType;
+    type Item = Token;
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(char) = self.chars_iter.next() {
-            return match char {
-                ' ' => self.next(),
-                '\n' => self.next(),
-                ',' => Some(TokenType::Comma),
-                ':' => Some(TokenType::Colon),
-                ';' => Some(TokenType::Semicolon),
-                '(' => Some(TokenType::LParen),
-                ')' => Some(TokenType::RParen),
-                '[' => Some(TokenType::LBracket),
-                ']' => Some(TokenType::RBracket),
-                '{' => Some(TokenType::LBrace),
-                '}' => Some(TokenType::RBrace),
-                '-' => Some(TokenType::Minus),
-                '+' => Some(TokenType::Plus),
-                '*' => Some(TokenType::Asterisk),
-                '.' => Some(TokenType::Dot),
-                '/' => Some(TokenType::Slash),
-                '=' => self
-                    .chars_iter
-                    .next_if_eq(&'=')
-                    .map_or(Some(TokenType::Assign), |_| Some(TokenType::Eq)),
-                '!' => self
-                    .chars_iter
-                    .next_if_eq(&'=')
-                    .map_or(Some(TokenType::Bang), |_| Some(TokenType::NotEq)),
-                '<' => self
-                    .chars_iter
-                    .next_if_eq(&'=')
-                    .map_or(Some(TokenType::Lt), |_| Some(TokenType::Lte)),
-                '>' => self
-                    .chars_iter
-                    .next_if_eq(&'=')
-                    .map_or(Some(TokenType::Gt), |_| Some(TokenType::Gte)),
-                num if num.is_ascii_digit() => iter::once(num)
+        let span = Span::new(self.line, self.column);
+        let start = self.offset;
+        let char = self.bump()?;
+        let kind = match char {
+            ' ' | '\t' | '\r' | '\n' => return self.next(),
+            ',' => TokenType::Comma,
+            ':' => TokenType::Colon,
+            ';' => TokenType::Semicolon,
+            '(' => TokenType::LParen,
+            ')' => TokenType::RParen,
+            '[' => TokenType::LBracket,
+            ']' => TokenType::RBracket,
+            '{' => TokenType::LBrace,
+            '}' => TokenType::RBrace,
+            '-' => TokenType::Minus,
+            '+' => TokenType::Plus,
+            '*' => TokenType::Asterisk,
+            '.' if self.peek_nth(0) == Some('.') && self.peek_nth(1) == Some('.') => {
+                self.bump();
+                self.bump();
+                TokenType::Ellipsis
+            }
+            '.' => TokenType::Dot,
+            '/' if self.bump_if(|char| char == '/').is_some() => {
+                while self.bump_if(|char| char != '\n').is_some() {}
+                return self.next();
+            }
+            '/' if self.bump_if(|char| char == '*').is_some() => match self.skip_block_comment() {
+                true => return self.next(),
+                false => TokenType::Illegal,
+            },
+            '/' => TokenType::Slash,
+            '%' => TokenType::Percent,
+            '"' => self.string(),
+            '=' => self
+                .bump_if(|char| char == '=')
+                .map_or(TokenType::Assign, |_| TokenType::Eq),
+            '!' => self
+                .bump_if(|char| char == '=')
+                .map_or(TokenType::Bang, |_| TokenType::NotEq),
+            '<' => self
+                .bump_if(|char| char == '=')
+                .map_or(TokenType::Lt, |_| TokenType::Lte),
+            '>' => self
+                .bump_if(|char| char == '=')
+                .map_or(TokenType::Gt, |_| TokenType::Gte),
+            num if num.is_ascii_digit() => self.number(num),
+            ch if ch.is_alphabetic() => {
+                let result = iter::once(ch)
                     .chain(iter::from_fn(|| {
-                        self.chars_iter.next_if(|char| char.is_ascii_digit())
+                        self.bump_if(|char| {
+                            char.is_alphabetic() || char.is_ascii_digit() || char == '_'
+                        })
                     }))
-                    .collect::<String>()
-                    .parse::<i64>()
-                    .map_or(Some(TokenType::Illegal), |x| Some(TokenType::Int(x))),
-                ch if ch.is_alphabetic() => {
-                    let result = iter::once(ch)
-                        .chain(iter::from_fn(|| {
-                            self.chars_iter.next_if(|char| char.is_alphabetic())
-                        }))
-                        .collect::<String>();
-
-                    match result.as_str() {
-                        "fn" => Some(TokenType::Function),
-                        "let" => Some(TokenType::Let),
-                        "false" => Some(TokenType::False),
-                        "true" => Some(TokenType::True),
-                        "if" => Some(TokenType::If),
-                        "else" => Some(TokenType::Else),
-                        "return" => Some(TokenType::Return),
-                        "nil" => Some(TokenType::Nil),
-                        _ => Some(TokenType::Identifier(Identifier::new(result))),
-                    }
+                    .collect::<String>();
+                let result = if self.case_insensitive {
+                    result.to_lowercase()
+                } else {
+                    result
+                };
+
+                match KEYWORDS.iter().find(|(keyword, _)| *keyword == result) {
+                    Some((_, token)) => token.clone(),
+                    None => TokenType::Identifier(Identifier::new(result)),
                 }
-                _ => Some(TokenType::Illegal),
-            };
-        } else {
-            None
-        }
+            }
+            _ => TokenType::Illegal,
+        };
+        Some(Token {
+            kind,
+            span,
+            range: start..self.offset,
+        })
     }
 }
 
 impl<'a> Lexer<'a> {
     pub fn new(text: &'a str) -> Self {
+        Self::from_chars(text.chars())
+    }
+
+    /// Lexes `reader` as it is read, a line at a time, so large files never have to be
+    /// held in memory whole. A read or UTF-8 error ends the input with an illegal token.
+    #[cfg(feature = "std")]
+    pub fn from_reader(reader: impl std::io::Read + 'a) -> Self {
+        Self::from_chars(ReaderChars::new(reader))
+    }
+
+    fn from_chars(chars: impl Iterator<Item = char> + 'a) -> Self {
         Lexer {
-            chars_iter: text.chars().peekable(),
+            chars_iter: Box::new(chars),
+            lookahead: VecDeque::new(),
+            case_insensitive: false,
+            line: 1,
+            column: 1,
+            offset: 0,
+        }
+    }
+
+    /// The tokens ready to hand to [`crate::parser::Parser::new`].
+    pub fn tokens(self) -> Peekable<Self> {
+        self.peekable()
+    }
+
+    pub fn case_insensitive(mut self, enabled: bool) -> Self {
+        self.case_insensitive = enabled;
+        self
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let char = match self.lookahead.pop_front() {
+            Some(char) => char,
+            None => self.chars_iter.next()?,
+        };
+        self.offset += char.len_utf8();
+        if char == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(char)
+    }
+
+    /// Skips the rest of a `/* ... */` comment, which may nest. Returns false when the
+    /// input ends before the comment is closed.
+    fn skip_block_comment(&mut self) -> bool {
+        let mut depth = 1;
+        while depth > 0 {
+            match self.bump() {
+                Some('*') if self.bump_if(|char| char == '/').is_some() => depth -= 1,
+                Some('/') if self.bump_if(|char| char == '*').is_some() => depth += 1,
+                Some(_) => {}
+                None => return false,
+            }
+        }
+        true
+    }
+
+    /// Lexes a string literal after its opening quote. A bad escape makes the literal
+    /// malformed, but it is still read up to its closing quote so lexing resumes after it.
+    fn string(&mut self) -> TokenType {
+        let mut text = String::from('"');
+        let mut string = String::new();
+        let mut error = None;
+        loop {
+            let Some(char) = self.bump() else {
+                return TokenType::Illegal;
+            };
+            text.push(char);
+            match char {
+                '"' => break,
+                '\\' => match self.escape(&mut text) {
+                    Ok(char) => string.push(char),
+                    Err(message) => {
+                        error.get_or_insert(message);
+                    }
+                },
+                char => string.push(char),
+            }
+        }
+        match error {
+            Some(message) => malformed(text, &format!("{message} in string")),
+            None => TokenType::String(string),
+        }
+    }
+
+    /// Reads the escape sequence after a `\`, adding its source to `text`: one of `\n`,
+    /// `\r`, `\t`, `\"`, `\\` or `\u{XXXX}` with one to six hex digits.
+    fn escape(&mut self, text: &mut String) -> Result<char, String> {
+        // The string is unterminated, which the caller finds next.
+        let Some(char) = self.bump() else {
+            return Err(String::new());
+        };
+        text.push(char);
+        match char {
+            'n' => Ok('\n'),
+            'r' => Ok('\r'),
+            't' => Ok('\t'),
+            '"' | '\\' => Ok(char),
+            'u' => {
+                if self.bump_if(|char| char == '{').is_none() {
+                    return Err("unicode escapes must look like \\u{1F600}".to_string());
+                }
+                let digits: String =
+                    iter::from_fn(|| self.bump_if(|char| char.is_ascii_hexdigit())).collect();
+                text.push('{');
+                text.push_str(&digits);
+                let escape = format!("\\u{{{digits}}}");
+                if self.bump_if(|char| char == '}').is_none() {
+                    return Err(format!("unterminated unicode escape \\u{{{digits}"));
+                }
+                text.push('}');
+                if digits.is_empty() || digits.len() > 6 {
+                    return Err(format!(
+                        "unicode escape {escape} must have 1 to 6 hex digits"
+                    ));
+                }
+                u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| format!("{escape} is not a unicode scalar value"))
+            }
+            char => Err(format!("unknown escape sequence \\{char}")),
+        }
+    }
+
+    /// Lexes a number starting with `first`: a decimal int or float, or an int in hex, octal
+    /// or binary after `0x`, `0o` or `0b`. Underscores may separate digits, as in `1_000`,
+    /// and floats may have an exponent, as in `1.5e-7` or `1e300`.
+    fn number(&mut self, first: char) -> TokenType {
+        let radix = match (first, self.peek_nth(0)) {
+            ('0', Some('x')) => Some((16, "hexadecimal")),
+            ('0', Some('o')) => Some((8, "octal")),
+            ('0', Some('b')) => Some((2, "binary")),
+            _ => None,
+        };
+        let mut text = String::from(first);
+        let Some((radix, name)) = radix else {
+            let digits = |lexer: &mut Self| -> String {
+                iter::from_fn(|| lexer.bump_if(|char| char.is_ascii_digit() || char == '_'))
+                    .collect()
+            };
+            text.push_str(&digits(self));
+            let mut float = false;
+            if self.fraction_follows() {
+                text.extend(self.bump());
+                text.push_str(&digits(self));
+                float = true;
+            }
+            if self.exponent_follows() {
+                text.extend(self.bump());
+                text.extend(self.bump_if(|char| char == '+' || char == '-'));
+                text.push_str(&digits(self));
+                float = true;
+            }
+            if !float {
+                return int(text, 10);
+            }
+            if !text.split(['.', 'e', 'E', '+', '-']).all(separates_digits) {
+                return malformed(text, "underscores must separate digits");
+            }
+            return match text.replace('_', "").parse::<f64>() {
+                Ok(float) => TokenType::Float(float),
+                Err(_) => malformed(text, "invalid float literal"),
+            };
+        };
+        text.extend(self.bump());
+        // Letters are taken too, so `0x1G` is reported whole rather than as `0x1` and `G`.
+        text.extend(iter::from_fn(|| {
+            self.bump_if(|char| char.is_ascii_alphanumeric() || char == '_')
+        }));
+        match text[2..]
+            .chars()
+            .find(|char| *char != '_' && !char.is_digit(radix))
+        {
+            Some(digit) => {
+                let message = format!("invalid digit '{digit}' in {name} literal");
+                malformed(text, &message)
+            }
+            None if text.len() == 2 => {
+                malformed(text, &format!("missing digits in {name} literal"))
+            }
+            None => int(text, radix),
+        }
+    }
+
+    /// Whether the input continues with a `.` and a digit, so `1.5` is a float but `1.x` is not.
+    fn fraction_follows(&mut self) -> bool {
+        self.peek_nth(0) == Some('.') && self.peek_nth(1).is_some_and(|char| char.is_ascii_digit())
+    }
+
+    /// Whether the input continues with an exponent such as `e5` or `E-5`.
+    fn exponent_follows(&mut self) -> bool {
+        let digit = |char: Option<char>| char.is_some_and(|char| char.is_ascii_digit());
+        matches!(self.peek_nth(0), Some('e' | 'E'))
+            && (digit(self.peek_nth(1))
+                || matches!(self.peek_nth(1), Some('+' | '-')) && digit(self.peek_nth(2)))
+    }
+
+    fn peek_nth(&mut self, n: usize) -> Option<char> {
+        while self.lookahead.len() <= n {
+            let char = self.chars_iter.next()?;
+            self.lookahead.push_back(char);
+        }
+        self.lookahead.get(n).copied()
+    }
+
+    fn bump_if(&mut self, accept: impl FnOnce(char) -> bool) -> Option<char> {
+        match self.peek_nth(0) {
+            Some(char) if accept(char) => self.bump(),
+            _ => None,
+        }
+    }
+}
+
+/// The int value of `text`, the digits of which are in `radix` and follow a two character
+/// prefix unless the radix is 10.
+fn int(text: String, radix: u32) -> TokenType {
+    let digits = match radix {
+        10 => &text[..],
+        _ => &text[2..],
+    };
+    if !separates_digits(digits) {
+        return malformed(text, "underscores must separate digits");
+    }
+    match Int::from_str_radix(&digits.replace('_', ""), radix) {
+        Ok(int) => TokenType::Int(int),
+        Err(_) => malformed(text, "integer literal is too large"),
+    }
+}
+
+/// Whether every underscore in `digits` sits between two digits.
+fn separates_digits(digits: &str) -> bool {
+    !digits.starts_with('_') && !digits.ends_with('_') && !digits.contains("__")
+}
+
+fn malformed(text: String, message: &str) -> TokenType {
+    let message = format!("{message}: {text}");
+    TokenType::Malformed { text, message }
+}
+
+/// The characters of a reader, decoded one line at a time.
+#[cfg(feature = "std")]
+struct ReaderChars<R> {
+    reader: std::io::BufReader<R>,
+    line: Vec<char>,
+    position: usize,
+    done: bool,
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> ReaderChars<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            reader: std::io::BufReader::new(reader),
+            line: vec![],
+            position: 0,
+            done: false,
         }
     }
 }
 
+#[cfg(feature = "std")]
+impl<R: std::io::Read> Iterator for ReaderChars<R> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        use std::io::BufRead;
+
+        while self.position == self.line.len() {
+            if self.done {
+                return None;
+            }
+            let mut line = String::new();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => return None,
+                Ok(_) => self.line = line.chars().collect(),
+                Err(_) => {
+                    // Not a character the lexer accepts, so the error surfaces as an illegal token.
+                    self.done = true;
+                    self.line = vec![char::REPLACEMENT_CHARACTER];
+                }
+            }
+            self.position = 0;
+        }
+        self.position += 1;
+        Some(self.line[self.position - 1])
+    }
+}
+
 mod test {
+    #[cfg(test)]
+    use crate::prelude::*;
+
+    #[cfg(feature = "std")]
     #[test]
     fn parse() {
         use crate::lexer;
@@ -114,4 +420,296 @@ return false;
             println!("{:?}", l);
         }
     }
+
+    #[test]
+    fn case_insensitive_identifiers() {
+        use crate::lexer::Lexer;
+        use crate::token::{Identifier, TokenType};
+
+        let tokens: Vec<TokenType> = Lexer::new("LET Foo = foo")
+            .case_insensitive(true)
+            .map(|token| token.kind)
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                TokenType::Let,
+                TokenType::Identifier(Identifier::new_str("foo")),
+                TokenType::Assign,
+                TokenType::Identifier(Identifier::new_str("foo")),
+            ]
+        );
+
+        let tokens: Vec<TokenType> = Lexer::new("Foo").map(|token| token.kind).collect();
+        assert_eq!(
+            tokens,
+            vec![TokenType::Identifier(Identifier::new_str("Foo"))]
+        );
+    }
+
+    #[test]
+    fn identifiers_with_underscores_and_digits() {
+        use crate::lexer::Lexer;
+        use crate::token::{Identifier, TokenType};
+
+        let tokens: Vec<TokenType> = Lexer::new("my_var foo2 x_1_ 2x _y")
+            .map(|token| token.kind)
+            .collect();
+        let identifier = |name| TokenType::Identifier(Identifier::new_str(name));
+        assert_eq!(
+            tokens,
+            vec![
+                identifier("my_var"),
+                identifier("foo2"),
+                identifier("x_1_"),
+                TokenType::Int(2),
+                identifier("x"),
+                TokenType::Illegal,
+                identifier("y"),
+            ]
+        );
+        let tokens: Vec<TokenType> = Lexer::new("let_ if2").map(|token| token.kind).collect();
+        assert_eq!(tokens, vec![identifier("let_"), identifier("if2")]);
+    }
+
+    #[test]
+    fn floats() {
+        use crate::lexer::Lexer;
+        use crate::token::{Identifier, TokenType};
+
+        let tokens: Vec<TokenType> = Lexer::new("1.5 2 3.x ...y")
+            .map(|token| token.kind)
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                TokenType::Float(1.5),
+                TokenType::Int(2),
+                TokenType::Int(3),
+                TokenType::Dot,
+                TokenType::Identifier(Identifier::new_str("x")),
+                TokenType::Ellipsis,
+                TokenType::Identifier(Identifier::new_str("y")),
+            ]
+        );
+        let tokens: Vec<TokenType> = Lexer::new("1e300 1.5E-7 2e+3 4e")
+            .map(|token| token.kind)
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                TokenType::Float(1e300),
+                TokenType::Float(1.5e-7),
+                TokenType::Float(2e3),
+                TokenType::Int(4),
+                TokenType::Identifier(Identifier::new_str("e")),
+            ]
+        );
+    }
+
+    #[test]
+    fn string_escapes() {
+        use crate::lexer::Lexer;
+        use crate::token::TokenType;
+
+        let tokens: Vec<TokenType> = Lexer::new(r#""a\"b\\c\n\r\t" "\u{e9}\u{1F600}\u{0}""#)
+            .map(|token| token.kind)
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                TokenType::String("a\"b\\c\n\r\t".to_string()),
+                TokenType::String("é😀\0".to_string()),
+            ]
+        );
+
+        let tokens: Vec<TokenType> =
+            Lexer::new(r#""a\qb" "\u41" "\u{}" "\u{1234567}" "\u{D800}" "\u{41" x"#)
+                .map(|token| token.kind)
+                .collect();
+        let malformed = |text: &str, message: &str| TokenType::Malformed {
+            text: text.to_string(),
+            message: format!("{message} in string: {text}"),
+        };
+        assert_eq!(
+            tokens,
+            vec![
+                malformed(r#""a\qb""#, r"unknown escape sequence \q"),
+                malformed(r#""\u41""#, r"unicode escapes must look like \u{1F600}"),
+                malformed(
+                    r#""\u{}""#,
+                    r"unicode escape \u{} must have 1 to 6 hex digits"
+                ),
+                malformed(
+                    r#""\u{1234567}""#,
+                    r"unicode escape \u{1234567} must have 1 to 6 hex digits"
+                ),
+                malformed(r#""\u{D800}""#, r"\u{D800} is not a unicode scalar value"),
+                // The quote after the unterminated escape is read as the end of the string.
+                malformed(r#""\u{41""#, r"unterminated unicode escape \u{41"),
+                TokenType::Identifier(crate::token::Identifier::new("x".to_string())),
+            ]
+        );
+        assert_eq!(
+            Lexer::new(r#""a\"#).next().unwrap().kind,
+            TokenType::Illegal
+        );
+    }
+
+    #[test]
+    fn number_prefixes_and_separators() {
+        use crate::lexer::Lexer;
+        use crate::token::TokenType;
+
+        let tokens: Vec<TokenType> = Lexer::new("0x1F 0o17 0b1010 1_000_000 0xff_ff 1_0.2_5 0")
+            .map(|token| token.kind)
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                TokenType::Int(31),
+                TokenType::Int(15),
+                TokenType::Int(10),
+                TokenType::Int(1_000_000),
+                TokenType::Int(0xffff),
+                TokenType::Float(10.25),
+                TokenType::Int(0),
+            ]
+        );
+
+        let messages: Vec<String> =
+            Lexer::new("0x1G 0b102 0o 1__0 1_ 0x_1 1_.5 99999999999999999999999999999999999999999")
+                .map(|token| match token.kind {
+                    TokenType::Malformed { message, .. } => message,
+                    kind => panic!("expected a malformed literal, got {kind:?}"),
+                })
+                .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "invalid digit 'G' in hexadecimal literal: 0x1G",
+                "invalid digit '2' in binary literal: 0b102",
+                "missing digits in octal literal: 0o",
+                "underscores must separate digits: 1__0",
+                "underscores must separate digits: 1_",
+                "underscores must separate digits: 0x_1",
+                "underscores must separate digits: 1_.5",
+                "integer literal is too large: 99999999999999999999999999999999999999999",
+            ]
+        );
+    }
+
+    #[test]
+    fn comments() {
+        use crate::lexer::Lexer;
+        use crate::token::{Span, TokenType};
+
+        let tokens: Vec<TokenType> =
+            Lexer::new("1 // one\n/* two /* nested */ still two */ 2 / 3\n// trailing")
+                .map(|token| token.kind)
+                .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                TokenType::Int(1),
+                TokenType::Int(2),
+                TokenType::Slash,
+                TokenType::Int(3),
+            ]
+        );
+
+        let tokens: Vec<_> = Lexer::new("1 /* open /* */").collect();
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[1].kind, TokenType::Illegal);
+        assert_eq!(tokens[1].span, Span::new(1, 3));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn reader_matches_str() {
+        use crate::lexer::Lexer;
+        use crate::token::{Token, TokenType};
+
+        let program = "let pi = 3.14;\n/* é\n */ let s = \"naïve\";\n1.x";
+        let tokens: Vec<Token> = Lexer::from_reader(program.as_bytes()).collect();
+        assert_eq!(tokens, Lexer::new(program).collect::<Vec<_>>());
+
+        let tokens: Vec<TokenType> = Lexer::from_reader(&b"let x = \xff"[..])
+            .map(|token| token.kind)
+            .collect();
+        assert_eq!(tokens, vec![TokenType::Illegal]);
+    }
+
+    #[test]
+    fn token_spans() {
+        use crate::lexer::Lexer;
+        use crate::token::Span;
+
+        let spans: Vec<Span> = Lexer::new("let x = 5;\n  x == \"a b\"")
+            .map(|token| token.span)
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                Span::new(1, 1),
+                Span::new(1, 5),
+                Span::new(1, 7),
+                Span::new(1, 9),
+                Span::new(1, 10),
+                Span::new(2, 3),
+                Span::new(2, 5),
+                Span::new(2, 8),
+            ]
+        );
+    }
+
+    #[test]
+    fn tabs_are_whitespace() {
+        use crate::lexer::Lexer;
+        use crate::token::{Identifier, Span, TokenType};
+
+        let tokens: Vec<(TokenType, Span)> = Lexer::new("let\tx =\t\t5;")
+            .map(|token| (token.kind, token.span))
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                (TokenType::Let, Span::new(1, 1)),
+                (
+                    TokenType::Identifier(Identifier("x".to_string())),
+                    Span::new(1, 5)
+                ),
+                (TokenType::Assign, Span::new(1, 7)),
+                (TokenType::Int(5), Span::new(1, 10)),
+                (TokenType::Semicolon, Span::new(1, 11)),
+            ]
+        );
+    }
+
+    #[test]
+    fn carriage_returns_are_whitespace() {
+        use crate::lexer::Lexer;
+        use crate::token::{Identifier, Span, TokenType};
+
+        let tokens: Vec<(TokenType, Span)> = Lexer::new("let x = 5;\r\nx\r\n")
+            .map(|token| (token.kind, token.span))
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                (TokenType::Let, Span::new(1, 1)),
+                (
+                    TokenType::Identifier(Identifier("x".to_string())),
+                    Span::new(1, 5)
+                ),
+                (TokenType::Assign, Span::new(1, 7)),
+                (TokenType::Int(5), Span::new(1, 9)),
+                (TokenType::Semicolon, Span::new(1, 10)),
+                (
+                    TokenType::Identifier(Identifier("x".to_string())),
+                    Span::new(2, 1)
+                ),
+            ]
+        );
+    }
 }
@@ -1,8 +1,57 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+#[cfg(all(test, not(feature = "std")))]
+extern crate std;
+
+pub mod analysis;
 pub mod ast;
+#[cfg(feature = "std")]
+pub mod bench;
+pub mod builtins;
+pub mod callgraph;
+#[cfg(feature = "vm")]
+pub mod code;
+#[cfg(feature = "vm")]
+pub mod compiler;
+#[cfg(feature = "std")]
+pub mod conformance;
+pub mod diff;
 pub mod environment;
 pub mod eval;
+pub mod formatter;
+pub mod gc;
+pub mod grammar;
+pub mod highlighting;
+pub mod hover;
 pub mod lexer;
+pub mod macros;
 pub mod object;
+pub mod optimizer;
 pub mod parser;
+#[cfg(feature = "plugins")]
+pub mod plugin;
+pub mod refactor;
+#[cfg(feature = "repl")]
 pub mod repl;
+pub mod runtime;
+pub mod semantic;
+pub mod stats;
+pub mod stdlib;
+#[cfg(feature = "std")]
+pub mod store;
+pub mod syntax;
 pub mod token;
+pub mod visitor;
+#[cfg(feature = "vm")]
+pub mod vm;
+
+mod prelude {
+    pub use alloc::{
+        boxed::Box,
+        format,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+}
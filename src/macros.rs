@@ -0,0 +1,394 @@
+//! Macro expansion as in "The Lost Chapter". `let name = macro(a, b) { ... };` at the top
+//! level defines a macro. Later calls to `name` are replaced before evaluation by the AST
+//! the macro body returns, which it builds with `quote` and `unquote`.
+
+use alloc::{collections::BTreeMap, rc::Rc};
+use anyhow::{bail, Result};
+
+use crate::{
+    ast::{Block, Call, Comprehension, Expression, If, Index, Infix, Literal, Prefix, Statement},
+    environment::{Environment, GlobalEnv},
+    object::{Function, Object, Shared},
+    parser::MAX_DEPTH,
+    prelude::*,
+};
+
+type Modifier<'a> = dyn FnMut(Expression) -> Result<Expression> + 'a;
+
+/// Evaluates `quote(expression)`: the expression itself, with every `unquote(x)` inside
+/// replaced by the value of `x`.
+pub fn quote(expression: Expression, env: &GlobalEnv) -> Result<Object> {
+    let expression = modify_expression(expression, &mut |expression| match expression {
+        Expression::Call(call) if calls(&call, "unquote") => {
+            let [argument] = <[Expression; 1]>::try_from(call.arguments)
+                .map_err(|_| anyhow::anyhow!("unquote expects exactly one argument"))?;
+            unquoted(argument.eval(env.clone())?)
+        }
+        expression => Ok(expression),
+    })?;
+    Ok(Object::Quote(Rc::new(expression)))
+}
+
+/// Whether `call` calls the plain identifier `name`.
+pub fn calls(call: &Call, name: &str) -> bool {
+    matches!(&*call.function, Expression::Identifier(identifier) if identifier.0 == name)
+}
+
+fn unquoted(object: Object) -> Result<Expression> {
+    let literal = match object {
+        Object::Quote(expression) => return Ok(Expression::clone(&expression)),
+        Object::Int(value) => Literal::Int(value),
+        Object::Float(value) => Literal::Float(value),
+        Object::Bool(true) => Literal::True,
+        Object::Bool(false) => Literal::False,
+        Object::String(value) => Literal::String(value.to_string()),
+        Object::Nil => Literal::Nil,
+        object => bail!("cannot unquote {}", object.name()),
+    };
+    Ok(Expression::Literal(literal))
+}
+
+/// Defines the macro when `statement` is a macro definition and returns `None`, otherwise
+/// returns the statement with all calls to known macros expanded.
+pub fn process(statement: Statement, env: &GlobalEnv) -> Result<Option<Statement>> {
+    if let Statement::Let {
+        identifier,
+        expression: Expression::Macro(literal),
+    } = statement
+    {
+        let definition = Object::Macro(Function {
+            parameters: literal.params.into(),
+            rest: literal.rest,
+            body: literal.body,
+            env: env.clone(),
+            escapes: true,
+        });
+        env.borrow().runtime.macros.set(true);
+        env.borrow_mut().set(identifier.0, definition);
+        return Ok(None);
+    }
+    // Until a macro is defined there is nothing to expand, and rebuilding a long `+` chain
+    // would recurse through every term.
+    if !env.borrow().runtime.macros.get() {
+        return Ok(Some(statement));
+    }
+    modify_statement(statement, &mut |expression| expand(expression, env)).map(Some)
+}
+
+fn expand(expression: Expression, env: &GlobalEnv) -> Result<Expression> {
+    let Expression::Call(call) = expression else {
+        return Ok(expression);
+    };
+    let definition = match &*call.function {
+        Expression::Identifier(name) => env.borrow().get(&name.0),
+        _ => None,
+    };
+    let Some(Object::Macro(definition)) = definition else {
+        return Ok(Expression::Call(call));
+    };
+    let (expected, got) = (definition.parameters.len(), call.arguments.len());
+    if got < expected || (got > expected && definition.rest.is_none()) {
+        let at_least = if definition.rest.is_some() {
+            "at least "
+        } else {
+            ""
+        };
+        bail!(
+            "wrong number of arguments to macro {}: expected {at_least}{expected}, got {got}",
+            call.function
+        );
+    }
+    let runtime = env.borrow().runtime.clone();
+    let mut quoted: Vec<Object> = call
+        .arguments
+        .into_iter()
+        .map(|argument| Object::Quote(Rc::new(argument)))
+        .collect();
+    let rest = definition
+        .rest
+        .as_ref()
+        .map(|rest| (rest, quoted.split_off(definition.parameters.len())));
+    let mut arguments: BTreeMap<Rc<str>, Object> = definition
+        .parameters
+        .iter()
+        .map(|parameter| runtime.intern_identifier(&parameter.0))
+        .zip(quoted)
+        .collect();
+    if let Some((rest, quoted)) = rest {
+        arguments.insert(
+            runtime.intern_identifier(&rest.0),
+            Object::Array(Shared::new(quoted)),
+        );
+    }
+    let scope = Environment::new_enclosed(definition.env.clone(), arguments);
+    match definition.body.eval(scope)? {
+        Object::Quote(expression) => Ok(Expression::clone(&expression)),
+        Object::Return(value) => match *value {
+            Object::Quote(expression) => Ok(Expression::clone(&expression)),
+            _ => bail!("macro {} must return a quoted expression", call.function),
+        },
+        _ => bail!("macro {} must return a quoted expression", call.function),
+    }
+}
+
+pub(crate) fn modify_statement(statement: Statement, f: &mut Modifier) -> Result<Statement> {
+    Rewrite { f, depth: 0 }.statement(statement)
+}
+
+/// Rebuilds `expression` bottom-up, passing every node to `f` after its children.
+pub(crate) fn modify_expression(expression: Expression, f: &mut Modifier) -> Result<Expression> {
+    Rewrite { f, depth: 0 }.expression(expression)
+}
+
+/// A bottom-up rebuild in progress, which gives up with an error rather than recurse
+/// through more nested expressions than the parser allows.
+struct Rewrite<'a, 'f> {
+    f: &'a mut Modifier<'f>,
+    depth: usize,
+}
+
+impl Rewrite<'_, '_> {
+    fn statement(&mut self, statement: Statement) -> Result<Statement> {
+        Ok(match statement {
+            Statement::Let {
+                identifier,
+                expression,
+            } => Statement::Let {
+                identifier,
+                expression: self.expression(expression)?,
+            },
+            Statement::Return(expression) => Statement::Return(self.expression(expression)?),
+            Statement::Expression(expression) => {
+                Statement::Expression(self.expression(expression)?)
+            }
+            Statement::Block(block) => Statement::Block(self.block(block)?),
+            Statement::Defer(expression) => Statement::Defer(self.expression(expression)?),
+            Statement::While { condition, body } => Statement::While {
+                condition: self.expression(condition)?,
+                body: self.block(body)?,
+            },
+            Statement::For {
+                variable,
+                value,
+                iterable,
+                body,
+            } => Statement::For {
+                variable,
+                value,
+                iterable: self.expression(iterable)?,
+                body: self.block(body)?,
+            },
+            statement @ (Statement::Break | Statement::Continue) => statement,
+        })
+    }
+
+    fn block(&mut self, block: Block) -> Result<Block> {
+        let statements = block
+            .0
+            .into_iter()
+            .map(|statement| self.statement(statement));
+        Ok(Block(statements.collect::<Result<_>>()?))
+    }
+
+    fn boxed(&mut self, expression: Expression) -> Result<Box<Expression>> {
+        Ok(self.expression(expression)?.boxed())
+    }
+
+    fn all(&mut self, expressions: Vec<Expression>) -> Result<Vec<Expression>> {
+        (expressions.into_iter())
+            .map(|expression| self.expression(expression))
+            .collect()
+    }
+
+    fn expression(&mut self, expression: Expression) -> Result<Expression> {
+        if self.depth >= MAX_DEPTH {
+            bail!("stack overflow: max recursion depth exceeded");
+        }
+        self.depth += 1;
+        let rebuilt = self.nested(expression);
+        self.depth -= 1;
+        rebuilt
+    }
+
+    fn nested(&mut self, expression: Expression) -> Result<Expression> {
+        let expression = match expression {
+            Expression::Identifier(_) | Expression::Literal(_) => expression,
+            Expression::Prefix(prefix) => Expression::Prefix(Prefix {
+                expression: self.boxed(*prefix.expression)?,
+                operation: prefix.operation,
+            }),
+            Expression::Infix(infix) => return self.chain(infix),
+            Expression::If(if_expression) => Expression::If(If {
+                condition: self.boxed(*if_expression.condition)?,
+                consequence: self.block(if_expression.consequence)?,
+                alternative: match if_expression.alternative {
+                    Some(block) => Some(self.block(block)?),
+                    None => None,
+                },
+            }),
+            Expression::Function(mut function) => {
+                function.body = Rc::new(self.block(Rc::unwrap_or_clone(function.body))?);
+                Expression::Function(function)
+            }
+            Expression::Macro(mut function) => {
+                function.body = Rc::new(self.block(Rc::unwrap_or_clone(function.body))?);
+                Expression::Macro(function)
+            }
+            Expression::Call(call) => Expression::Call(Call {
+                function: self.boxed(*call.function)?,
+                arguments: self.all(call.arguments)?,
+            }),
+            Expression::Array(elements) => Expression::Array(self.all(elements)?),
+            Expression::Comprehension(comprehension) => Expression::Comprehension(Comprehension {
+                element: self.boxed(*comprehension.element)?,
+                variable: comprehension.variable,
+                value: comprehension.value,
+                iterable: self.boxed(*comprehension.iterable)?,
+                condition: comprehension
+                    .condition
+                    .map(|condition| self.boxed(*condition))
+                    .transpose()?,
+            }),
+            Expression::Index(index) => Expression::Index(Index {
+                left: self.boxed(*index.left)?,
+                index: self.boxed(*index.index)?,
+            }),
+            Expression::Assign(name, value) => Expression::Assign(name, self.boxed(*value)?),
+            Expression::Hash(pairs) => Expression::Hash(
+                pairs
+                    .into_iter()
+                    .map(|(key, value)| Ok((self.expression(key)?, self.expression(value)?)))
+                    .collect::<Result<_>>()?,
+            ),
+        };
+        (self.f)(expression)
+    }
+
+    /// Rebuilds the infix chain down the left of `infix`, like `1 + 2 + 3`, in a loop, as
+    /// chains can be far longer than the parser lets expressions nest.
+    fn chain(&mut self, infix: Infix) -> Result<Expression> {
+        let mut chain = vec![];
+        let mut innermost = Expression::Infix(infix);
+        while let Expression::Infix(infix) = innermost {
+            innermost = *infix.left_expression;
+            chain.push((infix.right_expression, infix.operation));
+        }
+        let mut left = self.expression(innermost)?;
+        while let Some((right, operation)) = chain.pop() {
+            let infix = Infix {
+                left_expression: left.boxed(),
+                right_expression: self.boxed(*right)?,
+                operation,
+            };
+            left = (self.f)(Expression::Infix(infix))?;
+        }
+        Ok(left)
+    }
+}
+
+#[cfg(test)]
+mod macros_tests {
+    use alloc::rc::Rc;
+    use core::cell::RefCell;
+
+    use anyhow::Result;
+
+    use crate::{
+        environment::Environment,
+        eval::{Engine, Program},
+        lexer::Lexer,
+        object::Object,
+        parser::Parser,
+        prelude::*,
+    };
+
+    fn run(engine: Engine, text: &str) -> Result<Object> {
+        let mut parser = Parser::new(Lexer::new(text).tokens());
+        let env = Rc::new(RefCell::new(Environment::default()));
+        Program::with_engine(engine).eval(&mut parser, env)
+    }
+
+    fn eval(text: &str) -> String {
+        run(Engine::Tree, text).unwrap().to_string()
+    }
+
+    #[test]
+    fn quote_and_unquote() {
+        assert_eq!(eval("quote(5)"), "QUOTE(5)");
+        assert_eq!(eval("quote(5 + foobar)"), "QUOTE((5+foobar))");
+        assert_eq!(eval("quote(unquote(4 + 4))"), "QUOTE(8)");
+        assert_eq!(
+            eval("let q = quote(4 + 4); quote(unquote(4 + 4) + unquote(q))"),
+            "QUOTE((8+(4+4)))"
+        );
+        assert_eq!(eval("quote(unquote(1 > 2))"), "QUOTE(false)");
+    }
+
+    const UNLESS: &str = "let unless = macro(condition, consequence, alternative) {
+        quote(if (!(unquote(condition))) { unquote(consequence) } else { unquote(alternative) })
+    };";
+
+    #[test]
+    fn expansion_depth_is_limited() {
+        use crate::ast::{Expression, Literal, Prefix, PrefixOperation, Statement};
+
+        // Chains are rebuilt in a loop, however long.
+        let chain = vec!["1"; 20_000].join(" + ");
+        assert_eq!(eval(&format!("{UNLESS} {chain}")), "20000");
+
+        // Anything else nested deeper than the parser allows is an error.
+        let env = Rc::new(RefCell::new(Environment::default()));
+        env.borrow().runtime.macros.set(true);
+        let nested = (0..crate::parser::MAX_DEPTH).fold(
+            Expression::Literal(Literal::Int(1)),
+            |expression, _| {
+                Expression::Prefix(Prefix {
+                    expression: expression.boxed(),
+                    operation: PrefixOperation::Minus,
+                })
+            },
+        );
+        let err = super::process(Statement::Expression(nested), &env).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "stack overflow: max recursion depth exceeded"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn macros_expand_before_evaluation() {
+        assert_eq!(eval(&format!("{UNLESS} unless(10 > 5, 0, 1)")), "1");
+        assert_eq!(eval(&format!("{UNLESS} unless(10 < 5, 0, 1)")), "0");
+        // Arguments reach the macro unevaluated.
+        assert_eq!(
+            eval("let ignore = macro(x) { quote(1) }; ignore(missing)"),
+            "1"
+        );
+        assert_eq!(eval("let m = macro(x) { x }; m(1)"), "1");
+        let err = run(Engine::Tree, "let m = macro(x) { 1 }; m(2)").unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            "macro m must return a quoted expression"
+        );
+        // A rest parameter collects the quoted arguments left over.
+        assert_eq!(
+            eval(
+                "let last = macro(first, ...rest) { rest[len(rest) - 1] }; last(1, missing, 2 + 3)"
+            ),
+            "5"
+        );
+        let err = run(Engine::Tree, "let m = macro(a, ...b) { a }; m()").unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            "wrong number of arguments to macro m: expected at least 1, got 0"
+        );
+    }
+
+    #[cfg(feature = "vm")]
+    #[test]
+    fn macros_expand_for_the_vm() {
+        let value = run(Engine::Vm, &format!("{UNLESS} unless(10 > 5, 0, 1)")).unwrap();
+        assert_eq!(value, Object::Int(1));
+    }
+}
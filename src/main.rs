@@ -1,6 +1,334 @@
-use rustmonk::repl::Repl;
+use std::{cell::RefCell, env, fs, path::Path, process::ExitCode, rc::Rc};
 
-fn main() {
-    let repl = Repl {};
-    repl.start();
+use anyhow::{anyhow, bail, Context, Result};
+use rustmonk::{
+    bench::{self, BenchOptions},
+    callgraph, conformance,
+    environment::Environment,
+    eval::{self, Engine, EvalError, Program},
+    formatter::{self, FormatOptions},
+    grammar, highlighting,
+    lexer::Lexer,
+    parser::Parser,
+    refactor,
+    repl::Repl,
+    runtime::Runtime,
+    stats, stdlib,
+    store::FileStore,
+};
+
+const USAGE: &str =
+    "usage: rustmonk [--no-stdlib] [[run] [--vm] [--opt=N] [--store DB] [--plugin LIB] [--dump-ast] [--trace-nil] FILE | bench [--warmup N] [--iterations N] [--vm] [--opt=N] FILE \
+                     | stats FILE | conformance [--vm] [DIR] | grammar [--json] | syntax FILE \
+                     | gen-syntax --format=tmlanguage|vim | refactor rename OLD NEW FILE \
+                     | fmt [--indent N] [--no-operator-spaces] [--no-semicolons] FILE | explain CODE \
+                     | graph FILE [--format=dot]]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    // `--no-stdlib` comes before the subcommand; only the REPL and `run` load the library.
+    let (load_stdlib, args) = match args.split_first() {
+        Some((flag, rest)) if flag == "--no-stdlib" => (false, rest),
+        _ => (true, &args[..]),
+    };
+    let result = match args.first().map(String::as_str) {
+        None => {
+            let mut repl = Repl::default();
+            repl.config.stdlib = load_stdlib;
+            repl.start();
+            Ok(())
+        }
+        Some("run") => run_file(&args[1..], load_stdlib),
+        Some("bench") => run_bench(&args[1..]),
+        Some("stats") => run_stats(&args[1..]),
+        Some("conformance") => run_conformance(&args[1..]),
+        Some("grammar") => run_grammar(&args[1..]),
+        Some("syntax") => run_syntax(&args[1..]),
+        Some("gen-syntax") => run_gen_syntax(&args[1..]),
+        Some("refactor") => run_refactor(&args[1..]),
+        Some("fmt") => run_fmt(&args[1..]),
+        Some("explain") => run_explain(&args[1..]),
+        Some("graph") => run_graph(&args[1..]),
+        Some(_) => run_file(args, load_stdlib),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            match err.downcast_ref::<EvalError>() {
+                Some(kind) => eprintln!("error[{}]: {err:#}", kind.code()),
+                None => eprintln!("error: {err:#}"),
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_file(args: &[String], mut load_stdlib: bool) -> Result<()> {
+    let mut engine = Engine::default();
+    let mut opt = 0;
+    let mut runtime = Runtime::default();
+    let mut dump_ast = false;
+    let mut args = args.iter();
+    let file = loop {
+        match args.next().map(String::as_str) {
+            Some("--vm") => engine = vm_engine()?,
+            Some(flag) if flag.starts_with("--opt=") => opt = opt_level(flag)?,
+            Some("--dump-ast") => dump_ast = true,
+            Some("--trace-nil") => runtime.trace_nil = true,
+            Some("--no-stdlib") => load_stdlib = false,
+            Some("--store") => {
+                let path = args.next().ok_or_else(|| anyhow!("{USAGE}"))?;
+                runtime.store = Some(FileStore::open(path)?);
+            }
+            Some("--plugin") => {
+                let path = args.next().ok_or_else(|| anyhow!("{USAGE}"))?;
+                load_plugin(path, &runtime)?;
+            }
+            Some(file) if !file.starts_with("--") && args.len() == 0 => break file,
+            _ => bail!("{USAGE}"),
+        }
+    };
+    let source = fs::File::open(file).with_context(|| format!("reading {file}"))?;
+    let mut parser = Parser::new(Lexer::from_reader(source).tokens());
+    if dump_ast {
+        return dump(&mut parser);
+    }
+    let env = Rc::new(RefCell::new(Environment::with_runtime(runtime)));
+    if load_stdlib {
+        stdlib::load(&env, engine).context("loading the standard library")?;
+    }
+    let value = Program::with_engine(engine)
+        .optimized(opt >= 1)
+        .inline_hot(opt >= 2)
+        .eval(&mut parser, env)?;
+    println!("{value}");
+    Ok(())
+}
+
+#[cfg(feature = "plugins")]
+fn load_plugin(path: &str, runtime: &Runtime) -> Result<()> {
+    rustmonk::plugin::load(path, &runtime.builtins)
+}
+
+#[cfg(not(feature = "plugins"))]
+fn load_plugin(_path: &str, _runtime: &Runtime) -> Result<()> {
+    bail!("rustmonk was built without the plugins feature")
+}
+
+/// Prints each statement as an indented tree under the position it starts at.
+fn dump(parser: &mut Parser) -> Result<()> {
+    let mut trees = vec![];
+    while let Some(statement) = parser.next() {
+        if let Ok(statement) = statement {
+            trees.push(format!(
+                "# {}\n{}",
+                parser.statement_span(),
+                statement.to_pretty_string()
+            ));
+        }
+    }
+    if let Some(err) = parser.errors().first() {
+        bail!("{err}");
+    }
+    print!("{}", trees.join("\n"));
+    Ok(())
+}
+
+fn run_stats(args: &[String]) -> Result<()> {
+    let [file] = args else {
+        bail!("{USAGE}");
+    };
+    let source = fs::read_to_string(file).with_context(|| format!("reading {file}"))?;
+    let mut parser = Parser::new(Lexer::new(&source).tokens());
+    let statements: Vec<_> = parser.by_ref().flatten().collect();
+    if let Some(err) = parser.errors().first() {
+        bail!("{err}");
+    }
+    print!("{}", stats::collect(&statements));
+    Ok(())
+}
+
+fn run_graph(args: &[String]) -> Result<()> {
+    let file = match args {
+        [file] => file,
+        [file, flag] | [flag, file] if flag == "--format=dot" => file,
+        _ => bail!("{USAGE}"),
+    };
+    let source = fs::read_to_string(file).with_context(|| format!("reading {file}"))?;
+    let mut parser = Parser::new(Lexer::new(&source).tokens());
+    let statements: Vec<_> = parser.by_ref().flatten().collect();
+    if let Some(err) = parser.errors().first() {
+        bail!("{err}");
+    }
+    print!("{}", callgraph::call_graph(&statements).to_dot());
+    Ok(())
+}
+
+fn run_syntax(args: &[String]) -> Result<()> {
+    let [file] = args else {
+        bail!("{USAGE}");
+    };
+    let source = fs::read_to_string(file).with_context(|| format!("reading {file}"))?;
+    let mut parser = Parser::new(Lexer::new(&source).tokens()).record_syntax(true);
+    parser.by_ref().for_each(drop);
+    if let Some(err) = parser.errors().first() {
+        bail!("{err}");
+    }
+    if let Some(tree) = parser.syntax_tree() {
+        println!("{tree}");
+    }
+    Ok(())
+}
+
+fn run_refactor(args: &[String]) -> Result<()> {
+    let [command, old, new, file] = args else {
+        bail!("{USAGE}");
+    };
+    if command != "rename" {
+        bail!("{USAGE}");
+    }
+    let source = fs::read_to_string(file).with_context(|| format!("reading {file}"))?;
+    print!("{}", refactor::rename_identifier(&source, old, new)?);
+    Ok(())
+}
+
+fn run_fmt(args: &[String]) -> Result<()> {
+    let mut options = FormatOptions::default();
+    let mut file = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--indent" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| anyhow!("--indent needs a value"))?;
+                options.indent = value
+                    .parse()
+                    .with_context(|| format!("invalid --indent: {value}"))?;
+            }
+            "--no-operator-spaces" => options.operator_spaces = false,
+            "--no-semicolons" => options.semicolons = false,
+            path if file.is_none() && !path.starts_with("--") => file = Some(path),
+            _ => bail!("{USAGE}"),
+        }
+    }
+    let Some(file) = file else {
+        bail!("{USAGE}");
+    };
+    let source = fs::read_to_string(file).with_context(|| format!("reading {file}"))?;
+    print!("{}", formatter::format_with(&source, &options)?);
+    Ok(())
+}
+
+fn run_explain(args: &[String]) -> Result<()> {
+    let [code] = args else {
+        bail!("{USAGE}");
+    };
+    let explanation = eval::explain(code).ok_or_else(|| anyhow!("unknown error code: {code}"))?;
+    println!("{}: {}\n", explanation.code, explanation.title);
+    println!("{}\n", explanation.description);
+    if let Some(example) = explanation.example {
+        println!("Example:\n    {example}\n");
+    }
+    println!("Fix: {}", explanation.fix);
+    Ok(())
+}
+
+fn run_gen_syntax(args: &[String]) -> Result<()> {
+    match args {
+        [flag] if flag == "--format=tmlanguage" => print!("{}", highlighting::tmlanguage()),
+        [flag] if flag == "--format=vim" => print!("{}", highlighting::vim()),
+        _ => bail!("{USAGE}"),
+    }
+    Ok(())
+}
+
+fn run_grammar(args: &[String]) -> Result<()> {
+    match args {
+        [] => print!("{}", grammar::ebnf()),
+        [flag] if flag == "--json" => print!("{}", grammar::json()),
+        _ => bail!("{USAGE}"),
+    }
+    Ok(())
+}
+
+fn run_conformance(args: &[String]) -> Result<()> {
+    let (engine, dir) = match args {
+        [] => (Engine::default(), "conformance"),
+        [flag] if flag == "--vm" => (vm_engine()?, "conformance"),
+        [flag, dir] if flag == "--vm" => (vm_engine()?, dir.as_str()),
+        [dir] if !dir.starts_with("--") => (Engine::default(), dir.as_str()),
+        _ => bail!("{USAGE}"),
+    };
+    let results = conformance::run(Path::new(dir), engine)?;
+    let failed = results.iter().filter(|result| !result.passed()).count();
+    for result in &results {
+        if result.passed() {
+            println!("ok   {}", result.name);
+        } else {
+            println!("FAIL {}", result.name);
+            println!("  expected: {}", result.expected);
+            println!("  actual:   {}", result.actual);
+        }
+    }
+    println!("{} passed, {failed} failed", results.len() - failed);
+    if failed > 0 {
+        bail!("{failed} conformance programs failed");
+    }
+    Ok(())
+}
+
+/// The level `--opt=N` asks for: 1 runs the optimizer, 2 also inlines hot functions on the VM.
+fn opt_level(flag: &str) -> Result<u8> {
+    let level = &flag["--opt=".len()..];
+    match level.parse() {
+        Ok(level @ 0..=2) => Ok(level),
+        _ => bail!("invalid --opt: {level}, expected 0, 1 or 2"),
+    }
+}
+
+fn run_bench(args: &[String]) -> Result<()> {
+    let mut options = BenchOptions::default();
+    let mut file = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut count = |name: &str| -> Result<usize> {
+            let value = args.next().ok_or_else(|| anyhow!("{name} needs a value"))?;
+            value
+                .parse()
+                .with_context(|| format!("invalid {name}: {value}"))
+        };
+        match arg.as_str() {
+            "--warmup" => options.warmup = count("--warmup")?,
+            "--iterations" => options.iterations = count("--iterations")?,
+            "--vm" => options.engine = vm_engine()?,
+            flag if flag.starts_with("--opt=") => options.opt = opt_level(flag)?,
+            path if file.is_none() && !path.starts_with("--") => file = Some(path.to_string()),
+            _ => bail!("{USAGE}"),
+        }
+    }
+    let Some(file) = file else {
+        bail!("{USAGE}");
+    };
+    let source = fs::read_to_string(&file).with_context(|| format!("reading {file}"))?;
+    let report = bench::run(&source, &options)?;
+
+    println!("{file}: {} iterations", options.iterations);
+    println!("  min    {:?}", report.min);
+    println!("  median {:?}", report.median);
+    println!("  max    {:?}", report.max);
+    if let Some(instructions) = report.instructions {
+        println!("  instructions {instructions}");
+    }
+    Ok(())
+}
+
+#[cfg(feature = "vm")]
+fn vm_engine() -> Result<Engine> {
+    Ok(Engine::Vm)
+}
+
+#[cfg(not(feature = "vm"))]
+fn vm_engine() -> Result<Engine> {
+    bail!("rustmonk was built without the vm feature")
 }
@@ -1,15 +1,133 @@
-use anyhow::{anyhow, Result};
-use std::fmt::Display;
+use alloc::{collections::BTreeMap, rc::Rc};
+use anyhow::Result;
+use core::{cell::RefCell, fmt::Debug, fmt::Display, ops::Deref};
 
-use crate::{ast::Block, environment::GlobalEnv, token::Identifier};
+use crate::{
+    ast::{self, Block, Expression, InfixOperation, PrefixOperation},
+    builtins::Builtin,
+    environment::GlobalEnv,
+    eval::EvalError,
+    prelude::*,
+    token::{Identifier, Int},
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Object {
     Nil,
-    Int(i64),
+    Int(Int),
+    Float(f64),
     Bool(bool),
+    String(Rc<str>),
+    Array(Shared<Vec<Object>>),
+    Buffer(Rc<RefCell<String>>),
+    Hash(Shared<BTreeMap<HashKey, Object>>),
     Return(Box<Object>),
+    /// Signals from `break` and `continue` on their way to the enclosing loop.
+    Break,
+    Continue,
     Function(Function),
+    /// A `macro` literal bound with `let`, expanded before evaluation.
+    Macro(Function),
+    /// The unevaluated expression produced by `quote`.
+    Quote(Rc<Expression>),
+    Builtin(Builtin),
+    Handle(Handle),
+    CompiledFunction(Rc<CompiledFunction>),
+    Closure(Rc<Closure>),
+    Coroutine(Coroutine),
+    /// A variable the VM shares between a function and the closures it makes, because one
+    /// of them assigns it. Programs only ever see what it holds.
+    Cell(Rc<RefCell<Object>>),
+}
+
+/// Declares [`ObjectKind`] from one row per [`Object`] variant, so a new variant can't
+/// compile until it has a name and a decision on whether it can key a hash.
+macro_rules! object_kinds {
+    ($($variant:ident => $name:literal, hashable: $hashable:literal;)*) => {
+        /// The variant of an [`Object`], without its value.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum ObjectKind {
+            $($variant),*
+        }
+
+        impl ObjectKind {
+            pub const ALL: &'static [ObjectKind] = &[$(ObjectKind::$variant),*];
+
+            /// The type name used in error messages.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(ObjectKind::$variant => $name),*
+                }
+            }
+
+            /// Whether values of this kind can be used as hash keys.
+            pub fn hashable(self) -> bool {
+                match self {
+                    $(ObjectKind::$variant => $hashable),*
+                }
+            }
+        }
+
+        impl Object {
+            pub fn kind(&self) -> ObjectKind {
+                match self {
+                    $(Object::$variant { .. } => ObjectKind::$variant),*
+                }
+            }
+        }
+    };
+}
+
+object_kinds! {
+    Nil => "nil", hashable: false;
+    Int => "int", hashable: true;
+    Float => "float", hashable: false;
+    Bool => "bool", hashable: true;
+    String => "string", hashable: true;
+    Array => "array", hashable: false;
+    Buffer => "buffer", hashable: false;
+    Hash => "hash", hashable: false;
+    Return => "return", hashable: false;
+    Break => "break", hashable: false;
+    Continue => "continue", hashable: false;
+    Function => "fn", hashable: false;
+    Macro => "macro", hashable: false;
+    Quote => "quote", hashable: false;
+    Builtin => "builtin", hashable: false;
+    Handle => "handle", hashable: false;
+    CompiledFunction => "fn", hashable: false;
+    Closure => "fn", hashable: false;
+    Coroutine => "coroutine", hashable: false;
+    Cell => "cell", hashable: false;
+}
+
+/// Hashes are ordered by key, ints first, then booleans, then strings, so they iterate
+/// and print the same way on every run and platform whatever the insertion order.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HashKey {
+    Int(Int),
+    Bool(bool),
+    String(String),
+}
+
+impl From<HashKey> for Object {
+    fn from(key: HashKey) -> Self {
+        match key {
+            HashKey::Int(value) => Object::Int(value),
+            HashKey::Bool(value) => Object::Bool(value),
+            HashKey::String(value) => Object::String(value.into()),
+        }
+    }
+}
+
+impl Display for HashKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            HashKey::Int(value) => write!(f, "{value}"),
+            HashKey::Bool(value) => write!(f, "{value}"),
+            HashKey::String(value) => write!(f, "{value}"),
+        }
+    }
 }
 
 impl PartialEq for Function {
@@ -20,25 +138,180 @@ impl PartialEq for Function {
 
 #[derive(Debug, Clone)]
 pub struct Function {
-    pub parameters: Vec<Identifier>,
-    pub body: Block,
+    pub parameters: Rc<[Identifier]>,
+    /// Bound to an array of the arguments after `parameters`.
+    pub rest: Option<Identifier>,
+    pub body: Rc<Block>,
     pub env: GlobalEnv,
+    pub escapes: bool,
 }
 
-impl Display for Function {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "fn(")?;
-        for (index, identifier) in self.parameters.iter().enumerate() {
-            write!(f, "{identifier}")?;
-            if index != self.parameters.len() - 1 {
-                write!(f, ", ")?;
-            }
+/// A function lowered to bytecode by the compiler.
+#[derive(Debug, PartialEq)]
+pub struct CompiledFunction {
+    pub instructions: Vec<u8>,
+    pub num_locals: usize,
+    pub num_parameters: usize,
+    /// Whether the arguments after the parameters are passed as an array, the last local.
+    pub rest: bool,
+}
+
+/// A compiled function together with the free variables it captured.
+#[derive(Debug, PartialEq)]
+pub struct Closure {
+    pub function: Rc<CompiledFunction>,
+    pub free: Vec<Object>,
+}
+
+/// A closure `resume` runs on a stack of its own, up to each call to `yield`.
+#[derive(Debug, Clone)]
+pub struct Coroutine(pub Rc<RefCell<CoroutineState>>);
+
+#[derive(Debug)]
+pub enum CoroutineState {
+    /// Not resumed yet.
+    New(Rc<Closure>),
+    /// Waiting in `yield` for the next `resume`.
+    #[cfg(feature = "vm")]
+    Suspended(crate::vm::Suspended),
+    Running,
+    Done,
+}
+
+impl Coroutine {
+    pub fn new(closure: Rc<Closure>) -> Self {
+        Self(Rc::new(RefCell::new(CoroutineState::New(closure))))
+    }
+}
+
+impl PartialEq for Coroutine {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+pub trait Resource: Debug {
+    fn kind(&self) -> &'static str;
+    fn read_to_string(&mut self) -> Result<String>;
+    fn write_str(&mut self, text: &str) -> Result<()>;
+}
+
+/// An open resource such as a file. Dropping the last copy closes it.
+#[derive(Debug, Clone)]
+pub struct Handle {
+    pub name: String,
+    resource: Rc<RefCell<Option<Box<dyn Resource>>>>,
+}
+
+impl Handle {
+    pub fn new(name: String, resource: Box<dyn Resource>) -> Self {
+        Self {
+            name,
+            resource: Rc::new(RefCell::new(Some(resource))),
+        }
+    }
+
+    pub fn with<T>(&self, f: impl FnOnce(&mut dyn Resource) -> Result<T>) -> Result<T> {
+        match self.resource.borrow_mut().as_mut() {
+            Some(resource) => f(resource.as_mut()),
+            None => Err(EvalError::UseAfterClose(self.name.clone()).into()),
+        }
+    }
+
+    pub fn close(&self) -> Result<()> {
+        match self.resource.borrow_mut().take() {
+            Some(_) => Ok(()),
+            None => Err(EvalError::UseAfterClose(self.name.clone()).into()),
         }
-        write!(f, ")\n {} \n}}", self.body)
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.resource.borrow().is_none()
+    }
+}
+
+impl PartialEq for Handle {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.resource, &other.resource)
+    }
+}
+
+impl Display for Handle {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.resource.borrow().as_ref() {
+            Some(resource) => write!(f, "<{} {}>", resource.kind(), self.name),
+            None => write!(f, "<closed {}>", self.name),
+        }
+    }
+}
+
+/// Copy-on-write handle: clones share the value until one of them is mutated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shared<T>(Rc<T>);
+
+impl<T: Clone> Shared<T> {
+    pub fn new(value: T) -> Self {
+        Self(Rc::new(value))
+    }
+
+    pub fn make_mut(&mut self) -> &mut T {
+        Rc::make_mut(&mut self.0)
+    }
+
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+
+    pub fn as_ptr(&self) -> *const T {
+        Rc::as_ptr(&self.0)
+    }
+
+    /// How many handles share the value.
+    pub fn strong_count(&self) -> usize {
+        Rc::strong_count(&self.0)
+    }
+}
+
+impl<T> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl Display for Function {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let signature = ast::signature(&self.parameters, self.rest.as_ref());
+        write!(f, "fn({signature})\n {} \n}}", *self.body)
     }
 }
 
 impl Object {
+    pub fn prefix(&self, operation: &PrefixOperation) -> Result<Object> {
+        match operation {
+            PrefixOperation::Bang => self.bang(),
+            PrefixOperation::Minus => self.minus(),
+        }
+    }
+
+    pub fn infix(&self, operation: &InfixOperation, right: Object) -> Result<Object> {
+        match operation {
+            InfixOperation::Add => self.add(right),
+            InfixOperation::Sub => self.sub(right),
+            InfixOperation::Mul => self.mul(right),
+            InfixOperation::Div => self.div(right),
+            InfixOperation::Mod => self.rem(right),
+            InfixOperation::Eq => self.eq(right),
+            InfixOperation::NotEq => self.not_eq(right),
+            InfixOperation::Gt => self.gt(right),
+            InfixOperation::Gte => self.gte(right),
+            InfixOperation::Lt => self.lt(right),
+            InfixOperation::Lte => self.lte(right),
+            _ => Ok(Object::Nil),
+        }
+    }
+
     pub fn bang(&self) -> Result<Object> {
         match self {
             Object::Nil => Ok(Object::Bool(true)),
@@ -50,53 +323,132 @@ impl Object {
                 }
             }
             Object::Int(_) => Ok(Object::Bool(false)),
+            Object::Float(_) => Ok(Object::Bool(false)),
+            Object::String(_) => Ok(Object::Bool(false)),
+            Object::Array(_) => Ok(Object::Bool(false)),
+            Object::Buffer(_) => Ok(Object::Bool(false)),
+            Object::Hash(_) => Ok(Object::Bool(false)),
             Object::Return(_) => Ok(Object::Bool(false)),
+            Object::Break | Object::Continue => Ok(Object::Bool(false)),
             Object::Function(_) => Ok(Object::Bool(false)),
+            Object::Macro(_) => Ok(Object::Bool(false)),
+            Object::Quote(_) => Ok(Object::Bool(false)),
+            Object::Builtin(_) => Ok(Object::Bool(false)),
+            Object::Handle(_) => Ok(Object::Bool(false)),
+            Object::CompiledFunction(_) => Ok(Object::Bool(false)),
+            Object::Closure(_) => Ok(Object::Bool(false)),
+            Object::Coroutine(_) => Ok(Object::Bool(false)),
+            Object::Cell(_) => Ok(Object::Bool(false)),
         }
     }
 
-    pub fn name(&self) -> &str {
+    pub fn is_truthy(&self) -> bool {
         match self {
-            Object::Nil => "nil",
-            Object::Int(_) => "int",
-            Object::Bool(_) => "bool",
-            Object::Return(_) => "return",
-            Object::Function(_) => "fn",
+            Object::Nil => false,
+            Object::Bool(value) => *value,
+            _ => true,
         }
     }
 
+    pub fn name(&self) -> &'static str {
+        self.kind().name()
+    }
+
     pub fn minus(&self) -> Result<Object> {
         match self {
-            Object::Int(value) => Ok(Object::Int(-value)),
-            object => Err(anyhow!("unknown operator -{}", object)),
+            Object::Int(value) => value
+                .checked_neg()
+                .map(Object::Int)
+                .ok_or_else(|| EvalError::IntegerOverflow(format!("-({value})")).into()),
+            Object::Float(value) => Ok(Object::Float(-value)),
+            object => Err(EvalError::UnknownOperator {
+                operator: "-",
+                operand: object.to_string(),
+            }
+            .into()),
+        }
+    }
+
+    fn overflow(&self, operator: &'static str, right: Int) -> anyhow::Error {
+        EvalError::IntegerOverflow(format!("{self} {operator} {right}")).into()
+    }
+
+    fn type_mismatch(&self, operator: &'static str, right: &Object) -> anyhow::Error {
+        EvalError::TypeMismatch {
+            left: self.to_string(),
+            operator,
+            right: right.to_string(),
         }
+        .into()
     }
 
     pub fn add(&self, right: Object) -> Result<Object> {
         match (self, right) {
-            (Object::Int(left), Object::Int(right)) => Ok(Object::Int(left + right)),
-            (x, y) => Err(anyhow!("type mismatch: {x} + {y}")),
+            (Object::Int(left), Object::Int(right)) => left
+                .checked_add(right)
+                .map(Object::Int)
+                .ok_or_else(|| self.overflow("+", right)),
+            (Object::String(left), Object::String(right)) => {
+                Ok(Object::String(format!("{left}{right}").into()))
+            }
+            (left, right) => match promote(left, &right) {
+                Some((left, right)) => Ok(Object::Float(left + right)),
+                None => Err(left.type_mismatch("+", &right)),
+            },
         }
     }
 
     pub fn sub(&self, right: Object) -> Result<Object> {
         match (self, right) {
-            (Object::Int(left), Object::Int(right)) => Ok(Object::Int(left - right)),
-            _ => todo!(),
+            (Object::Int(left), Object::Int(right)) => left
+                .checked_sub(right)
+                .map(Object::Int)
+                .ok_or_else(|| self.overflow("-", right)),
+            (left, right) => match promote(left, &right) {
+                Some((left, right)) => Ok(Object::Float(left - right)),
+                None => Err(left.type_mismatch("-", &right)),
+            },
         }
     }
 
     pub fn mul(&self, right: Object) -> Result<Object> {
         match (self, right) {
-            (Object::Int(left), Object::Int(right)) => Ok(Object::Int(left * right)),
-            _ => todo!(),
+            (Object::Int(left), Object::Int(right)) => left
+                .checked_mul(right)
+                .map(Object::Int)
+                .ok_or_else(|| self.overflow("*", right)),
+            (left, right) => match promote(left, &right) {
+                Some((left, right)) => Ok(Object::Float(left * right)),
+                None => Err(left.type_mismatch("*", &right)),
+            },
         }
     }
 
     pub fn div(&self, right: Object) -> Result<Object> {
         match (self, right) {
-            (Object::Int(left), Object::Int(right)) => Ok(Object::Int(left / right)),
-            _ => todo!(),
+            (Object::Int(_), Object::Int(0)) => Err(EvalError::DivisionByZero.into()),
+            (Object::Int(left), Object::Int(right)) => left
+                .checked_div(right)
+                .map(Object::Int)
+                .ok_or_else(|| self.overflow("/", right)),
+            (left, right) => match promote(left, &right) {
+                Some((left, right)) => Ok(Object::Float(left / right)),
+                None => Err(left.type_mismatch("/", &right)),
+            },
+        }
+    }
+
+    pub fn rem(&self, right: Object) -> Result<Object> {
+        match (self, right) {
+            (Object::Int(_), Object::Int(0)) => Err(EvalError::DivisionByZero.into()),
+            (Object::Int(left), Object::Int(right)) => left
+                .checked_rem(right)
+                .map(Object::Int)
+                .ok_or_else(|| self.overflow("%", right)),
+            (left, right) => match promote(left, &right) {
+                Some((left, right)) => Ok(Object::Float(left % right)),
+                None => Err(left.type_mismatch("%", &right)),
+            },
         }
     }
 
@@ -104,46 +456,204 @@ impl Object {
         match (self, right) {
             (Object::Int(left), Object::Int(right)) => Ok(Object::Bool(left == &right)),
             (Object::Bool(left), Object::Bool(right)) => Ok(Object::Bool(left == &right)),
-            _ => todo!(),
+            (Object::String(left), Object::String(right)) => Ok(Object::Bool(left == &right)),
+            (Object::Array(left), Object::Array(right)) => Ok(Object::Bool(left == &right)),
+            (Object::Hash(left), Object::Hash(right)) => Ok(Object::Bool(left == &right)),
+            (left, right) => match promote(left, &right) {
+                Some((left, right)) => Ok(Object::Bool(left == right)),
+                None => Err(left.type_mismatch("==", &right)),
+            },
         }
     }
     pub fn not_eq(&self, right: Object) -> Result<Object> {
-        self.eq(right)?.bang()
+        self.eq(right.clone())
+            .map_err(|_| self.type_mismatch("!=", &right))?
+            .bang()
     }
     pub fn gt(&self, right: Object) -> Result<Object> {
         match (self, right) {
             (Object::Int(left), Object::Int(right)) => Ok(Object::Bool(left > &right)),
-            _ => todo!(),
+            (left, right) => match promote(left, &right) {
+                Some((left, right)) => Ok(Object::Bool(left > right)),
+                None => Err(left.type_mismatch(">", &right)),
+            },
         }
     }
 
     pub fn lt(&self, right: Object) -> Result<Object> {
         match (self, right) {
             (Object::Int(left), Object::Int(right)) => Ok(Object::Bool(left < &right)),
-            _ => todo!(),
+            (left, right) => match promote(left, &right) {
+                Some((left, right)) => Ok(Object::Bool(left < right)),
+                None => Err(left.type_mismatch("<", &right)),
+            },
         }
     }
 
     pub fn lte(&self, right: Object) -> Result<Object> {
         match (self, right) {
             (Object::Int(left), Object::Int(right)) => Ok(Object::Bool(left <= &right)),
-            _ => todo!(),
+            (left, right) => match promote(left, &right) {
+                Some((left, right)) => Ok(Object::Bool(left <= right)),
+                None => Err(left.type_mismatch("<=", &right)),
+            },
+        }
+    }
+
+    /// Whether this is a `return`, `break` or `continue` on its way out of a block.
+    pub fn is_signal(&self) -> bool {
+        matches!(self, Object::Return(_) | Object::Break | Object::Continue)
+    }
+
+    /// The values a `for` loop over this object steps through, `width` at a time: the
+    /// elements of an array or keys of a hash, each after its index or followed by its value
+    /// when `width` is 2.
+    pub fn loop_items(self, width: usize) -> Result<Shared<Vec<Object>>> {
+        match (self, width) {
+            (Object::Array(items), 1) => Ok(items),
+            (Object::Array(items), _) => Ok(Shared::new(
+                (0..)
+                    .map(Object::Int)
+                    .zip(items.iter().cloned())
+                    .flat_map(|(index, item)| [index, item])
+                    .collect(),
+            )),
+            (Object::Hash(pairs), 1) => Ok(Shared::new(
+                pairs.keys().cloned().map(Object::from).collect(),
+            )),
+            (Object::Hash(pairs), _) => Ok(Shared::new(
+                pairs
+                    .iter()
+                    .flat_map(|(key, value)| [key.clone().into(), value.clone()])
+                    .collect(),
+            )),
+            (other, _) => Err(EvalError::NotIterable(other.name()).into()),
+        }
+    }
+
+    pub fn hash_key(&self) -> Result<HashKey> {
+        match self {
+            Object::Int(value) => Ok(HashKey::Int(*value)),
+            Object::Bool(value) => Ok(HashKey::Bool(*value)),
+            Object::String(value) => Ok(HashKey::String(value.to_string())),
+            object => Err(EvalError::UnusableAsHashKey(object.name()).into()),
+        }
+    }
+
+    pub fn index(&self, index: Object) -> Result<Object> {
+        match (self, index) {
+            (Object::Hash(pairs), key) => {
+                Ok(pairs.get(&key.hash_key()?).cloned().unwrap_or(Object::Nil))
+            }
+            (Object::Array(elements), Object::Int(index)) => Ok(usize::try_from(index)
+                .ok()
+                .and_then(|index| elements.get(index))
+                .cloned()
+                .unwrap_or(Object::Nil)),
+            (left, index) => Err(EvalError::IndexNotSupported {
+                left: left.name(),
+                index: index.name(),
+            }
+            .into()),
         }
     }
 
     pub fn gte(&self, right: Object) -> Result<Object> {
         match (self, right) {
             (Object::Int(left), Object::Int(right)) => Ok(Object::Bool(left >= &right)),
-            _ => todo!(),
+            (left, right) => match promote(left, &right) {
+                Some((left, right)) => Ok(Object::Bool(left >= right)),
+                None => Err(left.type_mismatch(">=", &right)),
+            },
+        }
+    }
+}
+
+/// Both operands as floats when one is a float and the other a number.
+fn promote(left: &Object, right: &Object) -> Option<(f64, f64)> {
+    match (left, right) {
+        (Object::Float(left), Object::Float(right)) => Some((*left, *right)),
+        (Object::Float(left), Object::Int(right)) => Some((*left, *right as f64)),
+        (Object::Int(left), Object::Float(right)) => Some((*left as f64, *right)),
+        _ => None,
+    }
+}
+
+/// How `inspect` writes a value: as Monkey source that evaluates back to an equal value
+/// for nil, booleans, ints, finite floats, strings, and arrays and hashes of those. Other
+/// values are written as they display.
+pub struct Inspect<'a>(pub &'a Object);
+
+impl Display for Inspect<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            Object::String(value) => write!(f, "{}", Quoted(value)),
+            // Its magnitude is one more than the largest int literal.
+            Object::Int(Int::MIN) => write!(f, "{} - 1", Int::MIN + 1),
+            Object::Array(elements) => {
+                f.write_str("[")?;
+                for (index, element) in elements.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", Inspect(element))?;
+                }
+                f.write_str("]")
+            }
+            Object::Hash(pairs) => {
+                f.write_str("{")?;
+                for (index, (key, value)) in pairs.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    let key = Object::from(key.clone());
+                    write!(f, "{}: {}", Inspect(&key), Inspect(value))?;
+                }
+                f.write_str("}")
+            }
+            object => write!(f, "{object}"),
+        }
+    }
+}
+
+/// A string written as a Monkey string literal, with quotes and escapes.
+pub struct Quoted<'a>(pub &'a str);
+
+impl Display for Quoted<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("\"")?;
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                c => write!(f, "{c}")?,
+            }
+        }
+        f.write_str("\"")
+    }
+}
+
+impl Object {
+    /// Writes the object as an element of an array or hash, where strings are quoted so the
+    /// whole value reads back as a literal.
+    fn fmt_element(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Object::String(value) => write!(f, "{}", Quoted(value)),
+            object => write!(f, "{object}"),
         }
     }
 }
 
 impl Display for Object {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Object::Nil => write!(f, "nil"),
             Object::Int(value) => write!(f, "{value}"),
+            // The shortest digits that read back as the same float, in no particular locale.
+            Object::Float(value) => write!(f, "{value:?}"),
             Object::Bool(value) => {
                 if value == &true {
                     write!(f, "true")
@@ -151,8 +661,236 @@ impl Display for Object {
                     write!(f, "false")
                 }
             }
+            Object::String(value) => write!(f, "{value}"),
+            Object::Array(elements) => {
+                write!(f, "[")?;
+                for (index, element) in elements.iter().enumerate() {
+                    element.fmt_element(f)?;
+                    if index != elements.len() - 1 {
+                        write!(f, ", ")?;
+                    }
+                }
+                write!(f, "]")
+            }
+            Object::Buffer(buffer) => write!(f, "{}", buffer.borrow()),
+            Object::Hash(pairs) => {
+                write!(f, "{{")?;
+                for (index, (key, value)) in pairs.iter().enumerate() {
+                    match key {
+                        HashKey::String(key) => write!(f, "{}: ", Quoted(key))?,
+                        key => write!(f, "{key}: ")?,
+                    }
+                    value.fmt_element(f)?;
+                    if index != pairs.len() - 1 {
+                        write!(f, ", ")?;
+                    }
+                }
+                write!(f, "}}")
+            }
             Object::Return(ret) => write!(f, "return {ret}"),
+            Object::Break => f.write_str("break"),
+            Object::Continue => f.write_str("continue"),
             Object::Function(func) => write!(f, "{func}"),
+            Object::Macro(func) => {
+                let signature = ast::signature(&func.parameters, func.rest.as_ref());
+                write!(f, "macro({signature})")
+            }
+            Object::Quote(expression) => write!(f, "QUOTE({expression})"),
+            Object::Builtin(builtin) => write!(f, "builtin {}", builtin.name),
+            Object::Handle(handle) => write!(f, "{handle}"),
+            Object::CompiledFunction(function) => {
+                write!(f, "CompiledFunction[{:p}]", Rc::as_ptr(function))
+            }
+            Object::Closure(closure) => write!(f, "Closure[{:p}]", Rc::as_ptr(closure)),
+            Object::Coroutine(coroutine) => write!(f, "Coroutine[{:p}]", Rc::as_ptr(&coroutine.0)),
+            Object::Cell(cell) => write!(f, "{}", cell.borrow()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod object_tests {
+    use alloc::{collections::BTreeMap, rc::Rc};
+    use core::cell::RefCell;
+
+    use proptest::prelude::*;
+
+    use super::{
+        Closure, CompiledFunction, Coroutine, Function, Handle, HashKey, Inspect, Object,
+        ObjectKind, Shared,
+    };
+    use crate::{
+        ast::{Block, Expression, Literal},
+        builtins,
+        prelude::*,
+        token::Int,
+    };
+
+    fn sample(kind: ObjectKind) -> Object {
+        let function = || Function {
+            parameters: Rc::from([]),
+            rest: None,
+            body: Rc::new(Block(vec![])),
+            env: Default::default(),
+            escapes: false,
+        };
+        let compiled = || {
+            Rc::new(CompiledFunction {
+                instructions: vec![],
+                num_locals: 0,
+                num_parameters: 0,
+                rest: false,
+            })
+        };
+        match kind {
+            ObjectKind::Nil => Object::Nil,
+            ObjectKind::Int => Object::Int(1),
+            ObjectKind::Float => Object::Float(1.5),
+            ObjectKind::Bool => Object::Bool(true),
+            ObjectKind::String => Object::String("a".into()),
+            ObjectKind::Array => Object::Array(Shared::new(vec![Object::Nil])),
+            ObjectKind::Buffer => Object::Buffer(Rc::new(RefCell::new("b".into()))),
+            ObjectKind::Hash => Object::Hash(Shared::new(BTreeMap::new())),
+            ObjectKind::Return => Object::Return(Box::new(Object::Int(1))),
+            ObjectKind::Break => Object::Break,
+            ObjectKind::Continue => Object::Continue,
+            ObjectKind::Function => Object::Function(function()),
+            ObjectKind::Macro => Object::Macro(function()),
+            ObjectKind::Quote => Object::Quote(Rc::new(Expression::Literal(Literal::Int(1)))),
+            ObjectKind::Builtin => Object::Builtin(builtins::lookup("len").unwrap()),
+            ObjectKind::Handle => Object::Handle(Handle::new("h".into(), Box::new(NoResource))),
+            ObjectKind::CompiledFunction => Object::CompiledFunction(compiled()),
+            ObjectKind::Closure => Object::Closure(Rc::new(Closure {
+                function: compiled(),
+                free: vec![],
+            })),
+            ObjectKind::Coroutine => Object::Coroutine(Coroutine::new(Rc::new(Closure {
+                function: compiled(),
+                free: vec![],
+            }))),
+            ObjectKind::Cell => Object::Cell(Rc::new(RefCell::new(Object::Int(1)))),
+        }
+    }
+
+    #[derive(Debug)]
+    struct NoResource;
+
+    impl super::Resource for NoResource {
+        fn kind(&self) -> &'static str {
+            "none"
+        }
+
+        fn read_to_string(&mut self) -> anyhow::Result<String> {
+            unreachable!()
+        }
+
+        fn write_str(&mut self, _text: &str) -> anyhow::Result<()> {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn every_kind_is_complete() {
+        for &kind in ObjectKind::ALL {
+            let object = sample(kind);
+            assert_eq!(object.kind(), kind);
+            assert!(!object.to_string().is_empty(), "{kind:?} has no display");
+            assert_eq!(object.name(), kind.name());
+            assert_eq!(object.hash_key().is_ok(), kind.hashable(), "{kind:?}");
+            let _ = object.is_truthy();
+        }
+    }
+
+    #[test]
+    fn shared_copy_on_write() {
+        let original = Shared::new(vec![1, 2, 3]);
+        let mut copy = original.clone();
+        assert!(copy.ptr_eq(&original));
+
+        copy.make_mut().push(4);
+        assert!(!copy.ptr_eq(&original));
+        assert_eq!(*original, vec![1, 2, 3]);
+        assert_eq!(*copy, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn variable_access_shares_function_data() {
+        use crate::{environment::Environment, eval::Program, lexer::Lexer, parser::Parser};
+
+        let env = Rc::new(RefCell::new(Environment::default()));
+        let source = "let add = fn(a, b) { a + b };";
+        Program::default()
+            .eval(&mut Parser::new(Lexer::new(source).tokens()), env.clone())
+            .unwrap();
+        let (Some(Object::Function(first)), Some(Object::Function(second))) =
+            (env.borrow().get("add"), env.borrow().get("add"))
+        else {
+            panic!("add is not a function");
+        };
+        assert!(Rc::ptr_eq(&first.parameters, &second.parameters));
+        assert!(Rc::ptr_eq(&first.body, &second.body));
+    }
+
+    #[test]
+    fn evaluating_a_function_literal_shares_its_body() {
+        use crate::{environment::Environment, eval::Program, lexer::Lexer, parser::Parser};
+
+        let env = Rc::new(RefCell::new(Environment::default()));
+        let source =
+            "let make = fn() { fn(x) { x * 2 } }; let first = make(); let second = make();";
+        Program::default()
+            .eval(&mut Parser::new(Lexer::new(source).tokens()), env.clone())
+            .unwrap();
+        let (Some(Object::Function(first)), Some(Object::Function(second))) =
+            (env.borrow().get("first"), env.borrow().get("second"))
+        else {
+            panic!("make does not return functions");
+        };
+        assert!(Rc::ptr_eq(&first.body, &second.body));
+    }
+
+    fn hash_key() -> impl Strategy<Value = HashKey> {
+        prop_oneof![
+            any::<Int>().prop_map(HashKey::Int),
+            any::<bool>().prop_map(HashKey::Bool),
+            any::<String>().prop_map(HashKey::String),
+        ]
+    }
+
+    /// Nil, booleans, numbers, strings, and arrays and hashes of those.
+    fn data() -> impl Strategy<Value = Object> {
+        let leaf = prop_oneof![
+            Just(Object::Nil),
+            any::<bool>().prop_map(Object::Bool),
+            any::<Int>().prop_map(Object::Int),
+            prop_oneof![Just(Int::MIN), Just(Int::MAX)].prop_map(Object::Int),
+            any::<f64>()
+                .prop_filter("NaN and infinities have no literal", |float| float
+                    .is_finite())
+                .prop_map(Object::Float),
+            any::<String>().prop_map(|string| Object::String(string.into())),
+        ];
+        leaf.prop_recursive(4, 32, 6, |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 0..6)
+                    .prop_map(|elements| Object::Array(Shared::new(elements))),
+                prop::collection::btree_map(hash_key(), inner, 0..6)
+                    .prop_map(|pairs| Object::Hash(Shared::new(pairs))),
+            ]
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn inspect_evaluates_back_to_the_value(value in data()) {
+            use crate::{environment::Environment, eval::Program, lexer::Lexer, parser::Parser};
+
+            let source = Inspect(&value).to_string();
+            let env = Rc::new(RefCell::new(Environment::default()));
+            let evaluated = Program::default()
+                .eval(&mut Parser::new(Lexer::new(&source).tokens()), env)
+                .map_err(|err| TestCaseError::fail(format!("{source}: {err}")))?;
+            prop_assert_eq!(evaluated, value, "{}", source);
         }
     }
 }
@@ -0,0 +1,243 @@
+use alloc::{collections::BTreeMap, rc::Rc};
+use anyhow::{bail, Result};
+
+use crate::{
+    ast::{Block, Expression, Function, If, Literal, Statement},
+    macros,
+    object::Object,
+    prelude::*,
+    token::Identifier,
+    visitor::{walk_expression, walk_function, walk_statement, Visitor},
+};
+
+/// Folds constant sub-expressions of `statement`: `2 * 3 + 4` becomes `10`, `!true` becomes
+/// `false`, and an `if` whose condition is a literal keeps only the branch that runs.
+/// Operations that would fail at runtime, such as `1 / 0`, are left for the evaluator to
+/// report.
+pub fn optimize(statement: Statement) -> Statement {
+    macros::modify_statement(statement, &mut |expression| Ok(fold(expression)))
+        .expect("folding never fails")
+}
+
+/// Specializes `function` for the arguments that are known ahead of the call: `arguments`
+/// lines up with the parameters, and each `Some` parameter is dropped from the result and
+/// its value folded into the body. The specialized function takes the remaining parameters
+/// in their original order.
+pub fn specialize(function: &Function, arguments: &[Option<Literal>]) -> Result<Function> {
+    if arguments.len() > function.params.len() {
+        bail!(
+            "cannot specialize a function of {} parameters with {} arguments",
+            function.params.len(),
+            arguments.len()
+        );
+    }
+    let mut rebound = Rebound::default();
+    walk_function(&mut rebound, function);
+
+    let mut params = vec![];
+    let mut bindings = vec![];
+    let mut constants = BTreeMap::new();
+    for (index, param) in function.params.iter().enumerate() {
+        match arguments.get(index).cloned().flatten() {
+            None => params.push(param.clone()),
+            // A name the body binds again or assigns to isn't constant everywhere, so it
+            // keeps a binding of its own instead of being substituted.
+            Some(value) if rebound.0.contains(&param.0) => bindings.push(Statement::Let {
+                identifier: param.clone(),
+                expression: Expression::Literal(value),
+            }),
+            Some(value) => {
+                constants.insert(param.0.as_str(), value);
+            }
+        }
+    }
+    let body = function.body.0.iter().cloned().map(|statement| {
+        macros::modify_statement(statement, &mut |expression| {
+            Ok(fold(match expression {
+                Expression::Identifier(Identifier(name)) => match constants.get(name.as_str()) {
+                    Some(value) => Expression::Literal(value.clone()),
+                    None => Expression::Identifier(Identifier(name)),
+                },
+                expression => expression,
+            }))
+        })
+    });
+    bindings.extend(body.collect::<Result<Vec<_>>>()?);
+    Ok(Function {
+        params,
+        rest: function.rest.clone(),
+        body: Rc::new(Block(bindings)),
+    })
+}
+
+/// Names a function body declares with `let`, takes as parameters of nested functions, or
+/// assigns to.
+#[derive(Default)]
+struct Rebound(Vec<String>);
+
+impl Visitor for Rebound {
+    fn visit_statement(&mut self, statement: &Statement) {
+        if let Statement::Let { identifier, .. } = statement {
+            self.0.push(identifier.0.clone());
+        }
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        if let Expression::Assign(name, _) = expression {
+            self.0.push(name.0.clone());
+        }
+        walk_expression(self, expression);
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        let params = function.params.iter().chain(&function.rest);
+        self.0.extend(params.map(|param| param.0.clone()));
+        walk_function(self, function);
+    }
+}
+
+fn fold(expression: Expression) -> Expression {
+    match expression {
+        Expression::Prefix(prefix) => match constant(&prefix.expression) {
+            Some(operand) => match operand.prefix(&prefix.operation).ok().and_then(literal) {
+                Some(folded) => Expression::Literal(folded),
+                None => Expression::Prefix(prefix),
+            },
+            None => Expression::Prefix(prefix),
+        },
+        Expression::Infix(infix) => {
+            let operands = constant(&infix.left_expression).zip(constant(&infix.right_expression));
+            let folded = operands.and_then(|(left, right)| {
+                left.infix(&infix.operation, right).ok().and_then(literal)
+            });
+            match folded {
+                Some(folded) => Expression::Literal(folded),
+                None => Expression::Infix(infix),
+            }
+        }
+        Expression::If(if_expression) => match constant(&if_expression.condition) {
+            Some(condition) if condition.is_truthy() => branch(if_expression.consequence),
+            Some(_) => match if_expression.alternative {
+                Some(alternative) => branch(alternative),
+                None => Expression::Literal(Literal::Nil),
+            },
+            None => Expression::If(if_expression),
+        },
+        expression => expression,
+    }
+}
+
+/// The expression for an `if` whose condition is known to select `block`: the block's value
+/// itself when it is a single expression, otherwise an `if (true)` around it.
+fn branch(mut block: Block) -> Expression {
+    if let [Statement::Expression(_)] = block.0.as_slice() {
+        if let Some(Statement::Expression(expression)) = block.0.pop() {
+            return expression;
+        }
+    }
+    Expression::If(If {
+        condition: Expression::Literal(Literal::True).boxed(),
+        consequence: block,
+        alternative: None,
+    })
+}
+
+fn constant(expression: &Expression) -> Option<Object> {
+    match expression {
+        Expression::Literal(Literal::Int(value)) => Some(Object::Int(*value)),
+        Expression::Literal(Literal::Float(value)) => Some(Object::Float(*value)),
+        Expression::Literal(Literal::String(value)) => Some(Object::String(value.as_str().into())),
+        Expression::Literal(Literal::True) => Some(Object::Bool(true)),
+        Expression::Literal(Literal::False) => Some(Object::Bool(false)),
+        Expression::Literal(Literal::Nil) => Some(Object::Nil),
+        _ => None,
+    }
+}
+
+fn literal(object: Object) -> Option<Literal> {
+    match object {
+        Object::Int(value) => Some(Literal::Int(value)),
+        Object::Float(value) => Some(Literal::Float(value)),
+        Object::String(value) => Some(Literal::String(value.to_string())),
+        Object::Bool(true) => Some(Literal::True),
+        Object::Bool(false) => Some(Literal::False),
+        Object::Nil => Some(Literal::Nil),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod optimizer_tests {
+    use super::{optimize, specialize};
+    use crate::{
+        ast::{Expression, Literal, Statement},
+        lexer::Lexer,
+        parser::Parser,
+        prelude::*,
+    };
+
+    fn optimized(text: &str) -> Vec<String> {
+        Parser::new(Lexer::new(text).tokens())
+            .map(|statement| optimize(statement.unwrap()).to_string())
+            .collect()
+    }
+
+    #[test]
+    fn folds_constants() {
+        assert_eq!(
+            optimized(
+                r#"2 * 3 + 4; !true; -(1.5 * 2); "a" + "b" == "ab"; x + 2 * 3; 1 / 0; 1 + true"#
+            ),
+            vec!["10", "false", "-3.0", "true", "(x+6)", "(1/0)", "(1+true)"]
+        );
+    }
+
+    #[test]
+    fn removes_dead_branches() {
+        assert_eq!(
+            optimized(
+                "if (false) { 1 } else { 2 }; if (1 > 2) { 3 }; if (true) { let a = 1; a }; \
+                 let f = fn() { if (!false) { x } else { y } };"
+            ),
+            vec!["2", "nil", "if true let a = 1a", "let f = fn () x",]
+        );
+    }
+
+    #[test]
+    fn specializes_functions() {
+        let function = |text: &str| match Parser::new(Lexer::new(text).tokens()).next() {
+            Some(Ok(Statement::Expression(Expression::Function(function)))) => function,
+            other => panic!("not a function literal: {other:?}"),
+        };
+        let port =
+            function(r#"fn(env, port) { if (env == "prod") { port * 2 } else { port + 1000 } }"#);
+        let prod = || Some(Literal::String("prod".to_string()));
+        assert_eq!(
+            specialize(&port, &[prod()]).unwrap().to_string(),
+            "fn (port) (port*2)"
+        );
+        assert_eq!(
+            specialize(&port, &[None, Some(Literal::Int(8))])
+                .unwrap()
+                .to_string(),
+            "fn (env) if (env==\"prod\") 16 else 1008"
+        );
+        assert_eq!(
+            specialize(&port, &[prod(), Some(Literal::Int(8))])
+                .unwrap()
+                .to_string(),
+            "fn () 16"
+        );
+
+        // Parameters the body rebinds keep a binding rather than being substituted.
+        let shadowed = function("fn(x, y) { let f = fn(x) { x + y }; x = x + 1; f(x) }");
+        assert_eq!(
+            specialize(&shadowed, &[Some(Literal::Int(1)), Some(Literal::Int(2))])
+                .unwrap()
+                .to_string(),
+            "fn () let x = 1let f = fn (x) (x+2)x = (x+1)f (x)"
+        );
+        assert!(specialize(&shadowed, &[None, None, None]).is_err());
+    }
+}
@@ -1,226 +1,606 @@
 use crate::ast::{
-    Block, Call, Expression, Function, If, Infix, Literal, Prefix, PrefixOperation, Statement,
+    Block, Call, Comprehension, Expression, Function, If, Index, Infix, Literal, Prefix,
+    PrefixOperation, Statement,
 };
 use crate::lexer::Lexer;
-use crate::token::{Identifier, TokenType};
-use std::iter::Peekable;
+use crate::prelude::*;
+use crate::syntax::{expression_kind, statement_kind, Mark, SyntaxBuilder, SyntaxNode};
+use crate::token::{Identifier, Span, Token, TokenType};
+use alloc::{collections::VecDeque, rc::Rc};
+use core::fmt::{self, Display};
+use core::iter::Peekable;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub span: Span,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.span, self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+pub type ParseResult<T> = Result<T, ParseError>;
 
 pub struct Parser<'a> {
     tokens: Peekable<Lexer<'a>>,
+    errors: Vec<ParseError>,
+    /// Where the most recently consumed token starts.
+    span: Span,
+    statement_span: Span,
+    /// Byte range of the most recently consumed token.
+    start: usize,
+    end: usize,
+    syntax: Option<SyntaxBuilder>,
+    /// The bindings after the first of `let a = 1, b = 2;`, yielded as statements of their own.
+    pending: VecDeque<Statement>,
+    /// Expressions being parsed inside one another.
+    depth: usize,
+    max_depth: usize,
 }
 
+/// How deeply expressions may nest by default, well inside the stack of a test thread.
+pub(crate) const MAX_DEPTH: usize = 256;
+
+/// Yields each statement, or the error that stopped it. After an error the parser skips
+/// to the next statement and carries on, so one pass reports every error.
 impl<'a> Iterator for Parser<'a> {
-    type Item = Statement;
+    type Item = Result<Statement, ParseError>;
     fn next(&mut self) -> Option<Self::Item> {
-        self.tokens
-            .next()
-            .and_then(|token| self.parse_statement(token))
+        if let Some(statement) = self.pending.pop_front() {
+            return Some(Ok(statement));
+        }
+        let token = self.next_token()?;
+        self.statement_span = self.span;
+        let mark = self.mark();
+        let result = self.parse_statement(token);
+        if let Err(err) = &result {
+            self.errors.push(err.clone());
+            if let (Some(syntax), Some(mark)) = (&mut self.syntax, mark) {
+                syntax.discard(mark);
+            }
+            self.synchronize();
+        }
+        Some(result)
     }
 }
 
 impl<'a> Parser<'a> {
     pub fn new(tokens: Peekable<Lexer<'a>>) -> Self {
-        Parser { tokens }
+        Parser {
+            tokens,
+            errors: vec![],
+            span: Span::new(1, 1),
+            statement_span: Span::new(1, 1),
+            start: 0,
+            end: 0,
+            syntax: None,
+            pending: VecDeque::new(),
+            depth: 0,
+            max_depth: MAX_DEPTH,
+        }
     }
 
-    fn parse_statement(&mut self, token: TokenType) -> Option<Statement> {
-        match token {
-            TokenType::Let => {
-                let statement = self.parse_let();
-                Some(statement)
+    /// Fail with an error instead of recursing through more than `max_depth` nested
+    /// expressions.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Also build a [`SyntaxNode`] tree with byte ranges while parsing, for tooling.
+    pub fn record_syntax(mut self, enabled: bool) -> Self {
+        self.syntax = enabled.then(SyntaxBuilder::default);
+        self
+    }
+
+    /// The parse tree of the statements parsed so far, when recording was enabled.
+    pub fn syntax_tree(&self) -> Option<SyntaxNode> {
+        Some(self.syntax.as_ref()?.tree(self.end))
+    }
+
+    /// Starts a syntax node at the most recently consumed token.
+    fn mark(&self) -> Option<Mark> {
+        Some(self.syntax.as_ref()?.mark(self.start))
+    }
+
+    /// Starts a syntax node at the next token.
+    fn mark_next(&mut self) -> Option<Mark> {
+        let start = self
+            .tokens
+            .peek()
+            .map_or(self.end, |token| token.range.start);
+        Some(self.syntax.as_ref()?.mark(start))
+    }
+
+    /// Ends the node started at `mark` with the most recently consumed token.
+    fn finish(&mut self, mark: Option<Mark>, kind: &'static str) {
+        if let (Some(syntax), Some(mark)) = (&mut self.syntax, mark) {
+            syntax.finish(mark, kind, self.end);
+        }
+    }
+
+    /// Where the statement most recently returned by the iterator starts.
+    pub fn statement_span(&self) -> Span {
+        self.statement_span
+    }
+
+    fn error(&self, message: String) -> ParseError {
+        ParseError {
+            message,
+            span: self.span,
+        }
+    }
+
+    fn next_token(&mut self) -> Option<TokenType> {
+        let token = self.tokens.next()?;
+        Some(self.consume(token))
+    }
+
+    fn next_if(&mut self, accept: impl FnOnce(&TokenType) -> bool) -> Option<TokenType> {
+        let token = self.tokens.next_if(|token| accept(&token.kind))?;
+        Some(self.consume(token))
+    }
+
+    fn consume(&mut self, token: Token) -> TokenType {
+        self.span = token.span;
+        (self.start, self.end) = (token.range.start, token.range.end);
+        token.kind
+    }
+
+    fn next_if_eq(&mut self, kind: &TokenType) -> Option<TokenType> {
+        self.next_if(|token| token == kind)
+    }
+
+    fn peek(&mut self) -> Option<&TokenType> {
+        self.tokens.peek().map(|token| &token.kind)
+    }
+
+    /// Every error yielded so far.
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    fn synchronize(&mut self) {
+        while let Some(token) =
+            self.next_if(|token| !matches!(token, TokenType::Let | TokenType::Return))
+        {
+            if token == TokenType::Semicolon {
+                break;
+            }
+        }
+    }
+
+    fn parse_statement(&mut self, token: TokenType) -> ParseResult<Statement> {
+        let mark = self.mark();
+        let statement = match token {
+            TokenType::Let => return self.parse_let(mark),
+            TokenType::Return => self.parse_expr_statement()?,
+            TokenType::Defer => self.parse_defer()?,
+            TokenType::While => self.parse_while()?,
+            TokenType::For => self.parse_for()?,
+            TokenType::Break => {
+                self.next_if_eq(&TokenType::Semicolon);
+                Statement::Break
             }
-            TokenType::Return => {
-                let statement = self.parse_expr_statement();
-                Some(statement)
+            TokenType::Continue => {
+                self.next_if_eq(&TokenType::Semicolon);
+                Statement::Continue
             }
             token => {
-                let expression = self.parse_expression(0, token);
-                self.assert_next_and_advance(TokenType::Semicolon);
-                Some(Statement::Expression(expression))
+                let expression = self.parse_expression(0, token)?;
+                self.next_if_eq(&TokenType::Semicolon);
+                Statement::Expression(expression)
+            }
+        };
+        self.finish(mark, statement_kind(&statement));
+        Ok(statement)
+    }
+
+    pub fn assert_next_and_advance(&mut self, token: TokenType) -> ParseResult<TokenType> {
+        if let Some(token) = self.next_if_eq(&token) {
+            return Ok(token);
+        }
+        match self.tokens.peek() {
+            Some(Token { kind, span, .. }) => Err(ParseError {
+                message: format!("expected next token to be '{token}', got '{kind}' instead"),
+                span: *span,
+            }),
+            None => Err(self.error(format!(
+                "expected next token to be '{token}', got end of input instead"
+            ))),
+        }
+    }
+
+    pub fn try_next_token(&mut self) -> ParseResult<TokenType> {
+        self.next_token()
+            .ok_or_else(|| self.error("unexpected end of input".to_string()))
+    }
+
+    fn parse_identifier(&mut self) -> ParseResult<Identifier> {
+        match self.try_next_token()? {
+            TokenType::Identifier(identifier) => {
+                let mark = self.mark();
+                self.finish(mark, "identifier");
+                Ok(identifier)
             }
+            token => Err(self.error(format!("expected identifier, got '{token}' instead"))),
         }
     }
 
-    pub fn assert_next_and_advance(&mut self, token: TokenType) -> Option<TokenType> {
-        self.tokens.next_if_eq(&token)
+    pub fn parse_expr_statement(&mut self) -> ParseResult<Statement> {
+        let new_token = self.try_next_token()?;
+        let left = self.parse_expression(0, new_token)?;
+        self.next_if_eq(&TokenType::Semicolon);
+        Ok(Statement::Return(left))
     }
 
-    pub fn try_next_token(&mut self) -> TokenType {
-        self.tokens.next().unwrap()
+    pub fn parse_defer(&mut self) -> ParseResult<Statement> {
+        let new_token = self.try_next_token()?;
+        let expression = self.parse_expression(0, new_token)?;
+        self.next_if_eq(&TokenType::Semicolon);
+        Ok(Statement::Defer(expression))
     }
 
-    pub fn parse_expr_statement(&mut self) -> Statement {
-        let new_token = self.try_next_token();
-        let left = self.parse_expression(0, new_token);
-        self.tokens.next_if_eq(&TokenType::Semicolon);
-        Statement::Return(left)
+    pub fn parse_while(&mut self) -> ParseResult<Statement> {
+        self.assert_next_and_advance(TokenType::LParen)?;
+        let current_token = self.try_next_token()?;
+        let condition = self.parse_expression(0, current_token)?;
+        self.assert_next_and_advance(TokenType::RParen)?;
+        self.assert_next_and_advance(TokenType::LBrace)?;
+        let body = self.parse_block()?;
+        self.next_if_eq(&TokenType::Semicolon);
+        Ok(Statement::While { condition, body })
+    }
+
+    pub fn parse_for(&mut self) -> ParseResult<Statement> {
+        self.assert_next_and_advance(TokenType::LParen)?;
+        let variable = self.parse_identifier()?;
+        let value = match self.next_if_eq(&TokenType::Comma) {
+            Some(_) => Some(self.parse_identifier()?),
+            None => None,
+        };
+        self.assert_next_and_advance(TokenType::In)?;
+        let current_token = self.try_next_token()?;
+        let iterable = self.parse_expression(0, current_token)?;
+        self.assert_next_and_advance(TokenType::RParen)?;
+        self.assert_next_and_advance(TokenType::LBrace)?;
+        let body = self.parse_block()?;
+        self.next_if_eq(&TokenType::Semicolon);
+        Ok(Statement::For {
+            variable,
+            value,
+            iterable,
+            body,
+        })
     }
 
-    pub fn parse_let(&mut self) -> Statement {
-        let token = self.try_next_token();
-        let identifier = Identifier(token.to_string());
-        self.assert_next_and_advance(TokenType::Assign);
-        let current_token = self.try_next_token();
-        let expression = self.parse_expression(0, current_token);
-        self.tokens.next_if_eq(&TokenType::Semicolon);
-        Statement::Let {
+    /// Parses `let` and its bindings, each recorded as a `let_statement` node of its own.
+    /// Bindings after the first are queued for the iterator or the enclosing block.
+    fn parse_let(&mut self, mut mark: Option<Mark>) -> ParseResult<Statement> {
+        let mut bindings = vec![];
+        loop {
+            bindings.push(self.parse_binding()?);
+            if self.peek() != Some(&TokenType::Comma) {
+                break;
+            }
+            self.finish(mark, "let_statement");
+            self.next_token();
+            mark = self.mark_next();
+        }
+        self.next_if_eq(&TokenType::Semicolon);
+        self.finish(mark, "let_statement");
+        let first = bindings.remove(0);
+        self.pending.extend(bindings);
+        Ok(first)
+    }
+
+    fn parse_binding(&mut self) -> ParseResult<Statement> {
+        let identifier = self.parse_identifier()?;
+        // `let x;` declares `x` as nil, for a value assigned further down.
+        if matches!(
+            self.peek(),
+            None | Some(TokenType::Semicolon | TokenType::Comma | TokenType::RBrace)
+        ) {
+            return Ok(Statement::Let {
+                identifier,
+                expression: Expression::Literal(Literal::Nil),
+            });
+        }
+        self.assert_next_and_advance(TokenType::Assign)?;
+        let current_token = self.try_next_token()?;
+        let expression = self.parse_expression(0, current_token)?;
+        Ok(Statement::Let {
             identifier,
             expression,
+        })
+    }
+
+    pub fn parse_expression(
+        &mut self,
+        precedente: usize,
+        current_token: TokenType,
+    ) -> ParseResult<Expression> {
+        if self.depth >= self.max_depth {
+            return Err(self.error("stack overflow: max recursion depth exceeded".to_string()));
         }
+        self.depth += 1;
+        let result = self.parse_nested_expression(precedente, current_token);
+        self.depth -= 1;
+        result
     }
 
-    pub fn parse_expression(&mut self, precedente: usize, current_token: TokenType) -> Expression {
-        let mut left = self.parse_prefix(current_token).unwrap();
+    fn parse_nested_expression(
+        &mut self,
+        precedente: usize,
+        current_token: TokenType,
+    ) -> ParseResult<Expression> {
+        let mark = self.mark();
+        let grouped = current_token == TokenType::LParen;
+        let mut left = self.parse_prefix(current_token)?;
+        match grouped {
+            true => self.finish(mark, "parenthesized_expression"),
+            false => self.finish(mark, expression_kind(&left)),
+        }
+        // Assignment binds loosest and to the right: `a = b = 1` is `a = (b = 1)`.
+        if let Expression::Identifier(name) = &left {
+            if precedente == 0 && self.next_if_eq(&TokenType::Assign).is_some() {
+                let token = self.try_next_token()?;
+                let value = self.parse_expression(0, token)?;
+                let assignment = Expression::Assign(name.clone(), value.boxed());
+                self.finish(mark, expression_kind(&assignment));
+                return Ok(assignment);
+            }
+        }
 
-        while let Some(next) = self.tokens.next_if(|peek| {
+        while let Some(next) = self.next_if(|peek| {
             peek != &TokenType::Semicolon
                 && precedente < peek.precedence()
                 && peek.operation().is_some()
         }) {
             match next {
-                TokenType::LParen => left = self.parse_call_expression(left),
-                _ => left = self.parse_infix_expression(left, next),
+                TokenType::LParen => left = self.parse_call_expression(left)?,
+                TokenType::LBracket => left = self.parse_index_expression(left)?,
+                _ => left = self.parse_infix_expression(left, next)?,
             }
+            self.finish(mark, expression_kind(&left));
         }
-        left
+        Ok(left)
     }
 
-    fn parse_prefix_expression(&mut self, operation: PrefixOperation) -> Expression {
-        let token = self.try_next_token();
-        let expression = self.parse_expression(6, token);
-        Expression::Prefix(Prefix {
+    fn parse_prefix_expression(&mut self, operation: PrefixOperation) -> ParseResult<Expression> {
+        let token = self.try_next_token()?;
+        let expression = self.parse_expression(6, token)?;
+        Ok(Expression::Prefix(Prefix {
             operation,
             expression: expression.boxed(),
-        })
+        }))
     }
 
-    fn parse_grouped_expression(&mut self) -> Option<Expression> {
-        let previous_token = self.try_next_token();
-        let expression = self.parse_expression(0, previous_token);
+    fn parse_grouped_expression(&mut self) -> ParseResult<Expression> {
+        let previous_token = self.try_next_token()?;
+        let expression = self.parse_expression(0, previous_token)?;
         self.assert_next_and_advance(TokenType::RParen)?;
-        Some(expression)
+        Ok(expression)
     }
 
-    fn parse_if_expression(&mut self) -> Option<Expression> {
+    fn parse_if_expression(&mut self) -> ParseResult<Expression> {
         self.assert_next_and_advance(TokenType::LParen)?;
-        let current_token = self.try_next_token();
-        let condition = self.parse_expression(0, current_token).boxed();
+        let current_token = self.try_next_token()?;
+        let condition = self.parse_expression(0, current_token)?.boxed();
         self.assert_next_and_advance(TokenType::RParen)?;
         self.assert_next_and_advance(TokenType::LBrace)?;
-        let consequence = self.parse_block();
+        let consequence = self.parse_block()?;
         let mut alternative: Option<Block> = None;
-        if self.tokens.next_if_eq(&TokenType::Else).is_some() {
-            self.try_next_token();
-            self.assert_next_and_advance(TokenType::LBrace);
-            alternative = Some(self.parse_block());
+        if self.next_if_eq(&TokenType::Else).is_some() {
+            self.assert_next_and_advance(TokenType::LBrace)?;
+            alternative = Some(self.parse_block()?);
         }
-        Some(Expression::If(If {
+        Ok(Expression::If(If {
             condition,
             alternative,
             consequence,
         }))
     }
 
-    fn parse_block(&mut self) -> Block {
-        let mut current_token = self.try_next_token();
+    fn parse_block(&mut self) -> ParseResult<Block> {
+        let mark = self.mark();
+        let mut current_token = self.try_next_token()?;
         let mut statements = vec![];
         while current_token != TokenType::RBrace {
-            let statement = self.parse_statement(current_token);
-            statements.push(statement);
-            current_token = self.try_next_token();
+            statements.push(self.parse_statement(current_token)?);
+            statements.extend(self.pending.drain(..));
+            current_token = self.try_next_token()?;
         }
-        Block(statements.into_iter().flatten().collect())
+        self.finish(mark, "block");
+        Ok(Block(statements))
     }
 
-    fn parse_function(&mut self) -> Expression {
-        self.assert_next_and_advance(TokenType::LParen);
-        let params = self.parse_function_params();
-        self.assert_next_and_advance(TokenType::LBrace);
-        let body = self.parse_block();
-        Expression::Function(Function { body, params })
+    fn parse_function(&mut self) -> ParseResult<Function> {
+        self.assert_next_and_advance(TokenType::LParen)?;
+        let (params, rest) = self.parse_function_params()?;
+        self.assert_next_and_advance(TokenType::LBrace)?;
+        let body = Rc::new(self.parse_block()?);
+        Ok(Function { body, params, rest })
     }
 
-    fn parse_function_params(&mut self) -> Vec<Identifier> {
+    /// Parses the parameters up to the closing parenthesis, the last of which may be a
+    /// `...rest` parameter.
+    fn parse_function_params(&mut self) -> ParseResult<(Vec<Identifier>, Option<Identifier>)> {
         let mut identifiers = vec![];
-        if self.tokens.next_if_eq(&TokenType::RParen).is_some() {
-            return identifiers;
+        if self.next_if_eq(&TokenType::RParen).is_some() {
+            return Ok((identifiers, None));
         };
-        let token = self.try_next_token();
-        identifiers.push(Identifier::new(token.to_string()));
-        while self.tokens.peek().unwrap() == &TokenType::Comma {
-            self.try_next_token();
-            let current_token = self.try_next_token();
-            identifiers.push(Identifier::new(current_token.to_string()));
-        }
-        self.assert_next_and_advance(TokenType::RParen);
-        identifiers
+        let rest = loop {
+            if self.next_if_eq(&TokenType::Ellipsis).is_some() {
+                let rest = self.parse_identifier()?;
+                if self.next_if_eq(&TokenType::Comma).is_some() {
+                    return Err(self.error("the rest parameter must come last".to_string()));
+                }
+                break Some(rest);
+            }
+            identifiers.push(self.parse_identifier()?);
+            if self.next_if_eq(&TokenType::Comma).is_none() {
+                break None;
+            }
+        };
+        self.assert_next_and_advance(TokenType::RParen)?;
+        Ok((identifiers, rest))
     }
 
-    fn parse_call_arguments(&mut self) -> Vec<Expression> {
+    fn parse_expression_list(&mut self, end: TokenType) -> ParseResult<Vec<Expression>> {
         let mut args = vec![];
-        if self.tokens.next_if_eq(&TokenType::RParen).is_some() {
-            return args;
+        if self.next_if_eq(&end).is_some() {
+            return Ok(args);
+        };
+        let current_token = self.try_next_token()?;
+        args.push(self.parse_expression(0, current_token)?);
+        while self.next_if_eq(&TokenType::Comma).is_some() {
+            let current_token = self.try_next_token()?;
+            args.push(self.parse_expression(0, current_token)?);
+        }
+        self.assert_next_and_advance(end)?;
+        Ok(args)
+    }
+
+    /// Parses an array literal after its `[`, or a comprehension when the first element is
+    /// followed by `for`.
+    fn parse_array_literal(&mut self) -> ParseResult<Expression> {
+        if self.next_if_eq(&TokenType::RBracket).is_some() {
+            return Ok(Expression::Array(vec![]));
+        }
+        let current_token = self.try_next_token()?;
+        let first = self.parse_expression(0, current_token)?;
+        if self.next_if_eq(&TokenType::For).is_some() {
+            return self.parse_comprehension(first);
+        }
+        let mut elements = vec![first];
+        while self.next_if_eq(&TokenType::Comma).is_some() {
+            let current_token = self.try_next_token()?;
+            elements.push(self.parse_expression(0, current_token)?);
+        }
+        self.assert_next_and_advance(TokenType::RBracket)?;
+        Ok(Expression::Array(elements))
+    }
+
+    fn parse_comprehension(&mut self, element: Expression) -> ParseResult<Expression> {
+        let variable = self.parse_identifier()?;
+        let value = match self.next_if_eq(&TokenType::Comma) {
+            Some(_) => Some(self.parse_identifier()?),
+            None => None,
         };
-        let current_token = self.try_next_token();
-        args.push(self.parse_expression(0, current_token));
-        while self.tokens.peek().unwrap() == &TokenType::Comma {
-            self.try_next_token();
-            let current_token = self.try_next_token();
-            args.push(self.parse_expression(0, current_token));
+        self.assert_next_and_advance(TokenType::In)?;
+        let current_token = self.try_next_token()?;
+        let iterable = self.parse_expression(0, current_token)?.boxed();
+        let condition = match self.next_if_eq(&TokenType::If) {
+            Some(_) => {
+                let current_token = self.try_next_token()?;
+                Some(self.parse_expression(0, current_token)?.boxed())
+            }
+            None => None,
+        };
+        self.assert_next_and_advance(TokenType::RBracket)?;
+        Ok(Expression::Comprehension(Comprehension {
+            element: element.boxed(),
+            variable,
+            value,
+            iterable,
+            condition,
+        }))
+    }
+
+    fn parse_hash_literal(&mut self) -> ParseResult<Expression> {
+        let mut pairs = vec![];
+        while self.next_if_eq(&TokenType::RBrace).is_none() {
+            let token = self.try_next_token()?;
+            let key = self.parse_expression(0, token)?;
+            self.assert_next_and_advance(TokenType::Colon)?;
+            let token = self.try_next_token()?;
+            let value = self.parse_expression(0, token)?;
+            pairs.push((key, value));
+            if self.peek() != Some(&TokenType::RBrace) {
+                self.assert_next_and_advance(TokenType::Comma)?;
+            }
         }
-        self.assert_next_and_advance(TokenType::RParen);
-        args
+        Ok(Expression::Hash(pairs))
+    }
+
+    fn parse_index_expression(&mut self, left: Expression) -> ParseResult<Expression> {
+        let token = self.try_next_token()?;
+        let index = self.parse_expression(0, token)?;
+        self.assert_next_and_advance(TokenType::RBracket)?;
+        Ok(Expression::Index(Index {
+            left: left.boxed(),
+            index: index.boxed(),
+        }))
     }
 
-    fn parse_call_expression(&mut self, function: Expression) -> Expression {
-        let arguments = self.parse_call_arguments();
-        Expression::Call(Call {
+    fn parse_call_expression(&mut self, function: Expression) -> ParseResult<Expression> {
+        let arguments = self.parse_expression_list(TokenType::RParen)?;
+        Ok(Expression::Call(Call {
             function: function.boxed(),
             arguments,
-        })
+        }))
     }
 
     fn parse_infix_expression(
         &mut self,
         left_expression: Expression,
         token: TokenType,
-    ) -> Expression {
+    ) -> ParseResult<Expression> {
         let precedence = token.precedence();
-        let operation = token.operation().unwrap();
+        let operation = token
+            .operation()
+            .ok_or_else(|| self.error(format!("'{token}' is not an infix operator")))?;
 
-        let token_new = self.try_next_token();
-        let right_expression = self.parse_expression(precedence, token_new);
-        Expression::Infix(Infix {
+        let token_new = self.try_next_token()?;
+        let right_expression = self.parse_expression(precedence, token_new)?;
+        Ok(Expression::Infix(Infix {
             right_expression: right_expression.boxed(),
             operation,
             left_expression: left_expression.boxed(),
-        })
+        }))
     }
 
-    pub fn parse_prefix(&mut self, token: TokenType) -> Option<Expression> {
+    pub fn parse_prefix(&mut self, token: TokenType) -> ParseResult<Expression> {
         match token {
-            TokenType::Identifier(name) => Some(Expression::Identifier(name.to_owned())),
-            TokenType::Int(num) => Some(Expression::Literal(Literal::Int(num.to_owned()))),
-            TokenType::True => Some(Expression::Literal(Literal::True)),
-            TokenType::False => Some(Expression::Literal(Literal::False)),
-            TokenType::Nil => Some(Expression::Literal(Literal::Nil)),
-            TokenType::Bang => Some(self.parse_prefix_expression(PrefixOperation::Bang)),
-            TokenType::Minus => Some(self.parse_prefix_expression(PrefixOperation::Minus)),
+            TokenType::Identifier(name) => Ok(Expression::Identifier(name)),
+            TokenType::Int(num) => Ok(Expression::Literal(Literal::Int(num))),
+            TokenType::Float(num) => Ok(Expression::Literal(Literal::Float(num))),
+            TokenType::String(string) => Ok(Expression::Literal(Literal::String(string))),
+            TokenType::True => Ok(Expression::Literal(Literal::True)),
+            TokenType::False => Ok(Expression::Literal(Literal::False)),
+            TokenType::Nil => Ok(Expression::Literal(Literal::Nil)),
+            TokenType::Bang => self.parse_prefix_expression(PrefixOperation::Bang),
+            TokenType::Minus => self.parse_prefix_expression(PrefixOperation::Minus),
             TokenType::LParen => self.parse_grouped_expression(),
             TokenType::If => self.parse_if_expression(),
-            TokenType::Function => Some(self.parse_function()),
-            _ => None,
+            TokenType::Function => Ok(Expression::Function(self.parse_function()?)),
+            TokenType::Macro => Ok(Expression::Macro(self.parse_function()?)),
+            TokenType::LBrace => self.parse_hash_literal(),
+            TokenType::LBracket => self.parse_array_literal(),
+            TokenType::Malformed { message, .. } => Err(self.error(message)),
+            token => Err(self.error(format!("no prefix parse function for token '{token}'"))),
         }
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod parser_tests {
     use crate::{
         ast::{Expression, Infix, InfixOperation, Literal, Prefix, PrefixOperation, Statement},
         parser::Parser,
+        prelude::*,
         token::Identifier,
     };
+    #[cfg(not(feature = "std"))]
+    use std::println;
 
     #[test]
     fn let_expression() {
@@ -231,7 +611,7 @@ mod parser_tests {
         "#;
 
         let lexer = lexer::Lexer::new(program);
-        let parser = Parser::new(lexer.peekable());
+        let parser = Parser::new(lexer.tokens());
 
         let expected_vec = vec![
             Identifier::new("five".to_string()),
@@ -240,7 +620,7 @@ mod parser_tests {
 
         let mut expected = expected_vec.iter();
 
-        for statement in parser {
+        for statement in parser.map(Result::unwrap) {
             if let Statement::Let {
                 identifier,
                 expression: _,
@@ -251,6 +631,56 @@ mod parser_tests {
         }
     }
 
+    #[test]
+    fn let_without_initializer() {
+        use crate::lexer;
+
+        let parser =
+            Parser::new(lexer::Lexer::new("let a; let b = 1; fn() { let c }; let d").tokens());
+        let statements: Vec<String> = parser
+            .map(|statement| statement.unwrap().to_string())
+            .collect();
+        assert_eq!(
+            statements,
+            vec![
+                "let a = nil",
+                "let b = 1",
+                "fn () let c = nil",
+                "let d = nil"
+            ]
+        );
+        let mut parser = Parser::new(lexer::Lexer::new("let e 1;").tokens());
+        assert_eq!(
+            parser.next().unwrap().unwrap_err().message,
+            "expected next token to be '=', got '1' instead"
+        );
+    }
+    #[test]
+    fn let_with_several_bindings() {
+        use crate::lexer;
+
+        let source = "let a = 1, b = f(a, 2), c; fn() { let d = 3, e = d; e }; 4";
+        let parser = Parser::new(lexer::Lexer::new(source).tokens());
+        let statements: Vec<String> = parser
+            .map(|statement| statement.unwrap().to_string())
+            .collect();
+        assert_eq!(
+            statements,
+            vec![
+                "let a = 1",
+                "let b = f (a, 2)",
+                "let c = nil",
+                "fn () let d = 3let e = de",
+                "4"
+            ]
+        );
+        let mut parser = Parser::new(lexer::Lexer::new("let a = 1, = 2;").tokens());
+        assert_eq!(
+            parser.next().unwrap().unwrap_err().message,
+            "expected identifier, got '=' instead"
+        );
+        assert!(parser.next().is_none());
+    }
     #[test]
     fn return_expression() {
         use crate::lexer;
@@ -259,9 +689,9 @@ mod parser_tests {
         "#;
 
         let lexer = lexer::Lexer::new(program);
-        let parser = Parser::new(lexer.peekable());
+        let parser = Parser::new(lexer.tokens());
 
-        for statement in parser {
+        for statement in parser.map(Result::unwrap) {
             assert!(matches!(
                 statement,
                 Statement::Return(Expression::Literal(Literal::Int(10)))
@@ -290,7 +720,7 @@ mod parser_tests {
         "#;
 
         let lexer = lexer::Lexer::new(program);
-        let parser = Parser::new(lexer.peekable());
+        let parser = Parser::new(lexer.tokens());
 
         let expected_vec = vec![
             Expression::Identifier(Identifier("foobar".to_string())),
@@ -341,7 +771,7 @@ mod parser_tests {
 
         let mut expected = expected_vec.iter();
 
-        for statement in parser {
+        for statement in parser.map(Result::unwrap) {
             match statement {
                 Statement::Expression(expression) => {
                     assert_eq!(&expression, expected.next().unwrap());
@@ -372,6 +802,13 @@ mod parser_tests {
         3 * 9;
         foo * bar;
         88 + 2 * 3;
+        [1, 2 * 2];
+        a * [1, 2][b * c] * d;
+        add(a * b[2], b[1]);
+        {"one": 1, two: 1 + 1};
+        {};
+        [x * 2 for x in xs if x > 1];
+        [k for k, v in h];
         1 + 2 + 3;
         false == false;
         false <= true;
@@ -386,13 +823,19 @@ mod parser_tests {
         fn() {};
         fn(x) {};
         fn(x, y, z) {};
+        fn(x, ...rest) {};
         a + add(b * c) + d;
         let x = fn(x) {5 + 3};
         return x(5+3);
+        while (i < 3) { let i = i + 1; }
+        for (x in [1, 2]) { puts(x); }
+        for (k, v in h) { k }
+        while (true) { break; continue }
+        x = y = x + 1;
         "#;
 
         let lexer = lexer::Lexer::new(program);
-        let parser = Parser::new(lexer.peekable());
+        let parser = Parser::new(lexer.tokens());
 
         let expected_vec = vec![
             String::from("foobar"),
@@ -408,6 +851,13 @@ mod parser_tests {
             String::from("(3*9)"),
             String::from("(foo*bar)"),
             String::from("(88+(2*3))"),
+            String::from("[1, (2*2)]"),
+            String::from("((a*([1, 2][(b*c)]))*d)"),
+            String::from("add ((a*(b[2])), (b[1]))"),
+            String::from("{\"one\": 1, two: (1+1)}"),
+            String::from("{}"),
+            String::from("[(x*2) for x in xs if (x>1)]"),
+            String::from("[k for k, v in h]"),
             String::from("((1+2)+3)"),
             String::from("(false==false)"),
             String::from("(false<=true)"),
@@ -422,16 +872,143 @@ mod parser_tests {
             String::from("fn ()"),
             String::from("fn (x)"),
             String::from("fn (x, y, z)"),
+            String::from("fn (x, ...rest)"),
             String::from("((a+add ((b*c)))+d)"),
             String::from("let x = fn (x) (5+3)"),
             String::from("return x ((5+3))"),
+            String::from("while (i<3) let i = (i+1)"),
+            String::from("for x in [1, 2] puts (x)"),
+            String::from("for k, v in h k"),
+            String::from("while true breakcontinue"),
+            String::from("x = y = (x+1)"),
         ];
 
         let mut expected = expected_vec.iter();
-        for statement in parser {
+        for statement in parser.map(Result::unwrap) {
             let formatted = format!("{statement}");
             println!("{formatted}");
             assert_eq!(&formatted, expected.next().unwrap());
         }
     }
+
+    #[test]
+    fn parse_errors() {
+        use crate::lexer;
+
+        let program = r#"
+        let = 5;
+        let x 5;
+        let y = 10;
+        if (x { 1 };
+        let z = ;
+        return [1, 2;
+        "#;
+
+        let mut parser = Parser::new(lexer::Lexer::new(program).tokens());
+        let statements: Vec<Statement> = parser.by_ref().flatten().collect();
+
+        assert_eq!(
+            statements,
+            vec![Statement::Let {
+                identifier: Identifier::new_str("y"),
+                expression: Expression::Literal(Literal::Int(10)),
+            }]
+        );
+        let errors: Vec<String> = parser.errors().iter().map(|err| err.to_string()).collect();
+        assert_eq!(
+            errors,
+            vec![
+                "line 2, column 13: expected identifier, got '=' instead",
+                "line 3, column 15: expected next token to be '=', got '5' instead",
+                "line 5, column 15: expected next token to be ')', got '{' instead",
+                "line 6, column 17: no prefix parse function for token ';'",
+                "line 7, column 21: expected next token to be ']', got ';' instead",
+            ]
+        );
+    }
+
+    #[test]
+    fn errors_are_yielded_in_place() {
+        use crate::lexer;
+
+        let parser = Parser::new(lexer::Lexer::new("1; let = 2; 3;").tokens());
+        let results: Vec<String> = parser
+            .map(|result| match result {
+                Ok(statement) => statement.to_string(),
+                Err(err) => format!("error: {err}"),
+            })
+            .collect();
+        assert_eq!(
+            results,
+            vec![
+                "1",
+                "error: line 1, column 8: expected identifier, got '=' instead",
+                "3",
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_operands() {
+        use crate::lexer;
+
+        let cases = [
+            (
+                "let x = -;",
+                "line 1, column 10: no prefix parse function for token ';'",
+            ),
+            (
+                "!)",
+                "line 1, column 2: no prefix parse function for token ')'",
+            ),
+            (
+                "1 + ;",
+                "line 1, column 5: no prefix parse function for token ';'",
+            ),
+            (
+                "f(,)",
+                "line 1, column 3: no prefix parse function for token ','",
+            ),
+            ("let y = !", "line 1, column 9: unexpected end of input"),
+        ];
+        for (program, expected) in cases {
+            let mut parser = Parser::new(lexer::Lexer::new(program).tokens());
+            assert_eq!(parser.by_ref().flatten().count(), 0, "{program}");
+            let errors: Vec<String> = parser.errors().iter().map(|err| err.to_string()).collect();
+            assert_eq!(errors, vec![expected], "{program}");
+        }
+    }
+
+    #[test]
+    fn malformed_numbers() {
+        use crate::lexer;
+
+        let mut parser = Parser::new(lexer::Lexer::new("let x = 0b12;").tokens());
+        let err = parser.next().unwrap().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 9: invalid digit '2' in binary literal: 0b12"
+        );
+    }
+
+    #[test]
+    fn nesting_is_limited() {
+        use crate::lexer;
+
+        let nested = |depth: usize| format!("{}1{}; 2", "(".repeat(depth), ")".repeat(depth));
+        let source = nested(super::MAX_DEPTH + 1);
+        let mut parser = Parser::new(lexer::Lexer::new(&source).tokens());
+        assert_eq!(
+            parser.next().unwrap().unwrap_err().message,
+            "stack overflow: max recursion depth exceeded"
+        );
+        assert_eq!(parser.next().unwrap().unwrap().to_string(), "2");
+
+        // Each parenthesis nests an expression around the innermost `1`.
+        let source = nested(9);
+        let parser = Parser::new(lexer::Lexer::new(&source).tokens()).max_depth(10);
+        assert_eq!(parser.map(Result::unwrap).count(), 2);
+        let parser = Parser::new(lexer::Lexer::new(&source).tokens()).max_depth(9);
+        assert_eq!(parser.flatten().count(), 1);
+    }
 }
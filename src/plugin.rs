@@ -0,0 +1,96 @@
+//! Native extensions loaded at startup. A plugin is a `cdylib` built against this crate
+//! that exports a [`Register`] function named [`REGISTER_SYMBOL`]:
+//!
+//! ```ignore
+//! #[no_mangle]
+//! pub fn rustmonk_register(registry: &rustmonk::builtins::Registry) -> anyhow::Result<()> {
+//!     registry.register("double", double)
+//! }
+//! ```
+//!
+//! Plugins are called through the Rust ABI, so they must be built with the same compiler
+//! and version of this crate as the interpreter loading them.
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use crate::builtins::Registry;
+
+/// The name a plugin exports its [`Register`] function under.
+pub const REGISTER_SYMBOL: &str = "rustmonk_register";
+
+pub type Register = fn(&Registry) -> Result<()>;
+
+/// Loads the dynamic library at `path` and lets it add its builtins to `registry`. The
+/// library is never unloaded, as its builtins may be called until the process exits.
+pub fn load(path: impl AsRef<Path>, registry: &Registry) -> Result<()> {
+    let path = path.as_ref();
+    let register = open(path).with_context(|| format!("loading plugin {}", path.display()))?;
+    register(registry).with_context(|| format!("registering plugin {}", path.display()))
+}
+
+#[cfg(unix)]
+fn open(path: &Path) -> Result<Register> {
+    use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+    let path = CString::new(path.as_os_str().as_bytes())?;
+    let symbol = CString::new(REGISTER_SYMBOL)?;
+    // SAFETY: the path is NUL-terminated. Loading runs the library's initializers, which
+    // whoever asked for the plugin trusts.
+    let handle = unsafe { libc::dlopen(path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+    if handle.is_null() {
+        bail!("{}", dlerror());
+    }
+    // SAFETY: the handle was just opened and the symbol name is NUL-terminated.
+    let register = unsafe { libc::dlsym(handle, symbol.as_ptr()) };
+    if register.is_null() {
+        bail!("the library does not export {REGISTER_SYMBOL}");
+    }
+    // SAFETY: plugins export a `Register` under this name, see the module docs.
+    Ok(unsafe { std::mem::transmute::<*mut libc::c_void, Register>(register) })
+}
+
+#[cfg(not(unix))]
+fn open(_path: &Path) -> Result<Register> {
+    bail!("plugins are only supported on unix")
+}
+
+#[cfg(unix)]
+fn dlerror() -> String {
+    // SAFETY: `dlerror` returns null or a NUL-terminated message that stays valid until the
+    // next `dl*` call on this thread.
+    unsafe {
+        let message = libc::dlerror();
+        if message.is_null() {
+            return "unknown error".to_string();
+        }
+        std::ffi::CStr::from_ptr(message)
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+#[cfg(test)]
+mod plugin_tests {
+    use super::*;
+
+    #[test]
+    fn reports_libraries_that_are_not_plugins() {
+        let registry = Registry::default();
+        let err = load("/nonexistent/libmonkey_ext.so", &registry).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "loading plugin /nonexistent/libmonkey_ext.so"
+        );
+
+        #[cfg(target_os = "linux")]
+        {
+            let err = load("libc.so.6", &registry).unwrap_err();
+            assert_eq!(
+                err.root_cause().to_string(),
+                "the library does not export rustmonk_register"
+            );
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use anyhow::{bail, Result};
+
+use crate::{
+    builtins,
+    lexer::Lexer,
+    parser::Parser,
+    prelude::*,
+    semantic::{self, Classification},
+    token::TokenType,
+};
+
+/// Renames the global binding `old` of `source` to `new`: its declarations and every use
+/// that resolves to it. Parameters and locals that shadow it keep their name. Only the
+/// identifiers are rewritten, so the rest of the source keeps its layout and comments.
+pub fn rename_identifier(source: &str, old: &str, new: &str) -> Result<String> {
+    let mut parser = Parser::new(Lexer::new(source).tokens());
+    parser.by_ref().for_each(drop);
+    if let Some(err) = parser.errors().first() {
+        bail!("{err}");
+    }
+    let mut tokens = Lexer::new(new).map(|token| token.kind);
+    if !matches!(
+        (tokens.next(), tokens.next()),
+        (Some(TokenType::Identifier(name)), None) if name.0 == new
+    ) {
+        bail!("not an identifier: {new}");
+    }
+
+    let resolved = semantic::resolve(source);
+    let name = |range: &core::ops::Range<usize>| &source[range.clone()];
+    if builtins::lookup(new).is_some() || resolved.iter().any(|r| name(&r.token.range) == new) {
+        bail!("{new} is already in use");
+    }
+    let targets: Vec<_> = resolved
+        .iter()
+        .filter(|r| r.global && name(&r.token.range) == old)
+        .map(|r| &r.token)
+        .collect();
+    if targets
+        .iter()
+        .all(|token| token.classification == Classification::Unresolved)
+    {
+        bail!("no global named {old}");
+    }
+
+    let mut renamed = String::with_capacity(source.len());
+    let mut end = 0;
+    for token in targets {
+        renamed.push_str(&source[end..token.range.start]);
+        renamed.push_str(new);
+        end = token.range.end;
+    }
+    renamed.push_str(&source[end..]);
+    Ok(renamed)
+}
+
+#[cfg(test)]
+mod refactor_tests {
+    use super::rename_identifier;
+    use crate::prelude::*;
+
+    #[test]
+    fn renames_the_global_binding() {
+        let source = "let total = 1; // running total\n\
+                      let add = fn(total, n) { total + n };\n\
+                      let bump = fn(n) { total = add(total, n); helper(total) };\n\
+                      let helper = fn(x) { let total = x; total };\n\
+                      bump(total);";
+        assert_eq!(
+            rename_identifier(source, "total", "sum").unwrap(),
+            "let sum = 1; // running total\n\
+             let add = fn(total, n) { total + n };\n\
+             let bump = fn(n) { sum = add(sum, n); helper(sum) };\n\
+             let helper = fn(x) { let total = x; total };\n\
+             bump(sum);"
+        );
+        // `helper` is used before it is declared, which still refers to the global.
+        assert_eq!(
+            rename_identifier(source, "helper", "inner").unwrap(),
+            source.replace("helper", "inner")
+        );
+    }
+
+    #[test]
+    fn rejects_unsafe_renames() {
+        let source = "let a = 1; let b = fn(c) { a + c };";
+        let err =
+            |old: &str, new: &str| rename_identifier(source, old, new).unwrap_err().to_string();
+        assert_eq!(err("a", "c"), "c is already in use");
+        assert_eq!(err("a", "len"), "len is already in use");
+        assert_eq!(err("a", "if"), "not an identifier: if");
+        assert_eq!(err("a", "x y"), "not an identifier: x y");
+        assert_eq!(err("c", "d"), "no global named c");
+        assert!(rename_identifier("let = 1;", "a", "b").is_err());
+    }
+}
@@ -1,43 +1,728 @@
-use std::{cell::RefCell, io, rc::Rc};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    env, fs, io,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
 
-use crate::{environment::Environment, eval::Program, lexer::Lexer, parser::Parser};
-pub struct Repl {}
+use anyhow::{bail, Error, Result};
+use rustyline::{
+    completion::Completer, error::ReadlineError, highlight::Highlighter, hint::Hinter,
+    history::DefaultHistory, validate::Validator, Editor, Helper,
+};
+
+use crate::{
+    environment::{Environment, GlobalEnv},
+    eval::{Engine, Program},
+    lexer::Lexer,
+    object::Object,
+    parser::Parser,
+    runtime::{CancellationToken, Runtime},
+    stdlib,
+    token::{Span, TokenType},
+};
+
+const DEFAULT_COLUMNS: usize = 80;
+const DEFAULT_LINES: usize = 24;
+
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// Everything an embedder can customize about the interactive session.
+#[derive(Debug, Clone)]
+pub struct ReplConfig {
+    pub prompt: String,
+    /// Shown instead of `prompt` while an unfinished input is being continued.
+    pub continuation_prompt: String,
+    pub banner: String,
+    /// Highlight prompts and errors with ANSI colors.
+    pub color: bool,
+    /// Load the [standard library](crate::stdlib) before anything else runs, and again after
+    /// `:clear`.
+    pub stdlib: bool,
+    /// Monkey source evaluated into the global environment before the first prompt.
+    pub init_script: Option<String>,
+    /// Startup script run after `init_script` when it exists, `~/.monkeyrc` by default. A
+    /// `repl` hash it binds overrides `prompt`, `continuation_prompt`, `banner` and `color`.
+    pub rc_file: Option<PathBuf>,
+    /// Where line history is loaded from and saved to, `~/.monkey_history` by default.
+    pub history_file: Option<PathBuf>,
+}
+
+fn home_file(name: &str) -> Option<PathBuf> {
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    Some(Path::new(&home).join(name))
+}
+
+/// The user's `~/.monkeyrc`, if a home directory is known.
+pub fn user_rc_file() -> Option<PathBuf> {
+    home_file(".monkeyrc")
+}
+
+/// The user's `~/.monkey_history`, if a home directory is known.
+pub fn user_history_file() -> Option<PathBuf> {
+    home_file(".monkey_history")
+}
+
+/// Colors the prompt when [`ReplConfig::color`] is set.
+struct PromptHelper {
+    color: bool,
+}
+
+impl Completer for PromptHelper {
+    type Candidate = String;
+}
+
+impl Hinter for PromptHelper {
+    type Hint = String;
+}
+
+impl Validator for PromptHelper {}
+
+impl Highlighter for PromptHelper {
+    fn highlight_prompt<'b, 's: 'b, 'p: 'b>(&'s self, prompt: &'p str, _: bool) -> Cow<'b, str> {
+        match self.color {
+            true => Cow::Owned(format!("{GREEN}{prompt}{RESET}")),
+            false => Cow::Borrowed(prompt),
+        }
+    }
+}
+
+impl Helper for PromptHelper {}
+
+impl Default for ReplConfig {
+    fn default() -> Self {
+        Self {
+            prompt: ">> ".to_string(),
+            continuation_prompt: ".. ".to_string(),
+            banner: "Hello This is the Monkey programming language!\nFeel free to type in commands"
+                .to_string(),
+            color: false,
+            stdlib: true,
+            init_script: None,
+            rc_file: user_rc_file(),
+            history_file: user_history_file(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Repl {
+    pub config: ReplConfig,
+}
 
 impl Repl {
-    pub fn start(&self) {
-        println!("Hello This is the Monkey programming language!");
-        println!("Feel free to type in commands");
-        let new_env = Environment::default();
-        let env = Rc::new(RefCell::new(new_env));
+    pub fn new(config: ReplConfig) -> Self {
+        Self { config }
+    }
 
-        loop {
-            let mut input = String::new();
+    pub fn start(&mut self) {
+        let cancellation = CancellationToken::new();
+        let runtime = Runtime {
+            cancellation: Some(cancellation.clone()),
+            ..Default::default()
+        };
+        let env = Rc::new(RefCell::new(Environment::with_runtime(runtime)));
 
-            io::stdin()
-                .read_line(&mut input)
-                .expect("Failed to read line");
+        self.load_stdlib(&env);
+        if let Some(script) = &self.config.init_script {
+            if let Err(err) = self.eval(script, &env) {
+                self.print_error(&err, script);
+            }
+        }
+        if let Some(path) = self.config.rc_file.clone() {
+            if let Err(err) = self.run_rc_file(&path, &env) {
+                self.print_error(&err, "");
+            }
+            if let Err(err) = self.apply_settings(&env) {
+                self.print_error(&err.context(format!("{}", path.display())), "");
+            }
+        }
+        if !self.config.banner.is_empty() {
+            println!("{}", self.config.banner);
+        }
+
+        let mut editor = match Editor::<PromptHelper, DefaultHistory>::new() {
+            Ok(editor) => editor,
+            Err(err) => {
+                self.print_error(&err.into(), "");
+                return;
+            }
+        };
+        editor.set_helper(Some(PromptHelper {
+            color: self.config.color,
+        }));
+        if let Some(path) = &self.config.history_file {
+            let _ = editor.load_history(path);
+        }
 
-            input = input.trim().to_string();
+        let mut last = None;
+        let mut pending = String::new();
+        loop {
+            let prompt = match pending.is_empty() {
+                true => &self.config.prompt,
+                false => &self.config.continuation_prompt,
+            };
+            let line = match editor.readline(prompt) {
+                Ok(line) => line,
+                // Ctrl-C drops the current input, Ctrl-D ends the session.
+                Err(ReadlineError::Interrupted) => {
+                    pending.clear();
+                    continue;
+                }
+                Err(ReadlineError::Eof) => break,
+                Err(err) => {
+                    self.print_error(&err.into(), "");
+                    break;
+                }
+            };
+            if !pending.is_empty() {
+                pending.push('\n');
+            }
+            pending.push_str(&line);
+            if is_incomplete(&pending) {
+                continue;
+            }
+            let input = pending.trim().to_string();
+            pending.clear();
+            if !input.is_empty() {
+                let _ = editor.add_history_entry(input.as_str());
+            }
 
             if input == "exit" {
-                return;
+                break;
             }
             if input.is_empty() {
                 continue;
             }
+            if input == ":show full" {
+                if let Some(value) = &last {
+                    println!("{value}");
+                }
+                continue;
+            }
+            if input == ":env" {
+                print!("{}", list_bindings(&env.borrow(), DEFAULT_COLUMNS));
+                continue;
+            }
+            if input == ":clear" {
+                env.borrow_mut().clear();
+                self.load_stdlib(&env);
+                last = None;
+                continue;
+            }
 
-            let lexer = Lexer::new(input.as_str());
-            let mut parser = Parser::new(lexer.peekable());
-            let mut program = Program::default();
-            let eval = program.eval(&mut parser, env.clone());
-            match eval {
+            match self.eval_interruptible(&input, &env, &cancellation) {
                 Ok(stack) => {
-                    println!("{}", stack)
+                    println!("{}", render_truncated(&stack, output_budget()));
+                    last = Some(stack);
                 }
-                Err(err) => {
-                    println!("error: {:?}", err)
+                Err(err) => self.print_error(&err, &input),
+            }
+        }
+
+        if let Some(path) = &self.config.history_file {
+            if let Err(err) = editor.save_history(path) {
+                self.print_error(&err.into(), "");
+            }
+        }
+    }
+
+    fn load_stdlib(&self, env: &GlobalEnv) {
+        if self.config.stdlib {
+            if let Err(err) = stdlib::load(env, Engine::default()) {
+                self.print_error(&err, stdlib::SOURCE);
+            }
+        }
+    }
+
+    /// Evaluates the rc file into `env`. A missing file is not an error.
+    fn run_rc_file(&self, path: &Path, env: &GlobalEnv) -> Result<()> {
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(Error::new(err).context(format!("{}", path.display()))),
+        };
+        self.eval(&source, env)
+            .map(|_| ())
+            .map_err(|err| err.context(format!("{}", path.display())))
+    }
+
+    /// Applies the settings in the `repl` hash the rc file bound, if any.
+    fn apply_settings(&mut self, env: &GlobalEnv) -> Result<()> {
+        let Some(settings) = env.borrow().get("repl") else {
+            return Ok(());
+        };
+        let Object::Hash(settings) = settings else {
+            bail!("repl settings must be a hash, got {}", settings.name());
+        };
+        for (key, value) in settings.iter() {
+            let config = &mut self.config;
+            match (key.to_string().as_str(), value) {
+                ("prompt", Object::String(value)) => config.prompt = value.to_string(),
+                ("continuation_prompt", Object::String(value)) => {
+                    config.continuation_prompt = value.to_string()
+                }
+                ("banner", Object::String(value)) => config.banner = value.to_string(),
+                ("color", Object::Bool(value)) => config.color = *value,
+                ("prompt" | "continuation_prompt" | "banner", value) => {
+                    bail!("repl setting {key} must be a string, got {}", value.name())
                 }
+                ("color", value) => {
+                    bail!("repl setting color must be a bool, got {}", value.name())
+                }
+                (key, _) => bail!("unknown repl setting: {key}"),
             }
         }
+        Ok(())
+    }
+
+    fn eval(&self, input: &str, env: &GlobalEnv) -> Result<Object> {
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer.tokens());
+        let mut program = Program::default();
+        program.eval(&mut parser, env.clone())
+    }
+
+    /// Evaluates input typed at the prompt. Ctrl-C cancels it through `cancellation`, which
+    /// is reset for the next prompt.
+    fn eval_interruptible(
+        &self,
+        input: &str,
+        env: &GlobalEnv,
+        cancellation: &CancellationToken,
+    ) -> Result<Object> {
+        let result = {
+            let _interrupt = interrupt::catch(cancellation);
+            self.eval(input, env)
+        };
+        cancellation.reset();
+        result
+    }
+
+    fn print_error(&self, err: &Error, input: &str) {
+        print!("{}", format_error(err, input, self.config.color));
+    }
+}
+
+/// Whether `input` leaves a `{`, `(` or `[` open or stops mid-statement, so the REPL should
+/// read another line before evaluating it. Input with any other parse error is complete, so
+/// the error shows right away.
+fn is_incomplete(input: &str) -> bool {
+    let mut parser = Parser::new(Lexer::new(input).tokens());
+    parser.by_ref().for_each(drop);
+    let errors = parser.errors();
+    if errors
+        .iter()
+        .any(|err| !err.message.contains("end of input"))
+    {
+        return false;
+    }
+    let depth = Lexer::new(input).fold(0isize, |depth, token| match token.kind {
+        TokenType::LBrace | TokenType::LParen | TokenType::LBracket => depth + 1,
+        TokenType::RBrace | TokenType::RParen | TokenType::RBracket => depth - 1,
+        _ => depth,
+    });
+    depth > 0 || !errors.is_empty()
+}
+
+/// Renders an error with its location and kind highlighted and, when the offending
+/// text can be found in `input`, the input line echoed with a caret underneath it.
+fn format_error(err: &Error, input: &str, color: bool) -> String {
+    let runtime_span = err.downcast_ref::<Span>().copied();
+    let mut messages: Vec<String> = err.chain().map(|cause| cause.to_string()).collect();
+    if let Some(span) = runtime_span {
+        let located = span.to_string();
+        messages.retain(|message| *message != located);
+    }
+    messages.dedup();
+    let root = messages.pop().unwrap_or_default();
+    let context: String = messages
+        .iter()
+        .map(|message| format!("{message}: "))
+        .collect();
+
+    let mut out = String::new();
+    for line in root.lines() {
+        // Parse errors carry their own location, runtime errors the statement's.
+        let (span, line) = match split_span(line) {
+            Some((span, rest)) => (Some(span), rest),
+            None => (runtime_span, line),
+        };
+        let (kind, detail) = match line.split_once(": ") {
+            Some((kind, detail)) => (kind, Some(detail)),
+            None => (line, None),
+        };
+        let label = match span {
+            Some(span) => format!("error at {span}:"),
+            None => "error:".to_string(),
+        };
+        if color {
+            out.push_str(&format!("{RED}{label}{RESET} {context}{BOLD}{kind}{RESET}"));
+        } else {
+            out.push_str(&format!("{label} {context}{kind}"));
+        }
+        if let Some(detail) = detail {
+            out.push_str(&format!(": {detail}"));
+        }
+        out.push('\n');
+        let pointer = detail
+            .and_then(|detail| caret(input, detail))
+            .or_else(|| span.and_then(|span| caret_at(input, span)));
+        out.push_str(&pointer.unwrap_or_default());
+    }
+    out
+}
+
+/// Splits a leading `line N, column M: ` off a message.
+fn split_span(message: &str) -> Option<(Span, &str)> {
+    let rest = message.strip_prefix("line ")?;
+    let (line, rest) = rest.split_once(", column ")?;
+    let (column, rest) = rest.split_once(": ")?;
+    Some((Span::new(line.parse().ok()?, column.parse().ok()?), rest))
+}
+
+fn caret_at(input: &str, span: Span) -> Option<String> {
+    let line = input.lines().nth(span.line.checked_sub(1)?)?;
+    Some(format!(
+        "  {line}\n  {}^\n",
+        " ".repeat(span.column.saturating_sub(1))
+    ))
+}
+
+fn caret(input: &str, needle: &str) -> Option<String> {
+    input.lines().find_map(|line| {
+        let offset = line.find(needle)?;
+        let column = line[..offset].chars().count();
+        Some(format!(
+            "  {line}\n  {}{}\n",
+            " ".repeat(column),
+            "^".repeat(needle.chars().count())
+        ))
+    })
+}
+
+/// How many characters fit on the terminal, leaving a line for the next prompt.
+fn output_budget() -> usize {
+    let size = |name, default| {
+        env::var(name)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(default)
+    };
+    let columns: usize = size("COLUMNS", DEFAULT_COLUMNS);
+    let lines: usize = size("LINES", DEFAULT_LINES);
+    columns * lines.saturating_sub(1).max(1)
+}
+
+/// One line per binding of `env` with its type and value, each value cut short to fit
+/// `columns`.
+fn list_bindings(env: &Environment, columns: usize) -> String {
+    env.iter()
+        .map(|(name, value)| {
+            let label = format!("{name}: {} = ", value.name());
+            let budget = columns.saturating_sub(label.chars().count());
+            format!("{label}{}\n", render_truncated(value, budget))
+        })
+        .collect()
+}
+
+/// Renders `object`, cutting arrays and hashes short with a `... (n more elements)`
+/// marker once the output would exceed `budget` characters.
+fn render_truncated(object: &Object, budget: usize) -> String {
+    let full = object.to_string();
+    let length = full.chars().count();
+    if length <= budget {
+        return full;
+    }
+    let (open, close, items): (_, _, Vec<String>) = match object {
+        Object::Array(elements) => ("[", "]", elements.iter().map(|e| e.to_string()).collect()),
+        Object::Hash(pairs) => (
+            "{",
+            "}",
+            pairs.iter().map(|(k, v)| format!("{k}: {v}")).collect(),
+        ),
+        _ => {
+            let shown: String = full.chars().take(budget).collect();
+            return format!("{shown}... ({} more characters)", length - budget);
+        }
+    };
+    let mut out = String::from(open);
+    let mut shown = 0;
+    for item in &items {
+        let separator = if shown == 0 { 0 } else { 2 };
+        if out.chars().count() + separator + item.chars().count() > budget {
+            break;
+        }
+        if shown > 0 {
+            out.push_str(", ");
+        }
+        out.push_str(item);
+        shown += 1;
+    }
+    if shown > 0 {
+        out.push_str(", ");
+    }
+    out.push_str(&format!(
+        "... ({} more elements){close}",
+        items.len() - shown
+    ));
+    out
+}
+
+/// Turns SIGINT into cancelling a token while a statement runs, instead of ending the
+/// process. Readline handles Ctrl-C itself at the prompt.
+#[cfg(unix)]
+mod interrupt {
+    use std::{
+        marker::PhantomData,
+        ptr,
+        sync::atomic::{AtomicPtr, Ordering},
+    };
+
+    use crate::runtime::CancellationToken;
+
+    /// The token to cancel, set while a [`Guard`] is alive.
+    static TOKEN: AtomicPtr<CancellationToken> = AtomicPtr::new(ptr::null_mut());
+
+    extern "C" fn on_sigint(_: libc::c_int) {
+        // SAFETY: the pointer is null or to the token of a live guard, which restores the
+        // previous handler before it goes away. Cancelling is a single atomic store.
+        if let Some(token) = unsafe { TOKEN.load(Ordering::SeqCst).as_ref() } {
+            token.cancel();
+        }
+    }
+
+    /// Puts the previous SIGINT handler back when dropped.
+    pub struct Guard<'a> {
+        previous: libc::sighandler_t,
+        token: PhantomData<&'a CancellationToken>,
+    }
+
+    pub fn catch(token: &CancellationToken) -> Guard<'_> {
+        TOKEN.store(ptr::from_ref(token).cast_mut(), Ordering::SeqCst);
+        let handler = on_sigint as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        // SAFETY: the handler only touches an atomic and the token it points to.
+        let previous = unsafe { libc::signal(libc::SIGINT, handler) };
+        Guard {
+            previous,
+            token: PhantomData,
+        }
+    }
+
+    impl Drop for Guard<'_> {
+        fn drop(&mut self) {
+            // SAFETY: restores the handler that was installed before `catch`.
+            unsafe { libc::signal(libc::SIGINT, self.previous) };
+            TOKEN.store(ptr::null_mut(), Ordering::SeqCst);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod interrupt {
+    use crate::runtime::CancellationToken;
+
+    pub struct Guard;
+
+    pub fn catch(_token: &CancellationToken) -> Guard {
+        Guard
+    }
+}
+
+#[cfg(test)]
+mod repl_tests {
+    use std::{cell::RefCell, fs, rc::Rc};
+
+    use super::{format_error, is_incomplete, list_bindings, render_truncated, Repl, ReplConfig};
+    use crate::{
+        environment::{Environment, GlobalEnv},
+        object::Object,
+    };
+
+    #[cfg(unix)]
+    #[test]
+    fn ctrl_c_cancels_the_running_statement() {
+        use crate::runtime::{CancellationToken, Runtime};
+
+        fn interrupt(_args: Vec<Object>, _env: &GlobalEnv) -> anyhow::Result<Object> {
+            // SAFETY: raising a signal on the current thread has no preconditions.
+            unsafe { libc::raise(libc::SIGINT) };
+            Ok(Object::Nil)
+        }
+
+        let cancellation = CancellationToken::new();
+        let runtime = Runtime {
+            cancellation: Some(cancellation.clone()),
+            ..Default::default()
+        };
+        runtime.builtins.register("interrupt", interrupt).unwrap();
+        let env = Rc::new(RefCell::new(Environment::with_runtime(runtime)));
+        let repl = Repl::default();
+        let err = repl
+            .eval_interruptible("interrupt(); while (true) {}", &env, &cancellation)
+            .unwrap_err();
+        assert_eq!(err.root_cause().to_string(), "evaluation cancelled");
+        assert_eq!(
+            repl.eval_interruptible("1 + 1", &env, &cancellation)
+                .unwrap(),
+            Object::Int(2)
+        );
+    }
+
+    #[test]
+    fn rc_file_defines_globals() {
+        let path = std::env::temp_dir().join("rustmonk_repl_rc_test");
+        let repl = Repl::new(ReplConfig {
+            rc_file: Some(path.clone()),
+            ..Default::default()
+        });
+        let env = Rc::new(RefCell::new(Environment::default()));
+
+        fs::write(&path, "let double = fn(x) { x * 2 }; let broken = missing;").unwrap();
+        let err = repl.run_rc_file(&path, &env).unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            "identifier not found: missing"
+        );
+        assert_eq!(repl.eval("double(4)", &env).unwrap(), Object::Int(8));
+
+        fs::remove_file(&path).unwrap();
+        assert!(repl.run_rc_file(&path, &env).is_ok());
+    }
+
+    #[test]
+    fn rc_file_sets_the_prompts_banner_and_color() {
+        let mut repl = Repl::default();
+        let env = Rc::new(RefCell::new(Environment::default()));
+        assert!(repl.apply_settings(&env).is_ok());
+        assert_eq!(repl.config.prompt, ">> ");
+
+        let settings = r#"let repl = {"prompt": "monkey> ", "continuation_prompt": "...> ", "banner": "", "color": true};"#;
+        repl.eval(settings, &env).unwrap();
+        repl.apply_settings(&env).unwrap();
+        assert_eq!(repl.config.prompt, "monkey> ");
+        assert_eq!(repl.config.continuation_prompt, "...> ");
+        assert_eq!(repl.config.banner, "");
+        assert!(repl.config.color);
+
+        let mut err = |settings: &str| {
+            repl.eval(settings, &env).unwrap();
+            repl.apply_settings(&env).unwrap_err().to_string()
+        };
+        assert_eq!(
+            err(r#"let repl = {"color": "yes"};"#),
+            "repl setting color must be a bool, got string"
+        );
+        assert_eq!(
+            err(r#"let repl = {"banner": 1};"#),
+            "repl setting banner must be a string, got int"
+        );
+        assert_eq!(
+            err(r#"let repl = {"theme": "dark"};"#),
+            "unknown repl setting: theme"
+        );
+        assert_eq!(
+            err("let repl = 1;"),
+            "repl settings must be a hash, got int"
+        );
+    }
+
+    #[test]
+    fn errors_point_at_the_input() {
+        let repl = Repl::default();
+        let env = Rc::new(RefCell::new(Environment::default()));
+        let input = "let a = 1; a + foo";
+        let err = repl.eval(input, &env).unwrap_err();
+        assert_eq!(
+            format_error(&err, input, false),
+            "error at line 1, column 12: identifier not found: foo\n  let a = 1; a + foo\n                 ^^^\n"
+        );
+        assert_eq!(
+            format_error(&err, input, true),
+            "\x1b[31merror at line 1, column 12:\x1b[0m \x1b[1midentifier not found\x1b[0m: foo\n  let a = 1; a + foo\n                 ^^^\n"
+        );
+
+        let input = "let = 1; let b 2";
+        let err = repl.eval(input, &env).unwrap_err();
+        assert_eq!(
+            format_error(&err, input, false),
+            "error at line 1, column 5: expected identifier, got '=' instead\n  let = 1; let b 2\n      ^\n\
+             error at line 1, column 16: expected next token to be '=', got '2' instead\n  let = 1; let b 2\n                 ^\n"
+        );
+
+        let err = anyhow::anyhow!("identifier not found: x")
+            .context("rc")
+            .context("rc");
+        assert_eq!(
+            format_error(&err, "", false),
+            "error: rc: identifier not found: x\n"
+        );
+    }
+
+    #[test]
+    fn unfinished_input_continues() {
+        assert!(is_incomplete("let add = fn(a, b) {"));
+        assert!(is_incomplete("let add = fn(a, b) {\n  a +"));
+        assert!(is_incomplete("puts([1,\n2"));
+        assert!(is_incomplete("let x ="));
+        assert!(is_incomplete("if (x) { 1 } else"));
+        assert!(!is_incomplete("let add = fn(a, b) {\n  a + b\n};"));
+        assert!(!is_incomplete(""));
+        assert!(!is_incomplete(":env"));
+        // Errors the next line can't fix are reported right away.
+        assert!(!is_incomplete("let = 1; {"));
+        assert!(!is_incomplete("1 + }"));
+    }
+
+    #[test]
+    fn lists_and_clears_bindings() {
+        let repl = Repl::default();
+        let env = Rc::new(RefCell::new(Environment::default()));
+        repl.eval(
+            r#"let name = "monkey"; let count = 3; let items = [1, 2, 3, 4, 5, 6, 7, 8];"#,
+            &env,
+        )
+        .unwrap();
+        assert_eq!(
+            env.borrow().names().collect::<Vec<_>>(),
+            vec!["count", "items", "name"]
+        );
+        assert_eq!(
+            list_bindings(&env.borrow(), 30),
+            "count: int = 3\n\
+             items: array = [1, 2, 3, 4, 5, ... (3 more elements)]\n\
+             name: string = monkey\n"
+        );
+
+        env.borrow_mut().clear();
+        assert_eq!(list_bindings(&env.borrow(), 30), "");
+        assert!(repl.eval("count", &env).is_err());
+        assert_eq!(
+            repl.eval("let count = 1; count", &env).unwrap(),
+            Object::Int(1)
+        );
+    }
+
+    #[test]
+    fn large_values_are_truncated() {
+        let repl = Repl::default();
+        let env = Rc::new(RefCell::new(Environment::default()));
+        let array = repl.eval("[1, 2, 3, 4, 5, 6]", &env).unwrap();
+        assert_eq!(render_truncated(&array, 100), "[1, 2, 3, 4, 5, 6]");
+        assert_eq!(
+            render_truncated(&array, 8),
+            "[1, 2, 3, ... (3 more elements)]"
+        );
+        assert_eq!(render_truncated(&array, 0), "[... (6 more elements)]");
+
+        let hash = repl.eval(r#"{"a": 1, "b": 2}"#, &env).unwrap();
+        assert_eq!(render_truncated(&hash, 6), "{a: 1, ... (1 more elements)}");
+
+        let string = repl.eval(r#""monkey""#, &env).unwrap();
+        assert_eq!(render_truncated(&string, 3), "mon... (3 more characters)");
     }
 }
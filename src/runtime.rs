@@ -0,0 +1,400 @@
+use alloc::{
+    borrow::Cow,
+    collections::BTreeSet,
+    rc::{Rc, Weak},
+    sync::Arc,
+};
+use anyhow::Result;
+use core::{
+    cell::{Cell, OnceCell, RefCell},
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{
+    environment::{Environment, GlobalEnv},
+    eval::{Engine, EvalError},
+    gc,
+    prelude::*,
+};
+
+const MAX_POOLED_FRAMES: usize = 64;
+
+/// Steps a [`Runtime::deterministic`] program may take.
+const DETERMINISTIC_FUEL: u64 = 1_000_000;
+
+/// Seed of a [`Runtime::deterministic`] program's random numbers.
+const DETERMINISTIC_SEED: u64 = 0x853c_49e6_748f_ea9b;
+
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// What a runtime's builtins may reach outside the interpreter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Opening files with `open`.
+    pub io: bool,
+}
+
+impl Capabilities {
+    pub const NONE: Self = Self { io: false };
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self { io: true }
+    }
+}
+
+/// Bounds on what a program may use of the host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Calls to Monkey functions that may be in progress at once. Tail calls don't count.
+    pub max_depth: usize,
+    /// Steps a program may take before it is stopped, `None` for no limit. A step is a
+    /// statement, loop iteration or call on the tree-walker and a jump or call on the VM.
+    pub fuel: Option<u64>,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_depth: 256,
+            fuel: None,
+        }
+    }
+}
+
+/// Where `puts` and `print` write: standard output unless the host hands in a writer.
+/// Clones write to the same writer.
+#[derive(Clone)]
+pub struct Output(Rc<RefCell<Box<dyn fmt::Write>>>);
+
+impl Output {
+    pub fn new(writer: impl fmt::Write + 'static) -> Self {
+        Self(Rc::new(RefCell::new(Box::new(writer))))
+    }
+
+    pub fn write_str(&self, text: &str) -> fmt::Result {
+        self.0.borrow_mut().write_str(text)
+    }
+}
+
+impl Default for Output {
+    #[cfg(feature = "std")]
+    fn default() -> Self {
+        Self::new(Stdout)
+    }
+
+    /// Without `std` there is nowhere to print to, so output is dropped.
+    #[cfg(not(feature = "std"))]
+    fn default() -> Self {
+        Self::new(String::new())
+    }
+}
+
+impl fmt::Debug for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Output")
+    }
+}
+
+#[cfg(feature = "std")]
+struct Stdout;
+
+#[cfg(feature = "std")]
+impl fmt::Write for Stdout {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        use std::io::Write;
+        std::io::stdout()
+            .write_all(text.as_bytes())
+            .map_err(|_| fmt::Error)
+    }
+}
+
+/// A writer that keeps what was written, for capturing a program's output.
+#[derive(Debug, Clone, Default)]
+pub struct Capture(Rc<RefCell<String>>);
+
+impl Capture {
+    pub fn contents(&self) -> String {
+        self.0.borrow().clone()
+    }
+}
+
+impl fmt::Write for Capture {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.0.borrow_mut().push_str(text);
+        Ok(())
+    }
+}
+
+/// The random numbers behind `uuid` and `nanoid`, from a splitmix64 generator. Not for
+/// secrets: anyone who sees enough output can predict the rest.
+#[derive(Debug, Clone)]
+pub struct Random(Cell<u64>);
+
+impl Random {
+    pub fn with_seed(seed: u64) -> Self {
+        Self(Cell::new(seed))
+    }
+
+    pub fn next_u64(&self) -> u64 {
+        let state = self.0.get().wrapping_add(0x9e37_79b9_7f4a_7c15);
+        self.0.set(state);
+        let z = (state ^ (state >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        let z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+impl Default for Random {
+    /// Seeded from the process's hash keys and the clock, so every run differs.
+    #[cfg(feature = "std")]
+    fn default() -> Self {
+        use std::{collections::hash_map::RandomState, hash::BuildHasher, time::SystemTime};
+        Self::with_seed(RandomState::new().hash_one(SystemTime::now()))
+    }
+
+    /// Without `std` there is no source of entropy, so every run gets the same numbers.
+    #[cfg(not(feature = "std"))]
+    fn default() -> Self {
+        Self::with_seed(DETERMINISTIC_SEED)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Interner(RefCell<BTreeSet<Rc<str>>>);
+
+impl Interner {
+    pub fn intern(&self, value: &str) -> Rc<str> {
+        if let Some(interned) = self.0.borrow().get(value) {
+            return interned.clone();
+        }
+        let interned: Rc<str> = Rc::from(value);
+        self.0.borrow_mut().insert(interned.clone());
+        interned
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.borrow().is_empty()
+    }
+}
+
+#[derive(Default)]
+pub struct Runtime {
+    pub cancellation: Option<CancellationToken>,
+    pub capabilities: Capabilities,
+    pub limits: Limits,
+    /// Remember where variables got a nil value, and say so when an operator fails on one.
+    /// Only the tree-walking evaluator traces nils.
+    pub trace_nil: bool,
+    /// Calls to Monkey functions in progress on the tree-walking evaluator.
+    pub depth: Cell<usize>,
+    /// Steps taken so far, counted against [`Limits::fuel`].
+    pub steps: Cell<u64>,
+    /// How the current program runs, set by [`crate::eval::Program`].
+    pub engine: Cell<Engine>,
+    pub optimize: Cell<bool>,
+    /// Re-compile functions the VM calls often with the small functions they call inlined.
+    pub inline_hot: Cell<bool>,
+    /// Fold identifiers to lowercase wherever they are bound or looked up.
+    pub case_insensitive: Cell<bool>,
+    /// Whether a macro has been defined, so later statements need expanding.
+    pub macros: Cell<bool>,
+    pub output: Output,
+    /// The builtins programs can call; register more with [`crate::builtins::Registry::register`].
+    pub builtins: crate::builtins::Registry,
+    /// Where `uuid` and `nanoid` draw from; give it a fixed seed for repeatable ids.
+    pub random: Random,
+    pub interner: Interner,
+    /// Call frames kept for reuse by functions that cannot leak their environment.
+    pub frames: RefCell<Vec<GlobalEnv>>,
+    /// What pooled frames point to instead of this runtime, so they don't keep it alive.
+    pub detached: OnceCell<Rc<Runtime>>,
+    /// Call frames created so far, for [`crate::gc::collect_cycles`].
+    pub tracked: RefCell<Vec<Weak<RefCell<Environment>>>>,
+    /// How many tracked frames set off the next collection.
+    pub collect_at: Cell<usize>,
+    /// Where global bindings are written through to, see [`crate::store::FileStore`].
+    #[cfg(feature = "std")]
+    pub store: Option<crate::store::FileStore>,
+    #[cfg(feature = "vm")]
+    pub vm: RefCell<crate::vm::State>,
+}
+
+impl fmt::Debug for Runtime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("Runtime");
+        debug
+            .field("cancellation", &self.cancellation)
+            .field("capabilities", &self.capabilities)
+            .field("limits", &self.limits)
+            .field("trace_nil", &self.trace_nil)
+            .field("depth", &self.depth.get())
+            .field("steps", &self.steps.get())
+            .field("engine", &self.engine.get())
+            .field("optimize", &self.optimize.get())
+            .field("inline_hot", &self.inline_hot.get())
+            .field("case_insensitive", &self.case_insensitive.get())
+            .field("macros", &self.macros.get())
+            .field("output", &self.output)
+            .field("builtins", &self.builtins)
+            .field("random", &self.random)
+            .field("interner", &self.interner)
+            .field("frames", &self.frames.borrow().len())
+            .field("tracked", &self.tracked.borrow().len())
+            .field("collect_at", &self.collect_at.get());
+        #[cfg(feature = "std")]
+        debug.field("store", &self.store);
+        debug.finish()
+    }
+}
+
+/// A runtime with the same settings, limits, builtins and output, for another interpreter to
+/// run on. What a run keeps track of, like its depth, steps, frames and VM state, starts
+/// afresh, and the store stays with this runtime.
+impl Clone for Runtime {
+    fn clone(&self) -> Self {
+        Self {
+            cancellation: self.cancellation.clone(),
+            capabilities: self.capabilities,
+            limits: self.limits,
+            trace_nil: self.trace_nil,
+            engine: self.engine.clone(),
+            optimize: self.optimize.clone(),
+            inline_hot: self.inline_hot.clone(),
+            case_insensitive: self.case_insensitive.clone(),
+            output: self.output.clone(),
+            builtins: self.builtins.clone(),
+            random: self.random.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Runtime {
+    /// A sandbox for reproducible runs: builtins can't reach outside the interpreter and a
+    /// program runs out of fuel instead of looping forever. Hashes already iterate in key
+    /// order, there are no clock builtins and random ids come from a fixed seed, so the same
+    /// program always gives the same result.
+    pub fn deterministic() -> Self {
+        Self {
+            capabilities: Capabilities::NONE,
+            limits: Limits {
+                fuel: Some(DETERMINISTIC_FUEL),
+                ..Default::default()
+            },
+            random: Random::with_seed(DETERMINISTIC_SEED),
+            ..Default::default()
+        }
+    }
+
+    /// A pooled frame, attached to `runtime` again.
+    pub fn take_frame(runtime: &Rc<Runtime>) -> Option<GlobalEnv> {
+        let frame = runtime.frames.borrow_mut().pop()?;
+        frame.borrow_mut().runtime = runtime.clone();
+        Some(frame)
+    }
+
+    pub fn recycle_frame(&self, frame: GlobalEnv) {
+        if Rc::strong_count(&frame) != 1 || self.frames.borrow().len() >= MAX_POOLED_FRAMES {
+            return;
+        }
+        {
+            let mut env = frame.borrow_mut();
+            env.store.clear();
+            env.outer = None;
+            env.isolated = false;
+            env.deferred.clear();
+            env.nil_origins.clear();
+            env.runtime = self.detached.get_or_init(Default::default).clone();
+        }
+        self.frames.borrow_mut().push(frame);
+    }
+
+    /// Hands a new call frame to the cycle collector, collecting once twice as many frames
+    /// are tracked as survived the last collection.
+    pub fn track(&self, frame: &GlobalEnv) {
+        self.tracked.borrow_mut().push(Rc::downgrade(frame));
+        if self.tracked.borrow().len() >= self.collect_at.get().max(gc::COLLECT_AFTER) {
+            gc::collect_cycles(self);
+            self.collect_at.set(2 * self.tracked.borrow().len());
+        }
+    }
+
+    pub fn intern(&self, value: &str) -> Rc<str> {
+        self.interner.intern(value)
+    }
+
+    /// `name` as identifiers are compared: lowercased when [`Runtime::case_insensitive`] is set.
+    pub fn identifier<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        match self.case_insensitive.get() && name.chars().any(char::is_uppercase) {
+            true => Cow::Owned(name.to_lowercase()),
+            false => Cow::Borrowed(name),
+        }
+    }
+
+    /// Interns the [`identifier`](Runtime::identifier) form of `name`.
+    pub fn intern_identifier(&self, name: &str) -> Rc<str> {
+        self.intern(&self.identifier(name))
+    }
+
+    /// Counts a call as in progress until the returned guard drops, failing once that would
+    /// exceed [`Limits::max_depth`].
+    pub fn enter(&self) -> Result<Depth<'_>> {
+        if self.depth.get() >= self.limits.max_depth {
+            return Err(EvalError::StackOverflow.into());
+        }
+        self.depth.set(self.depth.get() + 1);
+        Ok(Depth(self))
+    }
+
+    /// Counts one step of evaluation, failing once the program is cancelled or out of fuel.
+    pub fn step(&self) -> Result<()> {
+        self.check_cancelled()?;
+        let steps = self.steps.get() + 1;
+        if self.limits.fuel.is_some_and(|fuel| steps > fuel) {
+            return Err(EvalError::OutOfFuel.into());
+        }
+        self.steps.set(steps);
+        Ok(())
+    }
+
+    pub fn check_cancelled(&self) -> Result<()> {
+        match &self.cancellation {
+            Some(token) if token.is_cancelled() => Err(EvalError::Cancelled.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+pub struct Depth<'a>(&'a Runtime);
+
+impl Drop for Depth<'_> {
+    fn drop(&mut self) {
+        self.0.depth.set(self.0.depth.get() - 1);
+    }
+}
@@ -0,0 +1,269 @@
+use alloc::collections::BTreeMap;
+use core::ops::Range;
+
+use crate::{builtins, lexer::Lexer, parser::Parser, prelude::*, syntax::SyntaxNode};
+
+/// What an identifier refers to, for semantic highlighting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Classification {
+    Parameter,
+    Local,
+    Global,
+    Builtin,
+    /// A name bound to a function literal with `let`.
+    Function,
+    /// Not bound anywhere in the source and not a builtin.
+    Unresolved,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemanticToken {
+    /// Byte offsets of the identifier.
+    pub range: Range<usize>,
+    pub classification: Classification,
+}
+
+/// Classifies every identifier occurrence in `source`, declarations and uses alike, in
+/// source order. Names resolve the way the evaluator scopes them: functions open a scope,
+/// blocks don't. Statements that fail to parse produce no tokens.
+pub fn semantic_tokens(source: &str) -> Vec<SemanticToken> {
+    resolve(source)
+        .into_iter()
+        .map(|resolved| resolved.token)
+        .collect()
+}
+
+pub(crate) struct Resolved {
+    pub token: SemanticToken,
+    /// Declares or refers to a binding outside of any function. Unresolved names count, as
+    /// at run time they can only be globals declared further down.
+    pub global: bool,
+}
+
+pub(crate) fn resolve(source: &str) -> Vec<Resolved> {
+    let mut parser = Parser::new(Lexer::new(source).tokens()).record_syntax(true);
+    parser.by_ref().for_each(drop);
+    let Some(tree) = parser.syntax_tree() else {
+        return vec![];
+    };
+    let mut resolver = Resolver {
+        source,
+        scopes: vec![BTreeMap::new()],
+        tokens: vec![],
+    };
+    resolver.visit(&tree);
+    resolver
+        .tokens
+        .sort_by_key(|resolved| resolved.token.range.start);
+    resolver.tokens
+}
+
+struct Resolver<'a> {
+    source: &'a str,
+    scopes: Vec<BTreeMap<&'a str, Classification>>,
+    tokens: Vec<Resolved>,
+}
+
+impl<'a> Resolver<'a> {
+    fn visit(&mut self, node: &SyntaxNode) {
+        match (node.kind, node.children.as_slice()) {
+            ("let_statement", [name, value @ ..]) => {
+                let function = value
+                    .first()
+                    .is_some_and(|value| matches!(value.kind, "function" | "macro"));
+                let classification = match (function, self.scopes.len()) {
+                    (true, _) => Classification::Function,
+                    (false, 1) => Classification::Global,
+                    (false, _) => Classification::Local,
+                };
+                // A function may call itself, any other value can't see its own name.
+                if function {
+                    self.declare(name, classification);
+                }
+                value.iter().for_each(|child| self.visit(child));
+                if !function {
+                    self.declare(name, classification);
+                }
+            }
+            // The loop variables are bound in a scope of their own each iteration. A second
+            // one follows a comma, where otherwise the iterable would follow `in`.
+            ("for_statement", [name, rest @ ..]) => {
+                let (value, rest) = match rest {
+                    [value, rest @ ..]
+                        if self.source[name.range.end..value.range.start].contains(',') =>
+                    {
+                        (Some(value), rest)
+                    }
+                    rest => (None, rest),
+                };
+                let [iterable, body @ ..] = rest else {
+                    return;
+                };
+                self.visit(iterable);
+                self.scopes.push(BTreeMap::new());
+                self.declare(name, Classification::Local);
+                if let Some(value) = value {
+                    self.declare(value, Classification::Local);
+                }
+                body.iter().for_each(|child| self.visit(child));
+                self.scopes.pop();
+            }
+            // The element comes first in the source but sees the variables, like the condition.
+            ("comprehension", [element, name, rest @ ..]) => {
+                let (value, rest) = match rest {
+                    [value, rest @ ..]
+                        if self.source[name.range.end..value.range.start].contains(',') =>
+                    {
+                        (Some(value), rest)
+                    }
+                    rest => (None, rest),
+                };
+                let [iterable, condition @ ..] = rest else {
+                    return;
+                };
+                self.visit(iterable);
+                self.scopes.push(BTreeMap::new());
+                self.declare(name, Classification::Local);
+                if let Some(value) = value {
+                    self.declare(value, Classification::Local);
+                }
+                condition.iter().for_each(|child| self.visit(child));
+                self.visit(element);
+                self.scopes.pop();
+            }
+            ("function" | "macro", children) => {
+                self.scopes.push(BTreeMap::new());
+                for child in children {
+                    match child.kind {
+                        "identifier" => self.declare(child, Classification::Parameter),
+                        _ => self.visit(child),
+                    }
+                }
+                self.scopes.pop();
+            }
+            ("identifier", _) => {
+                let name = &self.source[node.range.clone()];
+                let found = self
+                    .scopes
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .find_map(|(depth, scope)| Some((depth, *scope.get(name)?)));
+                let (classification, global) = match found {
+                    Some((depth, classification)) => (classification, depth == 0),
+                    None if builtins::lookup(name).is_some() => (Classification::Builtin, false),
+                    None => (Classification::Unresolved, true),
+                };
+                self.push(node, classification, global);
+            }
+            (_, children) => children.iter().for_each(|child| self.visit(child)),
+        }
+    }
+
+    fn declare(&mut self, node: &SyntaxNode, classification: Classification) {
+        let name = &self.source[node.range.clone()];
+        let global = self.scopes.len() == 1;
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name, classification);
+        }
+        self.push(node, classification, global);
+    }
+
+    fn push(&mut self, node: &SyntaxNode, classification: Classification, global: bool) {
+        self.tokens.push(Resolved {
+            token: SemanticToken {
+                range: node.range.clone(),
+                classification,
+            },
+            global,
+        });
+    }
+}
+
+#[cfg(test)]
+mod semantic_tests {
+    use super::{semantic_tokens, Classification};
+    use crate::prelude::*;
+
+    #[test]
+    fn classifies_identifiers() {
+        use Classification::*;
+
+        let source = "let limit = 2;\n\
+                      let count = fn(items) { let n = len(items); if (n > limit) { count(rest(items)) } else { n } };\n\
+                      count(missing)";
+        let tokens: Vec<(&str, Classification)> = semantic_tokens(source)
+            .into_iter()
+            .map(|token| (&source[token.range], token.classification))
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                ("limit", Global),
+                ("count", Function),
+                ("items", Parameter),
+                ("n", Local),
+                ("len", Builtin),
+                ("items", Parameter),
+                ("n", Local),
+                ("limit", Global),
+                ("count", Function),
+                ("rest", Builtin),
+                ("items", Parameter),
+                ("n", Local),
+                ("count", Function),
+                ("missing", Unresolved),
+            ]
+        );
+    }
+
+    #[test]
+    fn declarations_without_a_value() {
+        use Classification::*;
+
+        let tokens = semantic_tokens("let x; x = 1; let f = fn() { let y; y }");
+        let classifications: Vec<Classification> =
+            tokens.iter().map(|token| token.classification).collect();
+        assert_eq!(
+            classifications,
+            vec![Global, Global, Function, Local, Local]
+        );
+    }
+
+    #[test]
+    fn loop_variables_are_local_to_the_loop() {
+        use Classification::*;
+
+        let tokens = semantic_tokens("let xs = [1]; for (x in xs) { puts(x) } x");
+        let classifications: Vec<Classification> =
+            tokens.iter().map(|token| token.classification).collect();
+        assert_eq!(
+            classifications,
+            vec![Global, Local, Global, Builtin, Local, Unresolved]
+        );
+
+        let tokens = semantic_tokens("let h = {}; for (k, v in h) { [k, v] } v");
+        let classifications: Vec<Classification> =
+            tokens.iter().map(|token| token.classification).collect();
+        assert_eq!(
+            classifications,
+            vec![Global, Local, Local, Global, Local, Local, Unresolved]
+        );
+
+        let tokens = semantic_tokens("let xs = []; [x + y for x, y in xs if x] x");
+        let classifications: Vec<Classification> =
+            tokens.iter().map(|token| token.classification).collect();
+        assert_eq!(
+            classifications,
+            vec![Global, Local, Local, Local, Local, Global, Local, Unresolved]
+        );
+    }
+
+    #[test]
+    fn values_cannot_see_their_own_name() {
+        let tokens = semantic_tokens("let x = x;");
+        assert_eq!(tokens[0].classification, Classification::Global);
+        assert_eq!(tokens[1].classification, Classification::Unresolved);
+        assert_eq!(tokens[1].range, 8..9);
+    }
+}
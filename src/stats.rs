@@ -0,0 +1,188 @@
+use alloc::collections::BTreeMap;
+use core::fmt::{self, Display};
+
+use crate::{
+    ast::{Block, Expression, Function, Statement},
+    prelude::*,
+    visitor::{walk_block, walk_expression, walk_function, walk_statement, Visitor},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionStats {
+    /// The name the function was bound to with `let`, if any.
+    pub name: Option<String>,
+    pub parameters: usize,
+    /// Statements in the body, including those of nested blocks but not of nested functions.
+    pub statements: usize,
+}
+
+/// Node counts, nesting depth, function sizes and identifier usage of a program.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub nodes: BTreeMap<&'static str, usize>,
+    pub max_depth: usize,
+    pub functions: Vec<FunctionStats>,
+    pub identifiers: BTreeMap<String, usize>,
+}
+
+pub fn collect(statements: &[Statement]) -> Stats {
+    let mut collector = Collector::default();
+    for statement in statements {
+        collector.visit_statement(statement);
+    }
+    collector.stats
+}
+
+#[derive(Default)]
+struct Collector {
+    stats: Stats,
+    depth: usize,
+    let_name: Option<String>,
+    /// Indices into `stats.functions` of the functions being walked.
+    open_functions: Vec<usize>,
+}
+
+impl Collector {
+    fn count(&mut self, kind: &'static str) {
+        *self.stats.nodes.entry(kind).or_default() += 1;
+    }
+}
+
+impl Visitor for Collector {
+    fn visit_statement(&mut self, statement: &Statement) {
+        self.count(match statement {
+            Statement::Let { .. } => "Let",
+            Statement::Return(_) => "Return",
+            Statement::Expression(_) => "ExpressionStatement",
+            Statement::Block(_) => "Block",
+            Statement::Defer(_) => "Defer",
+            Statement::While { .. } => "While",
+            Statement::For { .. } => "For",
+            Statement::Break => "Break",
+            Statement::Continue => "Continue",
+        });
+        if let Some(&index) = self.open_functions.last() {
+            self.stats.functions[index].statements += 1;
+        }
+        if let Statement::Let {
+            identifier,
+            expression: Expression::Function(_),
+        } = statement
+        {
+            self.let_name = Some(identifier.get_name());
+        }
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        if let Expression::Identifier(identifier) = expression {
+            *self
+                .stats
+                .identifiers
+                .entry(identifier.get_name())
+                .or_default() += 1;
+        }
+        self.count(match expression {
+            Expression::Identifier(_) => "Identifier",
+            Expression::Literal(_) => "Literal",
+            Expression::Prefix(_) => "Prefix",
+            Expression::Infix(_) => "Infix",
+            Expression::If(_) => "If",
+            Expression::Function(_) => "Function",
+            Expression::Macro(_) => "Macro",
+            Expression::Call(_) => "Call",
+            Expression::Array(_) => "Array",
+            Expression::Comprehension(_) => "Comprehension",
+            Expression::Index(_) => "Index",
+            Expression::Hash(_) => "Hash",
+            Expression::Assign(..) => "Assign",
+        });
+        walk_expression(self, expression);
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        self.depth += 1;
+        self.stats.max_depth = self.stats.max_depth.max(self.depth);
+        walk_block(self, block);
+        self.depth -= 1;
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        self.stats.functions.push(FunctionStats {
+            name: self.let_name.take(),
+            parameters: function.params.len() + usize::from(function.rest.is_some()),
+            statements: 0,
+        });
+        self.open_functions.push(self.stats.functions.len() - 1);
+        walk_function(self, function);
+        self.open_functions.pop();
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "nodes:")?;
+        for (kind, count) in &self.nodes {
+            writeln!(f, "  {kind}: {count}")?;
+        }
+        writeln!(f, "max nesting depth: {}", self.max_depth)?;
+        writeln!(f, "functions:")?;
+        for function in &self.functions {
+            writeln!(
+                f,
+                "  {}: {} parameters, {} statements",
+                function.name.as_deref().unwrap_or("<anonymous>"),
+                function.parameters,
+                function.statements
+            )?;
+        }
+        writeln!(f, "identifiers:")?;
+        let mut identifiers: Vec<_> = self.identifiers.iter().collect();
+        identifiers.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        for (name, count) in identifiers {
+            writeln!(f, "  {name}: {count}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod stats_tests {
+    use super::{collect, FunctionStats};
+    use crate::{lexer::Lexer, parser::Parser, prelude::*};
+
+    #[test]
+    fn collects_program_statistics() {
+        let program = "let add = fn(a, b) { if (a > b) { a } else { let c = a + b; c } };
+            add(1, 2); fn(x) { x }(add(3, 4));";
+        let statements: Vec<_> = Parser::new(Lexer::new(program).tokens())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let stats = collect(&statements);
+
+        assert_eq!(stats.nodes["Function"], 2);
+        assert_eq!(stats.nodes["Call"], 3);
+        assert_eq!(stats.nodes["Let"], 2);
+        assert_eq!(stats.max_depth, 2);
+        assert_eq!(
+            stats.functions,
+            vec![
+                FunctionStats {
+                    name: Some("add".to_string()),
+                    parameters: 2,
+                    statements: 4,
+                },
+                FunctionStats {
+                    name: None,
+                    parameters: 1,
+                    statements: 1,
+                },
+            ]
+        );
+        assert_eq!(stats.identifiers["a"], 3);
+        assert_eq!(stats.identifiers["add"], 2);
+        assert!(stats
+            .to_string()
+            .contains("identifiers:\n  a: 3\n  add: 2\n  b: 2\n"));
+    }
+}
@@ -0,0 +1,8 @@
+// The Monkey standard library, evaluated into the global environment before a script or
+// REPL session starts unless it is disabled.
+
+let abs = fn(n) { if (n < 0) { -n } else { n } };
+
+let max = fn(a, b) { if (a < b) { b } else { a } };
+
+let min = fn(a, b) { if (b < a) { b } else { a } };
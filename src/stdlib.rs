@@ -0,0 +1,69 @@
+use anyhow::Result;
+
+use crate::{
+    environment::GlobalEnv,
+    eval::{Engine, Program},
+    lexer::Lexer,
+    parser::Parser,
+};
+
+/// Source of the standard library: `abs`, `max` and `min`, written in Monkey. `map`, `filter`
+/// and `reduce` are builtins.
+pub const SOURCE: &str = include_str!("stdlib.mk");
+
+/// Evaluates the standard library into `env` on `engine`, so later programs on the same
+/// engine can call it.
+pub fn load(env: &GlobalEnv, engine: Engine) -> Result<()> {
+    let mut parser = Parser::new(Lexer::new(SOURCE).tokens());
+    Program::with_engine(engine).eval(&mut parser, env.clone())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod stdlib_tests {
+    use alloc::rc::Rc;
+    use core::cell::RefCell;
+
+    use super::load;
+    use crate::{
+        environment::Environment,
+        eval::{Engine, Program},
+        lexer::Lexer,
+        parser::Parser,
+        prelude::*,
+    };
+
+    #[test]
+    fn functions() {
+        let engines = [
+            Engine::Tree,
+            #[cfg(feature = "vm")]
+            Engine::Vm,
+        ];
+        let cases = [
+            ("map([1, 2, 3], fn(x) { x * 2 })", "[2, 4, 6]"),
+            (
+                "filter(range(0, 10), fn(x) { x - x / 2 * 2 == 1 })",
+                "[1, 3, 5, 7, 9]",
+            ),
+            ("reduce(range(1, 5), 0, fn(acc, x) { acc + x })", "10"),
+            ("map([], abs)", "[]"),
+            ("range(3, 3)", "[]"),
+            (
+                "[abs(-4), abs(2.5), max(1, 2), max(2, 1), min(1, 2), min(2, 1)]",
+                "[4, 2.5, 2, 2, 1, 1]",
+            ),
+        ];
+        for engine in engines {
+            let env = Rc::new(RefCell::new(Environment::default()));
+            load(&env, engine).unwrap();
+            for (source, expected) in cases {
+                let mut parser = Parser::new(Lexer::new(source).tokens());
+                let value = Program::with_engine(engine)
+                    .eval(&mut parser, env.clone())
+                    .unwrap();
+                assert_eq!(value.to_string(), expected, "{source} on {}", engine.name());
+            }
+        }
+    }
+}
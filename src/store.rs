@@ -0,0 +1,249 @@
+use alloc::{collections::BTreeMap, rc::Rc};
+use anyhow::{bail, Context, Result};
+use core::{cell::RefCell, fmt::Write as _, mem};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    ast::Statement,
+    environment::Environment,
+    lexer::Lexer,
+    object::{Inspect, Object},
+    parser::Parser,
+    prelude::*,
+    runtime::Runtime,
+};
+
+/// A file that keeps the program's global bindings across interpreter restarts.
+///
+/// Each line holds `name = value` with the value written as `inspect` writes it. Only nil,
+/// booleans, numbers, strings and arrays or hashes of those are kept; bindings to
+/// anything else (functions, handles, buffers) are left out of the file.
+#[derive(Debug)]
+pub struct FileStore {
+    path: PathBuf,
+    bindings: Vec<(String, Object)>,
+    /// What the file holds: each value by name, with the literal it is written as.
+    saved: RefCell<BTreeMap<String, (Object, String)>>,
+}
+
+impl FileStore {
+    /// Opens the store at `path`, reading the bindings saved by an earlier run. A missing
+    /// file is an empty store.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err).with_context(|| format!("reading {}", path.display())),
+        };
+        let mut bindings = vec![];
+        let mut saved = BTreeMap::new();
+        for (number, line) in text.lines().enumerate() {
+            let Some((name, value)) = line.split_once(" = ") else {
+                continue;
+            };
+            let object = decode(value)
+                .with_context(|| format!("{}:{}: invalid value", path.display(), number + 1))?;
+            bindings.push((name.to_string(), object.clone()));
+            saved.insert(name.to_string(), (object, value.to_string()));
+        }
+        Ok(Self {
+            path,
+            bindings,
+            saved: RefCell::new(saved),
+        })
+    }
+
+    /// The bindings read when the store was opened.
+    pub fn bindings(&self) -> &[(String, Object)] {
+        &self.bindings
+    }
+
+    /// Writes the storable `bindings` to disk, replacing what the file held. Only values
+    /// that changed since the last write are encoded, and the file is only rewritten when
+    /// one did.
+    pub fn sync<'a>(
+        &self,
+        bindings: impl IntoIterator<Item = (&'a str, &'a Object)>,
+    ) -> Result<()> {
+        let mut saved = self.saved.borrow_mut();
+        let mut gone = mem::take(&mut *saved);
+        let mut changed = false;
+        for (name, value) in bindings {
+            let entry = match gone.remove(name) {
+                Some(entry) if entry.0 == *value => entry,
+                old => {
+                    let Some(literal) = encode(value) else {
+                        changed |= old.is_some();
+                        continue;
+                    };
+                    changed = true;
+                    (value.clone(), literal)
+                }
+            };
+            saved.insert(name.to_string(), entry);
+        }
+        if !changed && gone.is_empty() {
+            return Ok(());
+        }
+        let mut text = String::new();
+        for (name, (_, literal)) in saved.iter() {
+            let _ = writeln!(text, "{name} = {literal}");
+        }
+        // Write to a sibling file first so a crash never leaves a half-written store.
+        let temporary = self.path.with_extension("tmp");
+        let written = fs::write(&temporary, text).and_then(|_| fs::rename(&temporary, &self.path));
+        if written.is_err() {
+            // Write everything again next time.
+            saved.clear();
+        }
+        written.with_context(|| format!("writing {}", self.path.display()))
+    }
+}
+
+/// Whether `object` is nil, a boolean, a number, a string, or an array or hash of those,
+/// which [`Inspect`] writes as a literal that evaluates back to it.
+fn storable(object: &Object) -> bool {
+    match object {
+        Object::Nil | Object::Bool(_) | Object::Int(_) | Object::String(_) => true,
+        Object::Float(float) => float.is_finite(),
+        Object::Array(elements) => elements.iter().all(storable),
+        Object::Hash(pairs) => pairs.values().all(storable),
+        _ => false,
+    }
+}
+
+/// Renders `object` as a literal [`decode`] reads back, or `None` if it can't be stored.
+pub fn encode(object: &Object) -> Option<String> {
+    storable(object).then(|| Inspect(object).to_string())
+}
+
+/// Reads a literal written by [`encode`], evaluating it in a sandbox.
+pub fn decode(text: &str) -> Result<Object> {
+    let mut parser = Parser::new(Lexer::new(text).tokens());
+    let statements: Vec<Statement> = parser.by_ref().flatten().collect();
+    if let Some(err) = parser.errors().first() {
+        bail!("{err}");
+    }
+    let [Statement::Expression(expression)] = statements.as_slice() else {
+        bail!("expected a single value");
+    };
+    let env = Environment::with_runtime(Runtime::deterministic());
+    let value = expression.eval(Rc::new(RefCell::new(env)))?;
+    match storable(&value) {
+        true => Ok(value),
+        false => bail!("{} is not a stored value", value.name()),
+    }
+}
+
+#[cfg(test)]
+mod store_tests {
+    use super::*;
+    use crate::object::Shared;
+
+    #[test]
+    fn values_round_trip() {
+        let values = [
+            "nil",
+            "true",
+            "-42",
+            "2.5",
+            r#""say \"hi\"\n\\""#,
+            r#"[1, [2.0, "three"], {}]"#,
+            r#"{1: nil, true: [], "key": {"nested": false}}"#,
+        ];
+        for text in values {
+            let object = decode(text).unwrap();
+            assert_eq!(encode(&object).unwrap(), text);
+        }
+        assert!(decode("[1, 2").is_err());
+        assert!(decode("{[1]: 2}").is_err());
+        assert!(decode("1 2").is_err());
+    }
+
+    #[test]
+    fn only_storable_values_are_written() {
+        let path = std::env::temp_dir().join(format!("monkey-store-{}.db", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let store = FileStore::open(&path).unwrap();
+        assert!(store.bindings().is_empty());
+        let count = Object::Int(3);
+        let names = Object::Array(Shared::new(vec![Object::String("a b".into())]));
+        let buffer = Object::Buffer(Default::default());
+        store
+            .sync([("count", &count), ("names", &names), ("buffer", &buffer)])
+            .unwrap();
+
+        let reopened = FileStore::open(&path).unwrap();
+        assert_eq!(
+            reopened.bindings(),
+            [("count".to_string(), count), ("names".to_string(), names)]
+        );
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn unchanged_values_are_not_written() {
+        let path = std::env::temp_dir().join(format!("monkey-unchanged-{}.db", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let store = FileStore::open(&path).unwrap();
+        let count = Object::Int(3);
+        let names = Object::Array(Shared::new(vec![Object::String("a".into())]));
+        store.sync([("count", &count), ("names", &names)]).unwrap();
+        // With the file gone, only a change brings it back.
+        fs::remove_file(&path).unwrap();
+        let same = Object::Array(Shared::new(vec![Object::String("a".into())]));
+        store.sync([("count", &count), ("names", &same)]).unwrap();
+        assert!(!path.exists());
+        store
+            .sync([("count", &Object::Int(4)), ("names", &same)])
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "count = 4\nnames = [\"a\"]\n"
+        );
+        fs::remove_file(&path).unwrap();
+        store.sync([("count", &Object::Int(4))]).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "count = 4\n");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn bindings_survive_restarts() {
+        use crate::{
+            environment::Environment,
+            eval::{Engine, Program},
+            lexer::Lexer,
+            parser::Parser,
+            runtime::Runtime,
+        };
+        use alloc::rc::Rc;
+
+        let path = std::env::temp_dir().join(format!("monkey-restarts-{}.db", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let run = |engine: Engine, source: &str| {
+            let runtime = Runtime {
+                store: Some(FileStore::open(&path).unwrap()),
+                ..Default::default()
+            };
+            let env = Rc::new(RefCell::new(Environment::with_runtime(runtime)));
+            let mut parser = Parser::new(Lexer::new(source).tokens());
+            Program::with_engine(engine).eval(&mut parser, env).unwrap()
+        };
+
+        run(Engine::Tree, "let count = 0; let double = fn(x) { x * 2 };");
+        run(Engine::Tree, "count = count + 1;");
+        assert_eq!(run(Engine::Tree, "count"), Object::Int(1));
+        #[cfg(feature = "vm")]
+        {
+            run(Engine::Vm, "count = count + 1;");
+            assert_eq!(run(Engine::Tree, "count"), Object::Int(2));
+        }
+        fs::remove_file(&path).unwrap();
+    }
+}
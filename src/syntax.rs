@@ -0,0 +1,190 @@
+use core::{
+    fmt::{self, Display},
+    ops::Range,
+};
+
+use crate::{
+    ast::{Expression, Literal, Statement},
+    prelude::*,
+};
+
+/// A node of the concrete parse tree with the byte range of source it covers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxNode {
+    pub kind: &'static str,
+    pub range: Range<usize>,
+    pub children: Vec<SyntaxNode>,
+}
+
+impl SyntaxNode {
+    fn write(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(
+            f,
+            "{:indent$}({} [{}..{}]",
+            "",
+            self.kind,
+            self.range.start,
+            self.range.end,
+            indent = depth * 2
+        )?;
+        for child in &self.children {
+            writeln!(f)?;
+            child.write(f, depth + 1)?;
+        }
+        f.write_str(")")
+    }
+}
+
+/// Renders the tree as a tree-sitter style S-expression, one node per line.
+impl Display for SyntaxNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+/// Where a node starts: how many nodes were finished before it and its first byte.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Mark {
+    children: usize,
+    start: usize,
+}
+
+/// Collects nodes as the parser finishes them. Every node adopts the nodes finished
+/// since its mark was taken as its children.
+#[derive(Debug, Default)]
+pub(crate) struct SyntaxBuilder {
+    finished: Vec<SyntaxNode>,
+}
+
+impl SyntaxBuilder {
+    pub(crate) fn mark(&self, start: usize) -> Mark {
+        Mark {
+            children: self.finished.len(),
+            start,
+        }
+    }
+
+    pub(crate) fn finish(&mut self, mark: Mark, kind: &'static str, end: usize) {
+        let children: Vec<SyntaxNode> = self.finished.drain(mark.children..).collect();
+        self.finished.push(SyntaxNode {
+            kind,
+            range: mark.start..end,
+            children,
+        });
+    }
+
+    /// Drops the nodes of a statement that failed to parse.
+    pub(crate) fn discard(&mut self, mark: Mark) {
+        self.finished.truncate(mark.children);
+    }
+
+    pub(crate) fn tree(&self, end: usize) -> SyntaxNode {
+        SyntaxNode {
+            kind: "program",
+            range: 0..end,
+            children: self.finished.clone(),
+        }
+    }
+}
+
+pub(crate) fn statement_kind(statement: &Statement) -> &'static str {
+    match statement {
+        Statement::Let { .. } => "let_statement",
+        Statement::Return(_) => "return_statement",
+        Statement::Expression(_) => "expression_statement",
+        Statement::Block(_) => "block",
+        Statement::Defer(_) => "defer_statement",
+        Statement::While { .. } => "while_statement",
+        Statement::For { .. } => "for_statement",
+        Statement::Break => "break_statement",
+        Statement::Continue => "continue_statement",
+    }
+}
+
+pub(crate) fn expression_kind(expression: &Expression) -> &'static str {
+    match expression {
+        Expression::Identifier(_) => "identifier",
+        Expression::Literal(Literal::Int(_)) => "integer",
+        Expression::Literal(Literal::Float(_)) => "float",
+        Expression::Literal(Literal::String(_)) => "string",
+        Expression::Literal(Literal::True) => "true",
+        Expression::Literal(Literal::False) => "false",
+        Expression::Literal(Literal::Nil) => "nil",
+        Expression::Prefix(_) => "prefix_expression",
+        Expression::Infix(_) => "infix_expression",
+        Expression::If(_) => "if_expression",
+        Expression::Function(_) => "function",
+        Expression::Macro(_) => "macro",
+        Expression::Call(_) => "call_expression",
+        Expression::Array(_) => "array",
+        Expression::Comprehension(_) => "comprehension",
+        Expression::Index(_) => "index_expression",
+        Expression::Hash(_) => "hash",
+        Expression::Assign(..) => "assignment_expression",
+    }
+}
+
+#[cfg(test)]
+mod syntax_tests {
+    use crate::{lexer::Lexer, parser::Parser, prelude::*};
+
+    fn dump(source: &str) -> String {
+        let mut parser = Parser::new(Lexer::new(source).tokens()).record_syntax(true);
+        parser.by_ref().for_each(drop);
+        parser.syntax_tree().unwrap().to_string()
+    }
+
+    #[test]
+    fn nodes_cover_their_source() {
+        assert_eq!(
+            dump("let x = (1 + 2) * y;\nf(x)[0]"),
+            "(program [0..28]
+  (let_statement [0..20]
+    (identifier [4..5])
+    (infix_expression [8..19]
+      (parenthesized_expression [8..15]
+        (infix_expression [9..14]
+          (integer [9..10])
+          (integer [13..14])))
+      (identifier [18..19])))
+  (expression_statement [21..28]
+    (index_expression [21..28]
+      (call_expression [21..25]
+        (identifier [21..22])
+        (identifier [23..24]))
+      (integer [26..27]))))"
+        );
+    }
+
+    #[test]
+    fn functions_and_blocks() {
+        assert_eq!(
+            dump("fn(a) { a }"),
+            "(program [0..11]
+  (expression_statement [0..11]
+    (function [0..11]
+      (identifier [3..4])
+      (block [6..11]
+        (expression_statement [8..9]
+          (identifier [8..9]))))))"
+        );
+    }
+
+    #[test]
+    fn each_binding_is_a_statement() {
+        assert_eq!(
+            dump("let a = 1, b;"),
+            "(program [0..13]\n  (let_statement [0..9]\n    (identifier [4..5])\n    (integer [8..9]))\n  (let_statement [11..13]\n    (identifier [11..12])))"
+        );
+    }
+
+    #[test]
+    fn failed_statements_are_dropped() {
+        assert_eq!(
+            dump("let = 1; 2"),
+            "(program [0..10]\n  (expression_statement [9..10]\n    (integer [9..10])))"
+        );
+        let parser = Parser::new(Lexer::new("1").tokens());
+        assert!(parser.syntax_tree().is_none());
+    }
+}
@@ -1,18 +1,38 @@
 use core::fmt;
-use std::fmt::Display;
+use core::fmt::Display;
+use core::ops::Range;
 
 use anyhow::{anyhow, Result};
 
-use crate::ast::InfixOperation;
-#[derive(Debug, Clone, PartialEq, Eq)]
+use crate::{ast::InfixOperation, prelude::*};
+
+#[cfg(all(feature = "int-i32", feature = "int-i128"))]
+compile_error!("features `int-i32` and `int-i128` are mutually exclusive");
+
+#[cfg(feature = "int-i32")]
+pub type Int = i32;
+#[cfg(feature = "int-i128")]
+pub type Int = i128;
+#[cfg(not(any(feature = "int-i32", feature = "int-i128")))]
+pub type Int = i64;
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum TokenType {
     Illegal,
+    /// A literal the lexer recognised but couldn't read, such as `0x1G`.
+    Malformed {
+        text: String,
+        message: String,
+    },
     Identifier(Identifier),
-    Int(i64),
+    Int(Int),
+    Float(f64),
+    String(String),
     True,
     False,
     Nil,
     Dot,
+    Ellipsis,
     Eof,
     Eq,
     NotEq,
@@ -22,6 +42,7 @@ pub enum TokenType {
     Bang,
     Asterisk,
     Slash,
+    Percent,
     Lt,
     Lte,
     Gt,
@@ -37,13 +58,49 @@ pub enum TokenType {
     LBracket,
     RBracket,
     Function,
+    Macro,
     Let,
     If,
     Else,
     Return,
+    Defer,
+    While,
+    For,
+    In,
+    Break,
+    Continue,
+}
+
+/// A 1-based position in the source text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// A token together with where it starts in the source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenType,
+    pub span: Span,
+    /// Byte offsets of the token's text.
+    pub range: Range<usize>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Identifier(pub String);
 
 impl Display for Identifier {
@@ -55,10 +112,53 @@ impl Display for Identifier {
 impl Display for TokenType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            TokenType::Function => write!(f, "fn"),
+            TokenType::Illegal => write!(f, "ILLEGAL"),
+            TokenType::Malformed { text, .. } => write!(f, "{text}"),
             TokenType::Identifier(identifier) => write!(f, "{identifier}"),
-            TokenType::Eq => write!(f, ""),
-            _ => write!(f, ""),
+            TokenType::Int(value) => write!(f, "{value}"),
+            TokenType::Float(value) => write!(f, "{value:?}"),
+            TokenType::String(value) => write!(f, "\"{value}\""),
+            TokenType::True => write!(f, "true"),
+            TokenType::False => write!(f, "false"),
+            TokenType::Nil => write!(f, "nil"),
+            TokenType::Dot => write!(f, "."),
+            TokenType::Ellipsis => write!(f, "..."),
+            TokenType::Eof => write!(f, "EOF"),
+            TokenType::Eq => write!(f, "=="),
+            TokenType::NotEq => write!(f, "!="),
+            TokenType::Assign => write!(f, "="),
+            TokenType::Plus => write!(f, "+"),
+            TokenType::Minus => write!(f, "-"),
+            TokenType::Bang => write!(f, "!"),
+            TokenType::Asterisk => write!(f, "*"),
+            TokenType::Slash => write!(f, "/"),
+            TokenType::Percent => write!(f, "%"),
+            TokenType::Lt => write!(f, "<"),
+            TokenType::Lte => write!(f, "<="),
+            TokenType::Gt => write!(f, ">"),
+            TokenType::Gte => write!(f, ">="),
+            TokenType::Comma => write!(f, ","),
+            TokenType::LineBreak => write!(f, "\\n"),
+            TokenType::Colon => write!(f, ":"),
+            TokenType::Semicolon => write!(f, ";"),
+            TokenType::LParen => write!(f, "("),
+            TokenType::RParen => write!(f, ")"),
+            TokenType::LBrace => write!(f, "{{"),
+            TokenType::RBrace => write!(f, "}}"),
+            TokenType::LBracket => write!(f, "["),
+            TokenType::RBracket => write!(f, "]"),
+            TokenType::Function => write!(f, "fn"),
+            TokenType::Macro => write!(f, "macro"),
+            TokenType::Let => write!(f, "let"),
+            TokenType::If => write!(f, "if"),
+            TokenType::Else => write!(f, "else"),
+            TokenType::Return => write!(f, "return"),
+            TokenType::Defer => write!(f, "defer"),
+            TokenType::While => write!(f, "while"),
+            TokenType::For => write!(f, "for"),
+            TokenType::In => write!(f, "in"),
+            TokenType::Break => write!(f, "break"),
+            TokenType::Continue => write!(f, "continue"),
         }
     }
 }
@@ -88,13 +188,50 @@ impl TryFrom<TokenType> for Identifier {
     }
 }
 
+/// Reserved words and the tokens the lexer produces for them.
+pub const KEYWORDS: &[(&str, TokenType)] = &[
+    ("fn", TokenType::Function),
+    ("macro", TokenType::Macro),
+    ("let", TokenType::Let),
+    ("false", TokenType::False),
+    ("true", TokenType::True),
+    ("if", TokenType::If),
+    ("else", TokenType::Else),
+    ("return", TokenType::Return),
+    ("defer", TokenType::Defer),
+    ("while", TokenType::While),
+    ("for", TokenType::For),
+    ("in", TokenType::In),
+    ("break", TokenType::Break),
+    ("continue", TokenType::Continue),
+    ("nil", TokenType::Nil),
+];
+
+/// Tokens the parser accepts as unary operators.
+pub const PREFIX_OPERATORS: &[TokenType] = &[TokenType::Bang, TokenType::Minus];
+
+/// Tokens the parser accepts between two operands, see [`TokenType::precedence`].
+pub const INFIX_OPERATORS: &[TokenType] = &[
+    TokenType::Eq,
+    TokenType::NotEq,
+    TokenType::Lt,
+    TokenType::Lte,
+    TokenType::Gt,
+    TokenType::Gte,
+    TokenType::Plus,
+    TokenType::Minus,
+    TokenType::Asterisk,
+    TokenType::Slash,
+    TokenType::Percent,
+];
+
 impl TokenType {
     pub fn precedence(&self) -> usize {
         match self {
             TokenType::Eq | TokenType::NotEq => 2,
             TokenType::Gt | TokenType::Gte | TokenType::Lt | TokenType::Lte => 3,
             TokenType::Plus | TokenType::Minus => 4,
-            TokenType::Slash | TokenType::Asterisk => 5,
+            TokenType::Slash | TokenType::Asterisk | TokenType::Percent => 5,
             TokenType::LParen => 7,
             TokenType::LBracket => 8,
             _ => 0,
@@ -106,6 +243,7 @@ impl TokenType {
             TokenType::Minus => Some(InfixOperation::Sub),
             TokenType::Asterisk => Some(InfixOperation::Mul),
             TokenType::Slash => Some(InfixOperation::Div),
+            TokenType::Percent => Some(InfixOperation::Mod),
             TokenType::Eq => Some(InfixOperation::Eq),
             TokenType::NotEq => Some(InfixOperation::NotEq),
             TokenType::Lt => Some(InfixOperation::Lt),
@@ -113,6 +251,7 @@ impl TokenType {
             TokenType::Gt => Some(InfixOperation::Gt),
             TokenType::Gte => Some(InfixOperation::Gte),
             TokenType::LParen => Some(InfixOperation::LParen),
+            TokenType::LBracket => Some(InfixOperation::LBracket),
 
             _ => None,
         }
@@ -0,0 +1,100 @@
+use crate::ast::{Block, Expression, Function, Statement};
+
+/// Walks the AST. Override the methods for the nodes of interest and call the
+/// matching `walk_*` function to keep descending into children.
+pub trait Visitor {
+    fn visit_statement(&mut self, statement: &Statement) {
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        walk_expression(self, expression);
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        walk_block(self, block);
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        walk_function(self, function);
+    }
+}
+
+pub fn walk_block<V: Visitor + ?Sized>(visitor: &mut V, block: &Block) {
+    for statement in &block.0 {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_function<V: Visitor + ?Sized>(visitor: &mut V, function: &Function) {
+    visitor.visit_block(&function.body);
+}
+
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &Statement) {
+    match statement {
+        Statement::Let { expression, .. }
+        | Statement::Return(expression)
+        | Statement::Expression(expression)
+        | Statement::Defer(expression) => visitor.visit_expression(expression),
+        Statement::Block(block) => visitor.visit_block(block),
+        Statement::While { condition, body } => {
+            visitor.visit_expression(condition);
+            visitor.visit_block(body);
+        }
+        Statement::For { iterable, body, .. } => {
+            visitor.visit_expression(iterable);
+            visitor.visit_block(body);
+        }
+        Statement::Break | Statement::Continue => {}
+    }
+}
+
+pub fn walk_expression<V: Visitor + ?Sized>(visitor: &mut V, expression: &Expression) {
+    match expression {
+        Expression::Identifier(_) | Expression::Literal(_) => {}
+        Expression::Prefix(prefix) => visitor.visit_expression(&prefix.expression),
+        Expression::Infix(infix) => {
+            visitor.visit_expression(&infix.left_expression);
+            visitor.visit_expression(&infix.right_expression);
+        }
+        Expression::If(if_expression) => {
+            visitor.visit_expression(&if_expression.condition);
+            visitor.visit_block(&if_expression.consequence);
+            if let Some(alternative) = &if_expression.alternative {
+                visitor.visit_block(alternative);
+            }
+        }
+        Expression::Function(function) | Expression::Macro(function) => {
+            visitor.visit_function(function)
+        }
+        Expression::Call(call) => {
+            visitor.visit_expression(&call.function);
+            for argument in &call.arguments {
+                visitor.visit_expression(argument);
+            }
+        }
+        Expression::Array(elements) => {
+            for element in elements {
+                visitor.visit_expression(element);
+            }
+        }
+        Expression::Comprehension(comprehension) => {
+            visitor.visit_expression(&comprehension.iterable);
+            if let Some(condition) = &comprehension.condition {
+                visitor.visit_expression(condition);
+            }
+            visitor.visit_expression(&comprehension.element);
+        }
+        Expression::Index(index) => {
+            visitor.visit_expression(&index.left);
+            visitor.visit_expression(&index.index);
+        }
+        Expression::Hash(pairs) => {
+            for (key, value) in pairs {
+                visitor.visit_expression(key);
+                visitor.visit_expression(value);
+            }
+        }
+        Expression::Assign(_, value) => visitor.visit_expression(value),
+    }
+}
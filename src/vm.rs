@@ -0,0 +1,1228 @@
+use alloc::{collections::BTreeMap, rc::Rc};
+use anyhow::{anyhow, bail, Result};
+use core::{cell::RefCell, mem};
+
+use crate::{
+    ast::Statement,
+    code::{read_operands, Opcode},
+    compiler::{Bytecode, Compiler, GlobalFunction, SymbolTable},
+    environment::GlobalEnv,
+    eval::EvalError,
+    object::{Closure, CompiledFunction, Coroutine, CoroutineState, Object, Shared},
+    prelude::*,
+    token::Int,
+};
+
+const STACK_SIZE: usize = 2048;
+
+/// How many calls make a function hot enough to re-compile with inlining.
+const HOT_CALLS: u32 = 64;
+
+/// Compiler and VM state that outlives a single run, kept on the runtime so
+/// that later programs see earlier globals.
+#[derive(Debug, Default)]
+pub struct State {
+    pub symbol_table: SymbolTable,
+    pub constants: Vec<Object>,
+    pub globals: Vec<Object>,
+    /// The functions bound by top-level `let`s, recorded when the runtime inlines hot ones.
+    pub functions: BTreeMap<usize, GlobalFunction>,
+    /// The running VM's constants, globals and stack while it calls a builtin, so the
+    /// builtin can call closures back on them.
+    lent: Option<Vm>,
+    /// The value passed to `yield` by the builtin call that just returned.
+    yielded: Option<Object>,
+    /// The closures deferred at the top level, called once the program is done.
+    deferred: Vec<Rc<Closure>>,
+}
+
+/// Compiles and runs statements on the VM, picking up the session state stored
+/// on the runtime. A top-level `return` comes back wrapped in [`Object::Return`].
+pub fn eval(statements: &[Statement], env: &GlobalEnv) -> Result<Object> {
+    eval_located(statements, env).map_err(|(_, err)| err)
+}
+
+/// Like [`eval`], but an error comes with the index of the statement it came from.
+pub fn eval_located(
+    statements: &[Statement],
+    env: &GlobalEnv,
+) -> core::result::Result<Object, (usize, anyhow::Error)> {
+    let runtime = env.borrow().runtime.clone();
+    let state = mem::take(&mut *runtime.vm.borrow_mut());
+    #[cfg(feature = "std")]
+    let state = restore_globals(state, &runtime);
+
+    let mut compiler = Compiler::new_with_state(state.symbol_table, state.constants);
+    compiler.builtins = runtime.builtins.clone();
+    compiler.case_insensitive = runtime.case_insensitive.get();
+    compiler.inline_hot = runtime.inline_hot.get();
+    compiler.functions = state.functions;
+    let compiled = compiler.compile(statements);
+    let starts = mem::take(&mut compiler.statement_starts);
+    let compiled = compiled.map_err(|err| (starts.len().saturating_sub(1), err));
+    let symbol_table = mem::take(&mut compiler.symbol_table);
+    let functions = mem::take(&mut compiler.functions);
+    let bytecode = compiler.bytecode();
+
+    let mut vm = Vm::with_globals(bytecode, state.globals, env.clone());
+    if runtime.inline_hot.get() {
+        vm = vm.with_inlining(functions);
+    }
+    let result = compiled.and_then(|_| vm.run().map_err(|err| (vm.statement(&starts), err)));
+    let value = vm.result();
+    let mut deferred = state.deferred;
+    deferred.append(&mut vm.frames[0].deferred);
+
+    #[cfg(feature = "std")]
+    let result = result.and_then(|_| {
+        match &runtime.store {
+            Some(store) => store.sync(symbol_table.globals().filter_map(|symbol| {
+                let value = vm.globals.get(symbol.index)?;
+                Some((symbol.name.as_str(), value))
+            })),
+            None => Ok(()),
+        }
+        .map_err(|err| (statements.len().saturating_sub(1), err))
+    });
+
+    let functions = vm.inliner.map(|inliner| inliner.functions);
+    *runtime.vm.borrow_mut() = State {
+        symbol_table,
+        constants: vm.constants,
+        globals: vm.globals,
+        functions: functions.unwrap_or_default(),
+        lent: None,
+        yielded: None,
+        deferred,
+    };
+    result.map(|_| value)
+}
+
+/// Calls the closures deferred at the top level, the last deferred first, once the program
+/// is done.
+pub fn run_deferred(env: &GlobalEnv) -> Result<()> {
+    let runtime = env.borrow().runtime.clone();
+    let mut state = mem::take(&mut *runtime.vm.borrow_mut());
+    let deferred = mem::take(&mut state.deferred);
+    let bytecode = Bytecode {
+        instructions: vec![],
+        constants: mem::take(&mut state.constants),
+    };
+    let mut vm = Vm::with_globals(bytecode, mem::take(&mut state.globals), env.clone());
+    let result = vm.call_deferred(deferred);
+    state.constants = vm.constants;
+    state.globals = vm.globals;
+    *runtime.vm.borrow_mut() = state;
+    result
+}
+
+/// Defines the bindings saved in the runtime's store as globals, the first time the VM runs.
+#[cfg(feature = "std")]
+fn restore_globals(mut state: State, runtime: &crate::runtime::Runtime) -> State {
+    if state.globals.is_empty() {
+        for (name, value) in runtime.store.iter().flat_map(|store| store.bindings()) {
+            let symbol = state.symbol_table.define(&runtime.identifier(name));
+            state.globals.resize(symbol.index + 1, Object::Nil);
+            state.globals[symbol.index] = value.clone();
+        }
+    }
+    state
+}
+
+/// Calls `closure` from a builtin the VM is running, on a VM sharing its constants, globals
+/// and stack.
+pub fn call_closure(
+    closure: Rc<Closure>,
+    arguments: Vec<Object>,
+    env: &GlobalEnv,
+) -> Result<Object> {
+    let runtime = env.borrow().runtime.clone();
+    let _depth = runtime.enter()?;
+    let Some(mut vm) = runtime.vm.borrow_mut().lent.take() else {
+        bail!("compiled functions can only be called while the vm runs");
+    };
+    let result = vm.call_back(closure, arguments);
+    runtime.vm.borrow_mut().lent = Some(vm);
+    result
+}
+
+/// Runs `coroutine` from where it last yielded, or from the start, until it yields again or
+/// returns. `value` is what the pending `yield` returns, or the closure's argument when it
+/// starts and takes one.
+pub fn resume(coroutine: &Coroutine, value: Object, env: &GlobalEnv) -> Result<Object> {
+    let runtime = env.borrow().runtime.clone();
+    let _depth = runtime.enter()?;
+    let Some(mut lent) = runtime.vm.borrow_mut().lent.take() else {
+        bail!("coroutines can only be resumed while the vm runs");
+    };
+    let mut vm = Vm {
+        constants: mem::take(&mut lent.constants),
+        stack: vec![],
+        sp: 0,
+        globals: mem::take(&mut lent.globals),
+        frames: vec![],
+        returned: None,
+        env: env.clone(),
+        executed: 0,
+        coroutine: true,
+        suspended: None,
+        inliner: lent.inliner.take(),
+    };
+    let result = vm.resume(coroutine, value);
+    lent.constants = vm.constants;
+    lent.globals = vm.globals;
+    lent.inliner = vm.inliner;
+    lent.executed += vm.executed;
+    runtime.vm.borrow_mut().lent = Some(lent);
+    result
+}
+
+/// Suspends the coroutine running the builtin call that returns next, which then returns
+/// `value` from `resume`.
+pub fn yield_value(value: Object, env: &GlobalEnv) -> Result<()> {
+    let runtime = env.borrow().runtime.clone();
+    let mut state = runtime.vm.borrow_mut();
+    if state.lent.is_none() {
+        bail!("yield outside of a coroutine");
+    }
+    state.yielded = Some(value);
+    Ok(())
+}
+
+/// A coroutine's stack and frames while it waits in `yield`.
+#[derive(Debug)]
+pub struct Suspended {
+    stack: Vec<Object>,
+    sp: usize,
+    frames: Vec<Frame>,
+}
+
+/// Counts the calls to functions bound to globals, and re-compiles those that get hot with
+/// the small functions they call inlined.
+#[derive(Debug)]
+struct Inliner {
+    functions: BTreeMap<usize, GlobalFunction>,
+    /// What is known about each function called so far, by address. Each keeps its function
+    /// alive so the address isn't reused.
+    profiles: BTreeMap<usize, (Rc<CompiledFunction>, Profile)>,
+}
+
+#[derive(Debug)]
+enum Profile {
+    Calls(u32),
+    Optimized(Rc<Closure>),
+    /// Not bound by a top-level `let`, or failed to re-compile.
+    Cold,
+}
+
+impl Inliner {
+    /// Counts a call to `closure`, returning the closure to run instead if it is hot.
+    fn count(
+        &mut self,
+        closure: &Rc<Closure>,
+        constants: &mut Vec<Object>,
+        env: &GlobalEnv,
+    ) -> Option<Rc<Closure>> {
+        let function = &closure.function;
+        let key = Rc::as_ptr(function) as usize;
+        let (_, profile) = self
+            .profiles
+            .entry(key)
+            .or_insert_with(|| (function.clone(), Profile::Calls(0)));
+        match profile {
+            Profile::Optimized(optimized) => return Some(optimized.clone()),
+            Profile::Calls(calls) if *calls + 1 < HOT_CALLS => {
+                *calls += 1;
+                return None;
+            }
+            Profile::Calls(_) => {}
+            Profile::Cold => return None,
+        }
+        let optimized = match closure.free.is_empty() {
+            true => self.recompile(function, constants, env),
+            false => None,
+        };
+        let optimized = optimized.map(|function| {
+            Rc::new(Closure {
+                function,
+                free: vec![],
+            })
+        });
+        let profile = match &optimized {
+            Some(optimized) => Profile::Optimized(optimized.clone()),
+            None => Profile::Cold,
+        };
+        self.profiles.insert(key, (function.clone(), profile));
+        optimized
+    }
+
+    /// Compiles the global function `function` was compiled from again, with inlining.
+    fn recompile(
+        &mut self,
+        function: &Rc<CompiledFunction>,
+        constants: &mut Vec<Object>,
+        env: &GlobalEnv,
+    ) -> Option<Rc<CompiledFunction>> {
+        let global = self.functions.values().find(|global| {
+            matches!(&constants[global.constant],
+                Object::CompiledFunction(compiled) if Rc::ptr_eq(compiled, function))
+        })?;
+        let global = global.clone();
+        let runtime = env.borrow().runtime.clone();
+        let mut compiler = Compiler::new_with_state(global.symbols.clone(), mem::take(constants));
+        compiler.builtins = runtime.builtins.clone();
+        compiler.case_insensitive = runtime.case_insensitive.get();
+        compiler.functions = mem::take(&mut self.functions);
+        let recompiled = compiler.recompile(&global);
+        self.functions = mem::take(&mut compiler.functions);
+        *constants = compiler.constants;
+        recompiled.ok()
+    }
+}
+
+#[derive(Debug)]
+struct Frame {
+    closure: Rc<Closure>,
+    ip: usize,
+    base_pointer: usize,
+    /// The closures `defer` gave this call, in the order they were deferred.
+    deferred: Vec<Rc<Closure>>,
+}
+
+#[derive(Debug)]
+pub struct Vm {
+    constants: Vec<Object>,
+    stack: Vec<Object>,
+    sp: usize,
+    globals: Vec<Object>,
+    frames: Vec<Frame>,
+    returned: Option<Object>,
+    env: GlobalEnv,
+    executed: u64,
+    /// Whether this VM runs a coroutine, which a call to `yield` suspends.
+    coroutine: bool,
+    /// The value passed to `yield`, once it has suspended the coroutine.
+    suspended: Option<Object>,
+    /// Set when the runtime re-compiles hot functions with inlining.
+    inliner: Option<Box<Inliner>>,
+}
+
+impl Vm {
+    pub fn new(bytecode: Bytecode, env: GlobalEnv) -> Self {
+        Self::with_globals(bytecode, vec![], env)
+    }
+
+    pub fn with_globals(bytecode: Bytecode, globals: Vec<Object>, env: GlobalEnv) -> Self {
+        Self {
+            constants: bytecode.constants,
+            stack: vec![Object::Nil; STACK_SIZE],
+            sp: 0,
+            globals,
+            frames: vec![main_frame(bytecode.instructions, 0)],
+            returned: None,
+            env,
+            executed: 0,
+            coroutine: false,
+            suspended: None,
+            inliner: None,
+        }
+    }
+
+    /// Counts calls to the `functions` bound to globals, and once one is hot, runs it
+    /// re-compiled with the small functions it calls inlined.
+    pub fn with_inlining(mut self, functions: BTreeMap<usize, GlobalFunction>) -> Self {
+        self.inliner = Some(Box::new(Inliner {
+            functions,
+            profiles: BTreeMap::new(),
+        }));
+        self
+    }
+
+    /// The value of the last expression statement, or the value of a top-level `return`.
+    pub fn result(&self) -> Object {
+        match &self.returned {
+            Some(value) => Object::Return(Box::new(value.clone())),
+            None => self.stack.get(self.sp).cloned().unwrap_or(Object::Nil),
+        }
+    }
+
+    /// Number of instructions executed so far.
+    pub fn instructions_executed(&self) -> u64 {
+        self.executed
+    }
+
+    pub fn run(&mut self) -> Result<()> {
+        self.run_above(0)
+    }
+
+    /// Runs until the frame above the first `floor` returns. An error unwinds the frames
+    /// above them, calling what each deferred.
+    fn run_above(&mut self, floor: usize) -> Result<()> {
+        let result = self.execute(floor);
+        if result.is_err() {
+            self.unwind(floor);
+        }
+        result
+    }
+
+    fn execute(&mut self, floor: usize) -> Result<()> {
+        loop {
+            let frame = self.frames.last_mut().expect("vm always has a frame");
+            let instructions = &frame.closure.function.instructions;
+            if frame.ip >= instructions.len() {
+                return Ok(());
+            }
+            let op = Opcode::from_byte(instructions[frame.ip])
+                .ok_or_else(|| anyhow!("unknown opcode {}", instructions[frame.ip]))?;
+            let (operands, read) = read_operands(op, &instructions[frame.ip + 1..]);
+            frame.ip += 1 + read;
+            self.executed += 1;
+
+            match op {
+                Opcode::Constant => self.push(self.constants[operands[0]].clone())?,
+                Opcode::Pop => {
+                    self.pop();
+                }
+                Opcode::Add
+                | Opcode::Sub
+                | Opcode::Mul
+                | Opcode::Div
+                | Opcode::Mod
+                | Opcode::Equal
+                | Opcode::NotEqual
+                | Opcode::GreaterThan
+                | Opcode::GreaterEqual
+                | Opcode::LessThan
+                | Opcode::LessEqual => {
+                    let right = self.pop();
+                    let left = self.pop();
+                    let result = match op {
+                        Opcode::Add => left.add(right),
+                        Opcode::Sub => left.sub(right),
+                        Opcode::Mul => left.mul(right),
+                        Opcode::Div => left.div(right),
+                        Opcode::Mod => left.rem(right),
+                        Opcode::Equal => left.eq(right),
+                        Opcode::NotEqual => left.not_eq(right),
+                        Opcode::GreaterThan => left.gt(right),
+                        Opcode::GreaterEqual => left.gte(right),
+                        Opcode::LessThan => left.lt(right),
+                        _ => left.lte(right),
+                    }?;
+                    self.push(result)?;
+                }
+                Opcode::True => self.push(Object::Bool(true))?,
+                Opcode::False => self.push(Object::Bool(false))?,
+                Opcode::Null => self.push(Object::Nil)?,
+                Opcode::Minus => {
+                    let operand = self.pop();
+                    self.push(operand.minus()?)?;
+                }
+                Opcode::Bang => {
+                    let operand = self.pop();
+                    self.push(operand.bang()?)?;
+                }
+                Opcode::JumpNotTruthy => {
+                    let condition = self.pop();
+                    if !condition.is_truthy() {
+                        self.frame_mut().ip = operands[0];
+                    }
+                }
+                Opcode::Jump => {
+                    self.env.borrow().runtime.step()?;
+                    self.frame_mut().ip = operands[0];
+                }
+                Opcode::SetGlobal => {
+                    let value = self.pop();
+                    if self.globals.len() <= operands[0] {
+                        self.globals.resize(operands[0] + 1, Object::Nil);
+                    }
+                    self.globals[operands[0]] = value;
+                }
+                Opcode::GetGlobal => {
+                    let value = self.globals.get(operands[0]).cloned();
+                    self.push(value.unwrap_or(Object::Nil))?;
+                }
+                Opcode::SetLocal => {
+                    let base_pointer = self.frame_mut().base_pointer;
+                    let value = self.pop();
+                    self.stack[base_pointer + operands[0]] = value;
+                }
+                Opcode::GetLocal => {
+                    let base_pointer = self.frame_mut().base_pointer;
+                    self.push(self.stack[base_pointer + operands[0]].clone())?;
+                }
+                Opcode::GetBuiltin => {
+                    let builtin = (self.env.borrow().runtime.builtins)
+                        .get(operands[0])
+                        .ok_or_else(|| anyhow!("unknown builtin {}", operands[0]))?;
+                    self.push(Object::Builtin(builtin))?;
+                }
+                Opcode::GetFree => {
+                    let value = self.frame_mut().closure.free[operands[0]].clone();
+                    self.push(value)?;
+                }
+                Opcode::CurrentClosure => {
+                    let closure = self.frame_mut().closure.clone();
+                    self.push(Object::Closure(closure))?;
+                }
+                Opcode::Array => {
+                    let elements = self.take(operands[0]);
+                    self.push(Object::Array(Shared::new(elements)))?;
+                }
+                Opcode::Hash => {
+                    let mut hash = BTreeMap::new();
+                    let mut entries = self.take(operands[0]).into_iter();
+                    while let (Some(key), Some(value)) = (entries.next(), entries.next()) {
+                        hash.insert(key.hash_key()?, value);
+                    }
+                    self.push(Object::Hash(Shared::new(hash)))?;
+                }
+                Opcode::Index => {
+                    let index = self.pop();
+                    let left = self.pop();
+                    self.push(left.index(index)?)?;
+                }
+                Opcode::Call => {
+                    self.call(operands[0])?;
+                    if self.suspended.is_some() {
+                        return Ok(());
+                    }
+                }
+                Opcode::ReturnValue => {
+                    let value = self.pop();
+                    if self.return_from_frame(value)? || self.frames.len() <= floor {
+                        return Ok(());
+                    }
+                }
+                Opcode::Return => {
+                    if self.return_from_frame(Object::Nil)? || self.frames.len() <= floor {
+                        return Ok(());
+                    }
+                }
+                Opcode::LoopItems => {
+                    let items = self.pop().loop_items(operands[0])?;
+                    self.push(Object::Array(items))?;
+                }
+                Opcode::Iterate => {
+                    let Object::Int(index) = self.stack[self.sp - 1] else {
+                        bail!("loop index is not an int");
+                    };
+                    let (index, width) = (index as usize, operands[1]);
+                    let items = match &self.stack[self.sp - 2] {
+                        Object::Array(items) => items.clone(),
+                        other => return Err(EvalError::NotIterable(other.name()).into()),
+                    };
+                    match items.get(index..index + width) {
+                        Some(values) => {
+                            self.stack[self.sp - 1] = Object::Int((index + width) as Int);
+                            for value in values {
+                                self.push(value.clone())?;
+                            }
+                        }
+                        None => {
+                            self.take(2);
+                            self.frame_mut().ip = operands[0];
+                        }
+                    }
+                }
+                Opcode::Collect => {
+                    let element = self.pop();
+                    let Object::Array(elements) = &mut self.stack[self.sp - 3] else {
+                        bail!("comprehension result is not an array");
+                    };
+                    elements.make_mut().push(element);
+                }
+                Opcode::GuardFunction => {
+                    let value = self.pop();
+                    let inlined = match (&value, &self.constants[operands[1]]) {
+                        (Object::Closure(closure), Object::CompiledFunction(function)) => {
+                            Rc::ptr_eq(&closure.function, function)
+                        }
+                        _ => false,
+                    };
+                    if inlined {
+                        self.frame_mut().ip = operands[0];
+                    }
+                }
+                Opcode::NewCell => {
+                    let value = self.pop();
+                    self.push(Object::Cell(Rc::new(RefCell::new(value))))?;
+                }
+                Opcode::Deref => match self.pop() {
+                    Object::Cell(cell) => self.push(cell.borrow().clone())?,
+                    // A slot whose `let` hasn't run yet still holds nil.
+                    value => self.push(value)?,
+                },
+                Opcode::SetCell => {
+                    let value = self.pop();
+                    let Object::Cell(cell) = self.pop() else {
+                        bail!("assigned variable is not a cell");
+                    };
+                    *cell.borrow_mut() = value.clone();
+                    self.push(value)?;
+                }
+                Opcode::Defer => {
+                    let Object::Closure(closure) = self.pop() else {
+                        bail!("deferred value is not a closure");
+                    };
+                    self.frame_mut().deferred.push(closure);
+                }
+                Opcode::Closure => {
+                    let Object::CompiledFunction(function) = &self.constants[operands[0]] else {
+                        bail!("not a function: {}", self.constants[operands[0]]);
+                    };
+                    let function = function.clone();
+                    let free = self.take(operands[1]);
+                    self.push(Object::Closure(Rc::new(Closure { function, free })))?;
+                }
+            }
+        }
+    }
+
+    fn call(&mut self, num_args: usize) -> Result<()> {
+        let callee = self.stack[self.sp - 1 - num_args].clone();
+        match callee {
+            Object::Closure(closure) => {
+                self.env.borrow().runtime.step()?;
+                let hot = match &mut self.inliner {
+                    Some(inliner) => inliner.count(&closure, &mut self.constants, &self.env),
+                    None => None,
+                };
+                let closure = hot.unwrap_or(closure);
+                let function = &closure.function;
+                let expected = function.num_parameters;
+                if function.rest && num_args < expected {
+                    return Err(EvalError::TooFewArguments {
+                        expected,
+                        got: num_args,
+                    }
+                    .into());
+                }
+                if !function.rest && num_args != expected {
+                    return Err(EvalError::WrongArity {
+                        function: None,
+                        expected,
+                        got: num_args,
+                    }
+                    .into());
+                }
+                // The arguments left over become the array in the rest parameter's slot.
+                let num_args = match function.rest {
+                    true => {
+                        let rest = self.take(num_args - expected);
+                        self.push(Object::Array(Shared::new(rest)))?;
+                        expected + 1
+                    }
+                    false => num_args,
+                };
+                // The main frame isn't a call.
+                if self.frames.len() > self.env.borrow().runtime.limits.max_depth {
+                    return Err(EvalError::StackOverflow.into());
+                }
+                let base_pointer = self.sp - num_args;
+                let sp = base_pointer + function.num_locals;
+                if sp >= STACK_SIZE {
+                    bail!("stack overflow");
+                }
+                self.frames.push(Frame {
+                    closure,
+                    ip: 0,
+                    base_pointer,
+                    deferred: vec![],
+                });
+                self.sp = sp;
+                Ok(())
+            }
+            Object::Builtin(builtin) => {
+                let args = self.take(num_args);
+                self.pop();
+                let result = self.lend(|env| (builtin.func)(args, env))?;
+                let yielded = self.env.borrow().runtime.vm.borrow_mut().yielded.take();
+                match yielded {
+                    Some(value) if self.coroutine => {
+                        self.suspended = Some(value);
+                        Ok(())
+                    }
+                    // Closures a builtin calls back can't suspend the coroutine around it.
+                    Some(_) => bail!("yield outside of a coroutine"),
+                    None => self.push(result),
+                }
+            }
+            object => Err(EvalError::NotAFunction(object.name().to_string()).into()),
+        }
+    }
+
+    /// Runs `f` with this VM's state lent to the runtime, where [`call_closure`] finds it.
+    fn lend<T>(&mut self, f: impl FnOnce(&GlobalEnv) -> Result<T>) -> Result<T> {
+        let lent = Vm {
+            constants: mem::take(&mut self.constants),
+            stack: mem::take(&mut self.stack),
+            sp: self.sp,
+            globals: mem::take(&mut self.globals),
+            frames: vec![main_frame(vec![], self.sp)],
+            returned: None,
+            env: self.env.clone(),
+            executed: 0,
+            coroutine: false,
+            suspended: None,
+            inliner: self.inliner.take(),
+        };
+        let runtime = self.env.borrow().runtime.clone();
+        runtime.vm.borrow_mut().lent = Some(lent);
+        let result = f(&self.env);
+        let lent = runtime.vm.borrow_mut().lent.take();
+        let lent = lent.ok_or_else(|| anyhow!("the vm state lent to a builtin was lost"))?;
+        self.constants = lent.constants;
+        self.stack = lent.stack;
+        self.globals = lent.globals;
+        self.inliner = lent.inliner;
+        self.executed += lent.executed;
+        result
+    }
+
+    /// Calls `closure` above everything on the stack and runs it to its return, leaving the
+    /// stack as it was.
+    fn call_back(&mut self, closure: Rc<Closure>, arguments: Vec<Object>) -> Result<Object> {
+        let base = self.sp;
+        let depth = self.frames.len();
+        let count = arguments.len();
+        self.push(Object::Closure(closure))?;
+        for argument in arguments {
+            self.push(argument)?;
+        }
+        let result = self
+            .call(count)
+            .and_then(|_| self.run_above(depth))
+            .map(|_| self.stack[base].clone());
+        // A failed call leaves its frames and values behind.
+        self.frames.truncate(depth);
+        for slot in &mut self.stack[base..self.sp] {
+            *slot = Object::Nil;
+        }
+        self.sp = base;
+        result
+    }
+
+    /// Continues `coroutine` on this VM's empty stack, leaving it suspended or done.
+    fn resume(&mut self, coroutine: &Coroutine, value: Object) -> Result<Object> {
+        let state = mem::replace(&mut *coroutine.0.borrow_mut(), CoroutineState::Running);
+        let started = match state {
+            CoroutineState::New(closure) => self.start(closure, value),
+            CoroutineState::Suspended(suspended) => {
+                self.stack = suspended.stack;
+                self.sp = suspended.sp;
+                self.frames = suspended.frames;
+                // The value is what the pending `yield` returns.
+                self.push(value)
+            }
+            state => {
+                let done = matches!(state, CoroutineState::Done);
+                *coroutine.0.borrow_mut() = state;
+                match done {
+                    true => bail!("cannot resume a finished coroutine"),
+                    false => bail!("cannot resume a running coroutine"),
+                }
+            }
+        };
+        let (state, result) = match started.and_then(|_| self.run()) {
+            Ok(()) => match self.suspended.take() {
+                Some(value) => {
+                    let suspended = Suspended {
+                        stack: mem::take(&mut self.stack),
+                        sp: self.sp,
+                        frames: mem::take(&mut self.frames),
+                    };
+                    (CoroutineState::Suspended(suspended), Ok(value))
+                }
+                None => (CoroutineState::Done, Ok(self.stack[0].clone())),
+            },
+            Err(err) => (CoroutineState::Done, Err(err)),
+        };
+        *coroutine.0.borrow_mut() = state;
+        result
+    }
+
+    /// Sets up the call to a coroutine's closure, passing it `value` if it takes a parameter.
+    fn start(&mut self, closure: Rc<Closure>, value: Object) -> Result<()> {
+        self.stack = vec![Object::Nil; STACK_SIZE];
+        self.frames = vec![main_frame(vec![], 0)];
+        let takes_value = closure.function.num_parameters > 0;
+        self.push(Object::Closure(closure))?;
+        if takes_value {
+            self.push(value)?;
+        }
+        self.call(usize::from(takes_value))
+    }
+
+    /// Pops the current frame and pushes `value` for the caller. Returns true
+    /// when the main frame itself returned, which ends the program.
+    fn return_from_frame(&mut self, value: Object) -> Result<bool> {
+        if self.frames.len() == 1 {
+            self.returned = Some(value);
+            return Ok(true);
+        }
+        let frame = self.pop_frame();
+        self.call_deferred(frame.deferred)?;
+        self.push(value)?;
+        Ok(false)
+    }
+
+    /// Pops the current frame, clearing its callee, arguments and locals off the stack.
+    fn pop_frame(&mut self) -> Frame {
+        let frame = self.frames.pop().expect("vm always has a frame");
+        for slot in &mut self.stack[frame.base_pointer - 1..self.sp] {
+            *slot = Object::Nil;
+        }
+        self.sp = frame.base_pointer - 1;
+        frame
+    }
+
+    /// Pops the frames an error left above the first `floor`, calling what each deferred.
+    /// Their errors are dropped for the one being unwound.
+    fn unwind(&mut self, floor: usize) {
+        while self.frames.len() > floor.max(1) {
+            let frame = self.pop_frame();
+            let _ = self.call_deferred(frame.deferred);
+        }
+    }
+
+    /// Calls `deferred`, the last first. All of them are called, and the first error returned.
+    fn call_deferred(&mut self, deferred: Vec<Rc<Closure>>) -> Result<()> {
+        let mut first_error = None;
+        for closure in deferred.into_iter().rev() {
+            if let Err(err) = self.call_back(closure, vec![]) {
+                first_error.get_or_insert(err);
+            }
+        }
+        match first_error {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// The index of the statement, of those whose code starts at `starts`, that the main
+    /// frame is running.
+    fn statement(&self, starts: &[usize]) -> usize {
+        let ip = self.frames[0].ip;
+        starts
+            .partition_point(|&start| start < ip)
+            .saturating_sub(1)
+    }
+
+    fn frame_mut(&mut self) -> &mut Frame {
+        self.frames.last_mut().expect("vm always has a frame")
+    }
+
+    fn push(&mut self, object: Object) -> Result<()> {
+        if self.sp >= STACK_SIZE {
+            bail!("stack overflow");
+        }
+        self.stack[self.sp] = object;
+        self.sp += 1;
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Object {
+        self.sp -= 1;
+        self.stack[self.sp].clone()
+    }
+
+    fn take(&mut self, count: usize) -> Vec<Object> {
+        let start = self.sp - count;
+        let values = self.stack[start..self.sp]
+            .iter_mut()
+            .map(|slot| mem::replace(slot, Object::Nil))
+            .collect();
+        self.sp = start;
+        values
+    }
+}
+
+/// A frame for top-level `instructions`, which isn't a call.
+fn main_frame(instructions: Vec<u8>, base_pointer: usize) -> Frame {
+    let main = CompiledFunction {
+        instructions,
+        num_locals: 0,
+        num_parameters: 0,
+        rest: false,
+    };
+    let main = Closure {
+        function: Rc::new(main),
+        free: vec![],
+    };
+    Frame {
+        closure: Rc::new(main),
+        ip: 0,
+        base_pointer,
+        deferred: vec![],
+    }
+}
+
+#[cfg(test)]
+mod vm_tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        environment::Environment,
+        eval::{Engine, EvalError, Program},
+        lexer::Lexer,
+        object::{Object, Shared},
+        parser::Parser,
+        runtime::{Limits, Runtime},
+        token::Int,
+    };
+    use anyhow::Result;
+
+    fn run(engine: Engine, text: &str, env: &crate::environment::GlobalEnv) -> Result<Object> {
+        let mut parser = Parser::new(Lexer::new(text).tokens());
+        Program::with_engine(engine).eval(&mut parser, env.clone())
+    }
+
+    fn vm(text: &str) -> Result<Object> {
+        let env = Rc::new(RefCell::new(Environment::default()));
+        run(Engine::Vm, text, &env)
+    }
+
+    #[test]
+    fn errors_have_the_tree_walkers_codes() {
+        let code = |engine: Engine, text: &str| {
+            let env = Rc::new(RefCell::new(Environment::default()));
+            let err = run(engine, text, &env).unwrap_err();
+            err.downcast_ref::<EvalError>().map(EvalError::code)
+        };
+        for program in [
+            "missing",
+            "missing = 1",
+            "let f = fn() { x = 1 }; f()",
+            "5 + true",
+        ] {
+            let tree = code(Engine::Tree, program);
+            assert!(tree.is_some(), "{program}");
+            assert_eq!(code(Engine::Vm, program), tree, "{program}");
+        }
+    }
+
+    #[test]
+    fn errors_carry_the_statement_span() {
+        for program in [
+            "let a = 1;\nlet f = fn(x) {\n  x / 0\n};\n  f(a)",
+            "let a = 1;\nlet b = 2;\n  a + c",
+            "let a = 1;\n\nmissing = a",
+            "let a = 1;\nmap([1], fn(x) { x + true })",
+        ] {
+            let message = |engine: Engine| {
+                let env = Rc::new(RefCell::new(Environment::default()));
+                format!("{:#}", run(engine, program, &env).unwrap_err())
+            };
+            let tree = message(Engine::Tree);
+            assert!(tree.starts_with("line "), "{tree}");
+            assert_eq!(message(Engine::Vm), tree, "{program}");
+        }
+    }
+
+    #[test]
+    fn deferred_calls_match_tree_walker() {
+        for program in [
+            "let b = buffer(); let f = fn() { defer push(b, 1); defer push(b, 2); push(b, 0) }; f(); str(b)",
+            // They run as an error unwinds each frame, and the first error is the one reported.
+            "let b = buffer(); let g = fn() { defer push(b, 1); 1 / 0 }; \
+             let f = fn() { defer push(b, 2); g() }; map([1], fn(x) { defer push(b, 3); f() })",
+            "let f = fn() { defer 1 / 0; defer missing; 5 }; f()",
+            "let b = buffer(); defer push(b, 1); let f = fn() { defer push(b, 2); 5 }; [f(), str(b)]",
+            // A builtin calling a closure back runs that closure's deferred calls too.
+            "let b = buffer(); map([1, 2], fn(x) { defer push(b, x); x }); str(b)",
+        ] {
+            let env = Rc::new(RefCell::new(Environment::default()));
+            let tree = run(Engine::Tree, program, &env).map_err(|err| err.root_cause().to_string());
+            let vm = vm(program).map_err(|err| err.root_cause().to_string());
+            assert_eq!(vm, tree, "{program}");
+        }
+    }
+
+    #[test]
+    fn closures_share_assigned_variables() {
+        for program in [
+            "let f = fn(n) { fn() { n = n + 1 } }; let g = f(0); g(); g()",
+            "let f = fn() { let n = 0; let inc = fn() { n = n + 1 }; inc(); inc(); n }; f()",
+            // Each call makes its own cell, and each closure keeps the one it was made with.
+            "let f = fn(n) { [fn() { n = n + 1 }, fn() { n }] }; let a = f(0); let b = f(10); \
+             a[0](); a[0](); b[0](); [a[1](), b[1]()]",
+            "let fs = []; for (x in [1, 2]) { fs = push(fs, fn() { x = x + 1 }) }; \
+             [fs[0](), fs[0](), fs[1]()]",
+            "let f = fn() { let total = 0; for (x in [1, 2, 3]) { map([x], fn(y) { total = total + y }) }; total }; f()",
+        ] {
+            let env = Rc::new(RefCell::new(Environment::default()));
+            let tree = run(Engine::Tree, program, &env).unwrap();
+            assert_eq!(vm(program).unwrap(), tree, "{program}");
+        }
+    }
+
+    #[test]
+    fn inlining_matches_tree_walker() {
+        let inlined = |text: &str| {
+            let env = Rc::new(RefCell::new(Environment::default()));
+            let mut parser = Parser::new(Lexer::new(text).tokens());
+            Program::with_engine(Engine::Vm)
+                .inline_hot(true)
+                .eval(&mut parser, env)
+        };
+        for program in [
+            "let add = fn(a, b) { a + b }; let less = fn(a, b) { a < b }; \
+             let fib = fn(n) { if (less(n, 2)) { n } else { add(fib(n - 1), fib(n - 2)) } }; fib(15)",
+            // Once the global is rebound, the guard makes the call instead.
+            "let add = fn(a, b) { a + b }; let sum = fn(n) { add(n, 1) }; let i = 0; let total = 0; \
+             while (i < 100) { total = total + sum(i); i = i + 1 }; \
+             add = fn(a, b) { a * b }; [total, sum(5)]",
+            // The arguments are evaluated once each, in order, whatever order the body reads them in.
+            "let log = []; let note = fn(x) { log = push(log, x); x }; let sub = fn(a, b) { b - a }; \
+             let f = fn(i) { sub(note(i), note(-i)) }; let i = 0; while (i < 100) { f(i); i = i + 1 }; \
+             [f(3), len(log), log[len(log) - 2], log[len(log) - 1]]",
+            // A global the body reads is shadowed where it would be inlined.
+            "let k = 10; let plus = fn(x) { x + k }; let f = fn(n) { let k = 1; plus(n) + k }; \
+             let i = 0; let total = 0; while (i < 100) { total = total + f(i); i = i + 1 }; total",
+            "let size = fn(xs) { len(xs) + 1 }; let f = fn(n) { size([n, n]) * -size([]) }; \
+             let i = 0; let total = 0; while (i < 100) { total = total + f(i); i = i + 1 }; total",
+        ] {
+            let env = Rc::new(RefCell::new(Environment::default()));
+            let tree = run(Engine::Tree, program, &env).unwrap();
+            assert_eq!(inlined(program).unwrap(), tree, "{program}");
+        }
+    }
+
+    #[test]
+    fn matches_tree_walker() {
+        let programs = [
+            "1 + 2 * 3 - 4 / 2",
+            "17 % 5 * 2",
+            "1.5 * 2 + 7 / 2.0 - 1",
+            "2.5 > 2 == (1 == 1.0)",
+            "-5 + 10",
+            "!true == false",
+            "1 < 2 == 2 >= 2",
+            "if (1 > 2) { 10 }",
+            "if (nil) { 1 } else { 2 }",
+            "if (true) { let a = 1; }",
+            r#""mon" + "key""#,
+            "let a = 5; let b = a * 2; [a, b, a + b]",
+            r#"{"one": 1, 2: "two", true: [3]}"#,
+            r#"{"one": 1}["one"] + [1, 2, 3][2]"#,
+            "let a = 1;",
+            "return 10; 9;",
+            "9; return 2 * 5; 9;",
+            "let f = fn() { return 1; 2 }; f() + 1",
+            "let f = fn(a, b) { let c = a + b; c * 2 }; f(1, 2)",
+            "let f = fn() { }; f()",
+            "let adder = fn(x) { fn(y) { x + y } }; adder(2)(3)",
+            "let fib = fn(n) { if (n < 2) { n } else { fib(n - 1) + fib(n - 2) } }; fib(15)",
+            "let outer = fn() { let count = fn(n) { if (n == 0) { 0 } else { count(n - 1) } }; count(3) }; outer()",
+            r#"len("four") + len([1, 2]) + first([5]) + last([6])"#,
+            "rest([1, 2, 3])",
+            "let len = fn(x) { 42 }; len([])",
+            "let a = [1]; let b = push(a, 2); [len(a), len(b)]",
+            "fn(a, b, c) { fn() { fn() { a + b + c } } }(1, 2, 3)()()",
+            "let i = 0; let b = buffer(); while (i < 3) { push(b, i); let i = i + 1; } str(b)",
+            "let f = fn() { let n = 0; while (true) { let n = n + 1; if (n == 5) { return n; } } }; f()",
+            "while (false) { 1 }",
+            "let total = 0; for (x in [1, 2, 3]) { total = total + x; } total",
+            "let x = 10; for (x in range(0, 3)) { x } x",
+            "let f = fn(items) { for (item in items) { if (item > 1) { return item; } } nil }; f([1, 5, 2])",
+            "let f = fn() { let fs = []; for (i in range(0, 3)) { fs = push(fs, fn() { i }); } fs[0]() + fs[2]() }; f()",
+            "let n = 0; while (true) { n = n + 1; if (n == 3) { break } } n",
+            "let odd = []; for (x in range(0, 6)) { if (x % 2 == 0) { continue } odd = push(odd, x); } odd",
+            "let f = fn() { let found = nil; for (x in [4, 7, 9]) { if (x > 5) { found = x; break; } } found }; f()",
+            "let n = 0; for (a in [1, 2, 3]) { for (b in [1, 2, 3]) { if (b == 2) { break } n = n + 1; } if (a == 2) { continue } n = n + 10; } n",
+            "let pairs = []; for (a in [1, 2]) { for (b in [3, 4]) { pairs = push(pairs, a * b); } } pairs",
+            "let f = fn(first, ...rest) { [first, rest, len(rest)] }; [f(1), f(1, 2, 3)]",
+            "let count = fn(...xs) { let n = 0; for (x in xs) { n = n + x } n }; count(1, 2, 3) + count()",
+            "let wrap = fn(f) { fn(...args) { f(args) } }; wrap(len)(1, 2)",
+            r#"let keys = []; for (k in {"b": 2, "a": 1}) { keys = push(keys, k) } keys"#,
+            r#"let f = fn(h) { let out = []; for (k, v in h) { if (v == 2) { continue } out = push(out, k + str(v)); } out }; f({"a": 1, "b": 2, "c": 3})"#,
+            r#"let total = 0; for (i, x in [5, 6, 7]) { if (i == 2) { break } total = total + i * x; } total"#,
+            "[x * x for x in range(0, 6) if x % 2 == 0]",
+            r#"let f = fn(h) { [k + str(v) for k, v in h if v > 1] }; f({"a": 1, "b": 2, "c": 3})"#,
+            "let x = 10; let ys = [x * 2 for x in [1, 2] if x > 1]; [ys, x]",
+            "let f = fn(rows) { [[x * y for x in row] for y, row in rows] }; f([[1, 2], [3]])",
+            "let x = 5; let f = fn() { let xs = [fn() { x } for x in [1, 2]]; [xs[0](), x] }; f()",
+            "map([1, 2, 3], fn(x) { x * 2 })",
+            r#"map(["a", "bc"], len)"#,
+            "let k = 3; let f = fn(xs) { filter(xs, fn(x) { x % k == 0 }) }; f(range(0, 10))",
+            "reduce([[1], [2, 3]], 0, fn(acc, xs) { reduce(xs, acc, fn(a, x) { a + x }) })",
+            "let n = 0; map([1, 2], fn(x) { n = n + x }); n",
+            "let f = fn(xs) { let total = 0; for (x in map(xs, fn(x) { [x, x * x] })) { total = total + x[1] } total }; f([1, 2, 3])",
+            "let x = 1; x = x + 1; x",
+            "let a = 0; let b = 0; a = b = 3; a * b",
+            "let n = 0; let inc = fn() { n = n + 1 }; inc(); inc()",
+            "let f = fn() { let n = 1; n = n * 5; n }; f()",
+        ];
+        for program in programs {
+            let env = Rc::new(RefCell::new(Environment::default()));
+            let expected = run(Engine::Tree, program, &env).unwrap();
+            let env = Rc::new(RefCell::new(Environment::default()));
+            let actual = run(Engine::Vm, program, &env).unwrap();
+            assert_eq!(actual.to_string(), expected.to_string(), "{program}");
+        }
+    }
+
+    #[test]
+    fn coroutines() {
+        let value = |text: &str| vm(text).unwrap().to_string();
+        assert_eq!(
+            value("let co = coroutine(fn() { yield(1); yield(2); 3 }); [resume(co, nil), resume(co, nil), resume(co, nil)]"),
+            "[1, 2, 3]"
+        );
+        // resume hands its value to the closure first, then to the pending yield.
+        assert_eq!(
+            value("let sum = coroutine(fn(total) { while (true) { total = total + yield(total) } }); resume(sum, 1); resume(sum, 2); resume(sum, 10)"),
+            "13"
+        );
+        // Yielding from a nested call suspends every frame of the coroutine.
+        assert_eq!(
+            value("let each = fn(xs) { for (x in xs) { yield(x * 10) } }; let co = coroutine(fn() { each([1, 2]); nil }); [resume(co, nil), resume(co, nil), resume(co, nil)]"),
+            "[10, 20, nil]"
+        );
+        // Coroutines take turns and share the globals.
+        assert_eq!(
+            value(
+                r#"let log = []; let worker = fn(name) { fn() { for (i in [1, 2]) { log = push(log, name + str(i)); yield(nil) } } }; let a = coroutine(worker("a")); let b = coroutine(worker("b")); resume(a, nil); resume(b, nil); resume(a, nil); resume(b, nil); log"#
+            ),
+            r#"["a1", "b1", "a2", "b2"]"#
+        );
+
+        let err = |text: &str| vm(text).unwrap_err().root_cause().to_string();
+        assert_eq!(
+            err("let co = coroutine(fn() { 1 }); resume(co, nil); resume(co, nil)"),
+            "cannot resume a finished coroutine"
+        );
+        assert_eq!(
+            err("let co = coroutine(fn() { resume(co, nil) }); resume(co, nil)"),
+            "cannot resume a running coroutine"
+        );
+        assert_eq!(
+            err("let co = coroutine(fn() { 1 + true }); resume(co, nil)"),
+            "type mismatch: 1 + true"
+        );
+        assert_eq!(err("yield(1)"), "yield outside of a coroutine");
+        assert_eq!(
+            err("let co = coroutine(fn() { map([1], fn(x) { yield(x) }) }); resume(co, nil)"),
+            "yield outside of a coroutine"
+        );
+        assert_eq!(
+            err("coroutine(1)"),
+            "argument to coroutine must be a function, got int"
+        );
+        assert_eq!(
+            err("resume(1, nil)"),
+            "argument to resume must be a coroutine, got int"
+        );
+
+        // A coroutine that fails is finished.
+        let env = Rc::new(RefCell::new(Environment::default()));
+        let failing = "let co = coroutine(fn() { 1 + true }); resume(co, nil)";
+        assert!(run(Engine::Vm, failing, &env).is_err());
+        let err = run(Engine::Vm, "resume(co, nil)", &env).unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            "cannot resume a finished coroutine"
+        );
+
+        let err = run(Engine::Tree, "coroutine(fn() { 1 })", &env).unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            "coroutines are not supported by the tree-walker"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let err = |text: &str| vm(text).unwrap_err().root_cause().to_string();
+        assert_eq!(err("5 + true"), "type mismatch: 5 + true");
+        assert_eq!(err("foobar"), "identifier not found: foobar");
+        assert_eq!(err("5(1)"), "not a function: int");
+        assert_eq!(err("1 % 0"), "division by zero");
+        assert_eq!(
+            err("let n = 2; while (true) { n = n * n }"),
+            format!("integer overflow: {0} * {0}", Int::pow(2, Int::BITS / 2))
+        );
+        assert_eq!(err("len(1)"), "argument to len not supported, got int");
+        assert_eq!(
+            err("fn(a) { a }()"),
+            "wrong number of arguments: expected 1, got 0"
+        );
+        assert_eq!(
+            err("fn(a, b, ...rest) { a }(1)"),
+            "wrong number of arguments: expected at least 2, got 1"
+        );
+        assert_eq!(err("for (x in 1) { x }"), "cannot iterate over int");
+        assert_eq!(err("break"), "break outside of a loop");
+        assert_eq!(
+            err("while (true) { [if (x) { break } for x in [true]] }"),
+            "break outside of a loop"
+        );
+        assert_eq!(err("[x for x in 1]"), "cannot iterate over int");
+        assert_eq!(
+            err("while (true) { fn() { continue } }"),
+            "continue outside of a loop"
+        );
+        assert_eq!(err("x = 1"), "cannot assign to undeclared identifier: x");
+        assert_eq!(
+            err("map([1], fn(x) { x + true })"),
+            "type mismatch: 1 + true"
+        );
+        assert_eq!(
+            err("reduce([1], 0, fn(acc) { acc })"),
+            "wrong number of arguments: expected 1, got 2"
+        );
+        assert_eq!(
+            err("let f = fn(n) { f(n) }; f(1)"),
+            "stack overflow: max recursion depth exceeded"
+        );
+    }
+
+    #[test]
+    fn callbacks_count_towards_max_depth() {
+        // Each callback from a builtin recurses on the Rust stack, which is small in tests.
+        let runtime = Runtime {
+            limits: Limits {
+                max_depth: 50,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let env = Rc::new(RefCell::new(Environment::with_runtime(runtime)));
+        let err = run(Engine::Vm, "let f = fn(n) { map([n], f) }; f(1)", &env).unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            "stack overflow: max recursion depth exceeded"
+        );
+        // The VM that called back is left as it was.
+        assert_eq!(
+            run(
+                Engine::Vm,
+                "reduce(range(0, 4), 0, fn(a, x) { a + x })",
+                &env
+            )
+            .unwrap()
+            .to_string(),
+            "6"
+        );
+    }
+
+    #[test]
+    fn globals_persist_between_programs() {
+        let env = Rc::new(RefCell::new(Environment::default()));
+        run(Engine::Vm, "let a = 2; let double = fn(x) { x * 2 };", &env).unwrap();
+        assert!(run(Engine::Vm, "missing", &env).is_err());
+        assert_eq!(
+            run(Engine::Vm, "double(a) + 1", &env).unwrap(),
+            Object::Int(5)
+        );
+        assert_eq!(
+            run(Engine::Vm, r#"[a, "a"]"#, &env).unwrap(),
+            Object::Array(Shared::new(vec![
+                Object::Int(2),
+                Object::String("a".into())
+            ]))
+        );
+    }
+}
@@ -0,0 +1,35 @@
+//! Runs the programs in `conformance/` on every engine and checks them against their
+//! `.out` files, so the backends cannot drift apart.
+
+#![cfg(all(feature = "std", feature = "vm"))]
+
+use std::path::Path;
+
+use rustmonk::{conformance, eval::Engine};
+
+fn check(engine: Engine) {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("conformance");
+    let results = conformance::run(&dir, engine).unwrap();
+    assert!(!results.is_empty());
+    let failures: Vec<String> = results
+        .iter()
+        .filter(|result| !result.passed())
+        .map(|result| {
+            format!(
+                "{} on {engine:?}: expected {:?}, got {:?}",
+                result.name, result.expected, result.actual
+            )
+        })
+        .collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn tree_walker_conforms() {
+    check(Engine::Tree);
+}
+
+#[test]
+fn vm_conforms() {
+    check(Engine::Vm);
+}
@@ -0,0 +1,3 @@
+let xs = [1, 2 * 3, "four"];
+let h = {"a": xs[0], true: nil};
+h["a"];
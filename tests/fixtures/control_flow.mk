@@ -0,0 +1,3 @@
+let i = 0;
+while (i < 3) { let i = i + 1; }
+if (i >= 3) { i } else { -1 }
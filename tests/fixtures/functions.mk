@@ -0,0 +1,5 @@
+// Functions are values and can be passed around.
+let add = fn(a, b) { a + b };
+let apply = fn(f, x) { return f(x, x); }; /* f takes two arguments */
+apply(add, 3);
+let sum = fn(first, ...rest) { first + len(rest) };
@@ -0,0 +1,4 @@
+let five = 5;
+let name = "monkey";
+let flag = !true;
+let sum = five + 10 * 2;
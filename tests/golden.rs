@@ -0,0 +1,56 @@
+//! Parses every `tests/fixtures/*.mk` file and compares the pretty-printed AST, followed by
+//! any parse errors, with the neighbouring `.golden` file.
+//!
+//! Run with `UPDATE_GOLDEN=1 cargo test --test golden` to rewrite the golden files.
+
+use std::{env, fmt::Write, fs, path::Path};
+
+use rustmonk::{lexer::Lexer, parser::Parser};
+
+fn render(source: &str) -> String {
+    let mut parser = Parser::new(Lexer::new(source).tokens());
+    let mut out = String::new();
+    for statement in parser.by_ref().flatten() {
+        writeln!(out, "{statement:#?}").unwrap();
+    }
+    for err in parser.errors() {
+        writeln!(out, "error: {err}").unwrap();
+    }
+    out
+}
+
+#[test]
+fn parser_output_matches_golden_files() {
+    let update = env::var_os("UPDATE_GOLDEN").is_some();
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut fixtures: Vec<_> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "mk"))
+        .collect();
+    fixtures.sort();
+    assert!(!fixtures.is_empty(), "no fixtures in {}", dir.display());
+
+    let mut mismatches = vec![];
+    for fixture in fixtures {
+        let actual = render(&fs::read_to_string(&fixture).unwrap());
+        let golden = fixture.with_extension("golden");
+        if update {
+            fs::write(&golden, &actual).unwrap();
+            continue;
+        }
+        let expected =
+            fs::read_to_string(&golden).unwrap_or_else(|err| panic!("{}: {err}", golden.display()));
+        if actual != expected {
+            mismatches.push(format!(
+                "{}:\n--- expected\n{expected}\n+++ actual\n{actual}",
+                golden.display()
+            ));
+        }
+    }
+    assert!(
+        mismatches.is_empty(),
+        "{}\nrerun with UPDATE_GOLDEN=1 to accept the new output",
+        mismatches.join("\n")
+    );
+}